@@ -449,6 +449,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 text_color: None,
                 buttons: vec![],
             },
+
+            // Remember size, position and zoom between runs under this key
+            // None = always start with the options above
+            state_key: None,
+
+            // Centre on the monitor, or over a parent window's rectangle
+            // Ignored when x/y are set
+            center: None,
+
+            // Monitor to open on, by index or name
+            // Example: Some(MonitorSelector::Name("DP-1".to_string()))
+            monitor: None,
+
+            // Size as a percentage of the monitor's work area
+            width_percent: None,
+            height_percent: None,
+
+            // Size constraints in logical pixels
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
         };
     }
 
@@ -475,6 +497,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Allow JavaScript to show system notifications
         allow_notifications: true,
+
+        // Forward console.log/warn/error output as ViewerEvent::Console
+        capture_console: false,
+
+        // Treat uncaught JS errors as ViewerExitReason::Error (useful in CI)
+        fail_on_page_error: false,
+
+        // Keep this many refreshed versions to scrub back through
+        // None shows only the latest content
+        refresh_history: None,
     };
 
     // ----------------------------------------------------------------------------
@@ -120,6 +120,10 @@ fn handle_viewer_error(err: ViewerError) {
             eprintln!(" Error: Command failed error");
             eprintln!(" Details: {}", msg)
         }
+        ViewerError::LoadFailed(msg) => {
+            eprintln!(" Error: Content failed to load");
+            eprintln!(" Details: {}", msg)
+        }
         ViewerError::RefreshNotSupported(msg) => {
             eprintln!(" Error: Refresh not supported error");
             eprintln!(" Details: {}", msg)
@@ -0,0 +1,50 @@
+//! Refresh a live plot, keeping its history.
+//!
+//! Each refresh draws one more point of a noisy curve. Once the run ends,
+//! drag the timeline under the plot to scrub back through the earlier
+//! versions, and pin one to compare it with another.
+//!
+//! Run with: `cargo run --example refresh_history`
+
+use html_view::{ViewerOptions, ViewerResult};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// An SVG polyline through `values`, scaled to fit 400 by 200 pixels.
+fn plot(step: usize, values: &[f64]) -> String {
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| format!("{},{:.1}", i * 8, 190.0 - value * 180.0))
+        .collect();
+    format!(
+        r#"<h2 style="font-family: sans-serif">Step {step}</h2>
+<svg width="400" height="200" style="border: 1px solid #ccc">
+<polyline points="{}" fill="none" stroke="steelblue" stroke-width="2"/>
+</svg>"#,
+        points.join(" ")
+    )
+}
+
+fn main() -> Result<(), html_view::ViewerError> {
+    let result = ViewerOptions::new()
+        .non_blocking()
+        .refresh_history(50)
+        .title("Refresh history")
+        .show_html(plot(0, &[]))?;
+    let ViewerResult::NonBlocking(mut handle) = result else {
+        unreachable!("the viewer was opened non-blocking");
+    };
+
+    let mut values = Vec::new();
+    for step in 1..=40 {
+        let x = step as f64 / 40.0;
+        values.push((x * x + 0.1 * (step as f64 * 1.7).sin()).clamp(0.0, 1.0));
+        handle.refresh_html(plot(step, &values))?;
+        sleep(Duration::from_millis(250));
+    }
+
+    let status = handle.wait()?;
+    println!("Viewer exited: {:?}", status.reason);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Stream a level meter and a training curve at display rate.
+//!
+//! Frames are sent far faster than the screen refreshes; the viewer only
+//! applies the latest one on each animation frame and reports how many it
+//! dropped along the way.
+//!
+//! Run with: `cargo run --example stream_demo`
+
+use html_view::{ViewerEvent, ViewerOptions, ViewerResult};
+use std::thread::sleep;
+use std::time::Duration;
+
+const PAGE: &str = r#"<div style="font-family: sans-serif">
+<h2>Level</h2>
+<meter id="level" min="0" max="1" style="width: 400px"></meter>
+<h2>Loss</h2>
+<svg width="400" height="200" style="border: 1px solid #ccc">
+<polyline id="loss" fill="none" stroke="steelblue" stroke-width="2"/>
+</svg>
+<p id="step"></p>
+</div>
+<script>
+htmlView.onFrame(function (frame) {
+    document.getElementById('level').value = frame.level;
+    document.getElementById('step').textContent = 'Step ' + frame.step;
+    document.getElementById('loss').setAttribute('points', frame.loss
+        .map(function (loss, i) { return (i * 400 / 2000) + ',' + (190 - loss * 180); })
+        .join(' '));
+});
+</script>"#;
+
+fn main() -> Result<(), html_view::ViewerError> {
+    let result = ViewerOptions::new()
+        .non_blocking()
+        .title("Streaming")
+        .show_html(PAGE)?;
+    let ViewerResult::NonBlocking(mut handle) = result else {
+        unreachable!("the viewer was opened non-blocking");
+    };
+
+    let stream = handle.stream()?;
+    let mut loss = Vec::new();
+    for step in 0..2000 {
+        let t = step as f64 / 1000.0;
+        let level = ((t * 40.0).sin() * (t * 3.0).cos()).abs();
+        loss.push((1.0 / (1.0 + 5.0 * t) + 0.05 * (t * 90.0).sin()).clamp(0.0, 1.0));
+        stream.send_data(&serde_json::json!({ "step": step, "level": level, "loss": loss }))?;
+        sleep(Duration::from_millis(2));
+
+        for event in handle.poll_events()? {
+            if let ViewerEvent::StreamStats {
+                applied,
+                dropped,
+                fps,
+            } = event
+            {
+                println!("{applied} applied, {dropped} dropped, {fps:.0} fps");
+            }
+        }
+    }
+
+    let status = handle.wait()?;
+    println!("Viewer exited: {:?}", status.reason);
+    Ok(())
+}
@@ -51,6 +51,10 @@ pub enum ViewerError {
     #[error("command failed: {0}")]
     CommandFailed(String),
 
+    /// The viewer could not load its content.
+    #[error("content failed to load: {0}")]
+    LoadFailed(String),
+
     /// Refresh not supported (old viewer or wrong mode).
     #[error("refresh not supported: {0}")]
     RefreshNotSupported(String),
@@ -83,6 +87,7 @@ impl Clone for ViewerError {
                 timeout_secs: *timeout_secs,
             },
             ViewerError::CommandFailed(err) => ViewerError::CommandFailed(err.clone()),
+            ViewerError::LoadFailed(err) => ViewerError::LoadFailed(err.clone()),
             ViewerError::RefreshNotSupported(err) => ViewerError::RefreshNotSupported(err.clone()),
         }
     }
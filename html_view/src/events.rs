@@ -0,0 +1,118 @@
+use crate::ViewerError;
+use html_view_shared::ViewerEvent;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Incremental reader for the JSON-lines event file written by the viewer.
+#[derive(Debug)]
+pub(crate) struct EventReader {
+    /// Path to the event file.
+    path: PathBuf,
+
+    /// Number of bytes of the event file already read.
+    offset: u64,
+}
+
+impl EventReader {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self { path, offset: 0 }
+    }
+
+    /// Read the complete lines appended since the last read.
+    ///
+    /// A partially written last line is left for the next read.
+    pub(crate) fn read(&mut self) -> Result<Vec<ViewerEvent>, ViewerError> {
+        let mut file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+
+        let Some(end) = data.iter().rposition(|b| *b == b'\n') else {
+            return Ok(Vec::new());
+        };
+
+        let mut events = Vec::new();
+        for line in data[..end].split(|b| *b == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+            let event: ViewerEvent = serde_json::from_slice(line).map_err(|e| {
+                ViewerError::InvalidResponse(format!("Failed to parse viewer event: {}", e))
+            })?;
+
+            #[cfg(feature = "log")]
+            log_page_event(&event);
+
+            events.push(event);
+        }
+        self.offset += end as u64 + 1;
+
+        Ok(events)
+    }
+}
+
+/// Forward page console output and errors to the `log` crate.
+#[cfg(feature = "log")]
+fn log_page_event(event: &ViewerEvent) {
+    use html_view_shared::ConsoleLevel;
+
+    const TARGET: &str = "html_view::page";
+
+    match event {
+        ViewerEvent::Console { level, message } => {
+            let level = match level {
+                ConsoleLevel::Debug => log::Level::Debug,
+                ConsoleLevel::Log | ConsoleLevel::Info => log::Level::Info,
+                ConsoleLevel::Warn => log::Level::Warn,
+                ConsoleLevel::Error => log::Level::Error,
+            };
+            log::log!(target: TARGET, level, "{}", message);
+        }
+        ViewerEvent::PageError {
+            kind,
+            message,
+            source,
+            line,
+            ..
+        } => {
+            log::error!(
+                target: TARGET,
+                "{:?}: {} ({}:{})",
+                kind,
+                message,
+                source.as_deref().unwrap_or("<unknown>"),
+                line.unwrap_or(0)
+            );
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_event_reader_skips_partial_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let mut reader = EventReader::new(path.clone());
+
+        // Missing file means no events yet
+        assert!(reader.read().unwrap().is_empty());
+
+        let mut file = std::fs::File::create(&path).unwrap();
+        write!(file, "{{\"type\":\"window_shown\"}}\n{{\"type\":\"lo").unwrap();
+        assert_eq!(reader.read().unwrap(), vec![ViewerEvent::WindowShown]);
+
+        writeln!(file, "aded\",\"url\":\"about:blank\"}}").unwrap();
+        let events = reader.read().unwrap();
+        assert!(matches!(events.as_slice(), [ViewerEvent::Loaded { .. }]));
+        assert!(reader.read().unwrap().is_empty());
+    }
+}
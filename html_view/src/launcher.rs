@@ -1,10 +1,17 @@
-use crate::{AppLocator, ViewerError, ViewerHandle, ViewerOptions, ViewerResult, ViewerWaitMode};
+use crate::result::IpcPaths;
+use crate::{
+    AppLocator, ViewerError, ViewerHandle, ViewerOptions, ViewerResult, ViewerWaitMode, WaitUntil,
+};
 use html_view_shared::{PROTOCOL_VERSION, ViewerExitReason, ViewerExitStatus, ViewerRequest};
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// How long a non-blocking open waits for the requested startup stage.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// RAII guard for temporary directory cleanup.
 /// Ensures the directory is removed when this guard is dropped, even on panics.
 struct TempDirGuard {
@@ -83,6 +90,8 @@ pub(crate) fn launch_viewer(
     let result_path = temp_dir.path().join("result.json");
     let command_path = temp_dir.path().join("commands.json");
     let response_path = temp_dir.path().join("command_responses.json");
+    let event_path = temp_dir.path().join("events.jsonl");
+    let stream_path = temp_dir.path().join("stream.json");
 
     // Create request with command path
     let request = ViewerRequest {
@@ -93,6 +102,8 @@ pub(crate) fn launch_viewer(
         environment: options.environment,
         dialog: options.dialog,
         command_path: Some(command_path.clone()),
+        event_path: Some(event_path.clone()),
+        stream_path: Some(stream_path.clone()),
     };
 
     // Write config file
@@ -138,6 +149,10 @@ pub(crate) fn launch_viewer(
             // Read result file
             let result = read_result_file(&result_path, id)?;
 
+            // Forward any page output to the log before the events are discarded
+            #[cfg(feature = "log")]
+            let _ = crate::events::EventReader::new(event_path).read();
+
             // Temp directory will be automatically cleaned up when temp_dir is dropped
 
             // Check exit code
@@ -157,14 +172,27 @@ pub(crate) fn launch_viewer(
         ViewerWaitMode::NonBlocking => {
             // Transfer cleanup responsibility to the handle
             temp_dir.disable_cleanup();
-            let handle = ViewerHandle::new(
+            let mut handle = ViewerHandle::new(
                 id,
                 child,
                 result_path,
                 temp_dir.path().clone(),
-                Some(command_path),
-                Some(response_path),
+                IpcPaths {
+                    command_path: Some(command_path),
+                    response_path: Some(response_path),
+                    event_path: Some(event_path),
+                    stream_path: Some(stream_path),
+                },
             );
+
+            // Optionally wait for the window or content to be ready
+            if options.wait_until != WaitUntil::Spawned
+                && let Err(e) = handle.wait_for_stage(options.wait_until, STARTUP_TIMEOUT)
+            {
+                let _ = handle.terminate();
+                return Err(e);
+            }
+
             Ok(ViewerResult::NonBlocking(handle))
         }
     }
@@ -317,6 +345,9 @@ fn read_result_file(path: &PathBuf, expected_id: Uuid) -> Result<ViewerExitStatu
             id: expected_id,
             reason: ViewerExitReason::ClosedByUser,
             viewer_version: PROTOCOL_VERSION.to_string(),
+            geometry: None,
+            audio_selection: None,
+            slide: None,
         });
     }
 
@@ -350,6 +381,68 @@ mod tests {
         }
     }
 
+    /// Write an executable shell script that stands in for the viewer binary.
+    #[cfg(unix)]
+    fn fake_viewer(dir: &std::path::Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("fake_viewer.sh");
+        fs::write(
+            &path,
+            format!(
+                "#!/bin/sh\nevents=\"$(dirname \"$2\")/events.jsonl\"\n{}\n",
+                body
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_launcher_waits_for_window_shown() {
+        let dir = tempfile::tempdir().unwrap();
+        let locator = MockAppLocator {
+            path: Some(fake_viewer(
+                dir.path(),
+                r#"echo '{"type":"window_shown"}' >> "$events"; sleep 5"#,
+            )),
+        };
+
+        let mut options = ViewerOptions::inline_html("<h1>Test</h1>");
+        options.wait = ViewerWaitMode::NonBlocking;
+        options.wait_until = WaitUntil::WindowShown;
+
+        match launch_viewer(options, &locator) {
+            Ok(ViewerResult::NonBlocking(mut handle)) => {
+                let events = handle.poll_events().unwrap();
+                assert_eq!(events, vec![html_view_shared::ViewerEvent::WindowShown]);
+                handle.terminate().unwrap();
+            }
+            other => panic!("Expected NonBlocking handle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_launcher_reports_load_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let locator = MockAppLocator {
+            path: Some(fake_viewer(
+                dir.path(),
+                r#"echo '{"type":"load_failed","url":null,"error":"missing"}' >> "$events"; sleep 5"#,
+            )),
+        };
+
+        let mut options = ViewerOptions::inline_html("<h1>Test</h1>");
+        options.wait = ViewerWaitMode::NonBlocking;
+        options.wait_until = WaitUntil::ContentLoaded;
+
+        let result = launch_viewer(options, &locator);
+        assert!(matches!(result, Err(ViewerError::LoadFailed(message)) if message == "missing"));
+    }
+
     #[test]
     fn test_launcher_binary_not_found() {
         let options = ViewerOptions::inline_html("<h1>Test</h1>");
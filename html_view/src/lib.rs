@@ -17,29 +17,71 @@
 //! # Features
 //!
 //! - Display inline HTML, local files, directories, or remote URLs
+//! - Render Markdown with tables, task lists and highlighted code
+//! - View images from files or memory with zoom, pan and a pixel readout
+//! - Serve an HTML page and its assets entirely from memory
+//! - Serve an HTML application straight out of a zip or tar(.gz) archive
+//! - Render HTML templates with JSON data, and re-render with new data only
+//! - Explore any `Serialize` value as a searchable, collapsible tree
+//! - Browse rows or CSV/TSV files in a sortable, filterable table
+//! - Show highlighted source code and line diffs, all rendered offline
+//! - Show coloured terminal output, converting its ANSI escape sequences
+//! - Stream lines into a live, filterable log window
+//! - Play audio over a zoomable waveform and spectrogram, and get back the
+//!   selected time range
+//! - Draw Graphviz DOT and Mermaid diagrams offline, with pan, zoom and SVG
+//!   export
+//! - Show several contents behind a tab strip in one window, and add, close,
+//!   select or refresh single tabs while it is open
+//! - Compose HTML fragments side by side in a [`Layout`] grid, like
+//!   subplots, and refresh single cells while it is open
+//! - Step through contents as slides, optionally advancing on a timer, and
+//!   get back the slide reached
+//! - Keep a history of refreshed contents to scrub back through, pinning one
+//!   version beside another to compare them
+//! - Stream frames to an open page at display rate, coalescing bursts and
+//!   reporting dropped frames, for live meters and training curves
+//! - Send bytes or `f32`/`i16` arrays to page scripts as `ArrayBuffer`s,
+//!   without encoding them as text
 //! - Blocking and non-blocking modes
 //! - Window configuration (size, position, title)
 //! - Security controls for navigation and remote content
 //! - Cross-platform (Windows, macOS, Linux)
+//!
+//! # Cargo Features
+//!
+//! - `log`: forward page console output and uncaught JS errors, as they are
+//!   read from a viewer, to the [`log`](https://docs.rs/log) crate under the
+//!   `html_view::page` target
 
 mod error;
+mod events;
 mod launcher;
 mod locator;
+mod log_window;
 mod options;
 mod result;
+mod stream;
+mod table;
 
 pub use error::ViewerError;
 pub use locator::{AppLocator, DefaultAppLocator};
-pub use options::{ViewerOptions, ViewerWaitMode};
+pub use log_window::LogHandle;
+pub use options::{ViewerOptions, ViewerWaitMode, WaitUntil};
 pub use result::{ViewerHandle, ViewerResult};
+pub use stream::StreamSender;
 
 // Re-export commonly used types from shared crate
 pub use html_view_shared::{
-    BehaviourOptions, DialogOptions, EnvironmentOptions, ToolbarOptions, ViewerContent,
-    ViewerExitReason, ViewerExitStatus, WindowOptions, WindowTheme,
+    AudioSelection, AudioSource, BehaviourOptions, ConsoleLevel, DiagramKind, DialogOptions,
+    DiffMode, EnvironmentOptions, ImageSource, Layout, LayoutCell, LoadState, MonitorSelector,
+    PageErrorKind, TableSource, TemplateEngine, ToolbarOptions, ViewerContent, ViewerEvent,
+    ViewerExitReason, ViewerExitStatus, ViewerState, WindowCenter, WindowGeometry, WindowOptions,
+    WindowTheme,
 };
 
 use launcher::launch_viewer;
+use serde::Serialize;
 
 /// Display inline HTML in a new viewer window and block until the window is closed.
 ///
@@ -116,6 +158,378 @@ pub fn show<S: Into<String>>(html: S) -> Result<(), ViewerError> {
     }
 }
 
+/// Display Markdown in a window and block until it is closed.
+///
+/// The Markdown is rendered by the viewer as CommonMark with the GitHub
+/// extensions (tables, task lists, strikethrough, footnotes), with syntax
+/// highlighted fenced code blocks and a stylesheet that follows the window
+/// theme.
+///
+/// # Example
+///
+/// ```no_run
+/// html_view::show_markdown("# Summary\n\n| run | loss |\n|---|---|\n| 1 | 0.12 |").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation.
+pub fn show_markdown<S: Into<String>>(markdown: S) -> Result<(), ViewerError> {
+    let options = ViewerOptions::markdown(markdown);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("markdown uses Blocking mode"),
+    }
+}
+
+/// Display an image in a window and block until it is closed.
+///
+/// `source` is either a path or encoded image bytes (PNG, JPEG, ...). The
+/// viewer offers fit/actual size, wheel zoom, drag to pan, a checkerboard
+/// behind transparent pixels and a pixel coordinate and colour readout.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::PathBuf;
+///
+/// html_view::show_image(PathBuf::from("plot.png")).unwrap();
+///
+/// let png: Vec<u8> = std::fs::read("render.png").unwrap();
+/// html_view::show_image(png).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation.
+pub fn show_image<S: Into<ImageSource>>(source: S) -> Result<(), ViewerError> {
+    let options = ViewerOptions::image(source);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("image uses Blocking mode"),
+    }
+}
+
+/// Explore a serializable value in an interactive tree view and block until
+/// the window is closed.
+///
+/// The value is serialized to JSON and shown as a collapsible tree with
+/// search over keys and values, copy-path (as `$.runs[3].name`) and
+/// expand-to-depth. Children are created lazily and large arrays are split
+/// into index ranges, so multi-megabyte values stay responsive.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::BTreeMap;
+///
+/// let mut scores = BTreeMap::new();
+/// scores.insert("baseline", vec![0.71, 0.74]);
+/// scores.insert("tuned", vec![0.80, 0.83]);
+///
+/// html_view::inspect(&scores).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ViewerError::SerdeError`] if the value cannot be serialized to
+/// JSON. See [`show`] for the other errors.
+pub fn inspect<T: Serialize + ?Sized>(value: &T) -> Result<(), ViewerError> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize value: {}", e)))?;
+    let mut options = ViewerOptions::json(value);
+    options.window.title = Some(format!("Inspect: {}", std::any::type_name::<T>()));
+
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("json uses Blocking mode"),
+    }
+}
+
+/// Display rows in a sortable, filterable table and block until the window
+/// closes.
+///
+/// Each row is serialized to JSON. Struct and map rows become one column per
+/// field, tuple and array rows one column per index, and scalar rows a single
+/// `value` column. The grid supports sorting, per-column filters (including
+/// `> 0.5` style comparisons on numeric columns) and exporting the filtered
+/// rows as CSV. Rows are rendered on demand, so hundreds of thousands of rows
+/// scroll smoothly.
+///
+/// # Example
+///
+/// ```no_run
+/// #[derive(serde::Serialize)]
+/// struct Run {
+///     name: String,
+///     loss: f64,
+/// }
+///
+/// let runs = (0..1000).map(|i| Run {
+///     name: format!("run-{i}"),
+///     loss: 1.0 / (i + 1) as f64,
+/// });
+///
+/// html_view::table(runs).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ViewerError::SerdeError`] if a row cannot be serialized to
+/// JSON. See [`show`] for the other errors.
+pub fn table<I, T>(rows: I) -> Result<(), ViewerError>
+where
+    I: IntoIterator<Item = T>,
+    T: Serialize,
+{
+    let options = ViewerOptions::table(table::table_source(rows)?);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("table uses Blocking mode"),
+    }
+}
+
+/// Display a line diff between `expected` and `actual` and block until the
+/// window closes.
+///
+/// Useful when a golden-file or snapshot comparison fails: the diff is shown
+/// in unified form, with unchanged runs folded, and can be switched to side
+/// by side in the window.
+///
+/// # Example
+///
+/// ```no_run
+/// let expected = "fn main() {\n    println!(\"hello\");\n}\n";
+/// let actual = "fn main() {\n    println!(\"hello, world\");\n}\n";
+///
+/// if expected != actual {
+///     html_view::show_diff(expected, actual).unwrap();
+/// }
+/// ```
+pub fn show_diff<A: Into<String>, B: Into<String>>(
+    expected: A,
+    actual: B,
+) -> Result<(), ViewerError> {
+    let mut options = ViewerOptions::diff(expected, actual);
+    options.window.title = Some("Expected vs actual".to_string());
+
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("diff uses Blocking mode"),
+    }
+}
+
+/// Display audio as a waveform with playback and block until the window
+/// closes, returning the time range selected when it closed.
+///
+/// WAV, FLAC, MP3 and Ogg Vorbis files are decoded by the viewer; in-memory
+/// samples are shown as they are. The waveform zooms with the mouse wheel
+/// and pans with shift+wheel, dragging selects a range, clicking moves the
+/// playhead and Space plays the selection or the whole file. A spectrogram
+/// can be toggled below the waveform.
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::AudioSource;
+///
+/// let tone: Vec<f32> = (0..44_100)
+///     .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 44_100.0).sin() * 0.5)
+///     .collect();
+///
+/// if let Some(selection) = html_view::show_audio(AudioSource::mono(tone, 44_100))? {
+///     println!("selected {:.3}s to {:.3}s", selection.start, selection.end);
+/// }
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation. A file that cannot be decoded is
+/// reported as [`ViewerExitReason::Error`] in the exit status, so this
+/// returns `Ok(None)`.
+pub fn show_audio<S: Into<AudioSource>>(source: S) -> Result<Option<AudioSelection>, ViewerError> {
+    let options = ViewerOptions::audio(source);
+    match open(options)? {
+        ViewerResult::Blocking(status) => Ok(status.audio_selection),
+        ViewerResult::NonBlocking(_) => unreachable!("audio uses Blocking mode"),
+    }
+}
+
+/// Draw a Graphviz DOT or Mermaid diagram and block until the window closes.
+///
+/// The diagram is laid out and drawn by the viewer itself, so neither `dot`
+/// nor a network connection is needed. It can be panned by dragging, zoomed
+/// with the mouse wheel, and saved as SVG.
+///
+/// DOT covers graphs and digraphs with subgraphs, node and edge defaults,
+/// shapes, records, colours and `rankdir`. Mermaid covers flowcharts and
+/// state diagrams only; see [`DiagramKind`] for what each language leaves
+/// out.
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::DiagramKind;
+///
+/// let dot = r#"digraph {
+///     idle -> running [label="start"];
+///     running -> idle [label="stop"];
+///     running -> failed;
+/// }"#;
+///
+/// html_view::show_diagram(dot, DiagramKind::Dot)?;
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation. A source that cannot be parsed is
+/// reported as [`ViewerExitReason::Error`] in the exit status, with the line
+/// at fault, so this still returns `Ok(())`.
+pub fn show_diagram<S: Into<String>>(source: S, kind: DiagramKind) -> Result<(), ViewerError> {
+    let options = ViewerOptions::diagram(source, kind);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("diagram uses Blocking mode"),
+    }
+}
+
+/// Show several contents behind a tab strip and block until the window
+/// closes.
+///
+/// Each tab is shown in its own frame, so tabs keep their scroll position
+/// and state while another is selected, and their styles do not collide.
+/// Tabs are given as `(id, content)` pairs; the id is the tab's label.
+/// To change tabs while the window is open, use
+/// `ViewerOptions::new().non_blocking().show_tabs(..)` and the returned
+/// [`ViewerHandle`]'s tab methods.
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::ViewerOptions;
+/// use std::path::PathBuf;
+///
+/// html_view::show_tabs([
+///     ("Baseline", ViewerOptions::local_file(PathBuf::from("baseline.html")).content),
+///     ("Candidate", ViewerOptions::local_file(PathBuf::from("candidate.html")).content),
+/// ])?;
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation.
+pub fn show_tabs<I, S>(tabs: I) -> Result<(), ViewerError>
+where
+    I: IntoIterator<Item = (S, ViewerContent)>,
+    S: Into<String>,
+{
+    let options = ViewerOptions::tabs(tabs);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("tabs use Blocking mode"),
+    }
+}
+
+/// Show several contents side by side in a grid and block until the window
+/// closes.
+///
+/// Each cell is shown in its own frame, so the styles and scripts of one
+/// fragment do not affect the others. To replace cells while the window is
+/// open, use `ViewerOptions::new().non_blocking().show_layout(..)` and
+/// [`ViewerHandle::refresh_cell`].
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::Layout;
+///
+/// html_view::show_layout(
+///     Layout::grid(2, 2)
+///         .cell(0, 0, "<h2>Loss</h2>")
+///         .cell(0, 1, "<h2>Accuracy</h2>")
+///         .cell(1, 0, "<h2>Samples</h2>")
+///         .span(1, 2),
+/// )?;
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation.
+pub fn show_layout(layout: Layout) -> Result<(), ViewerError> {
+    let options = ViewerOptions::layout(layout);
+    match open(options)? {
+        ViewerResult::Blocking(_status) => Ok(()),
+        ViewerResult::NonBlocking(_) => unreachable!("layout uses Blocking mode"),
+    }
+}
+
+/// Show several contents one at a time and block until the window closes,
+/// returning the index of the slide shown when it closed.
+///
+/// Slides are stepped through with the arrow keys, PageUp/PageDown, Home and
+/// End, or the buttons below them; F toggles fullscreen. To advance on a
+/// timer, use `ViewerOptions::new().show_slides(slides, Some(seconds))`.
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::ViewerOptions;
+///
+/// let plots = (1..=10).map(|epoch| {
+///     ViewerOptions::inline_html(format!("<h1>Epoch {epoch}</h1>")).content
+/// });
+/// if let Some(index) = html_view::show_slides(plots)? {
+///     println!("Stopped at epoch {}", index + 1);
+/// }
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`show`] for error documentation.
+pub fn show_slides<I: IntoIterator<Item = ViewerContent>>(
+    slides: I,
+) -> Result<Option<usize>, ViewerError> {
+    let options = ViewerOptions::slides(slides);
+    match open(options)? {
+        ViewerResult::Blocking(status) => Ok(status.slide),
+        ViewerResult::NonBlocking(_) => unreachable!("slides use Blocking mode"),
+    }
+}
+
+/// Open a live log window and return a handle for appending to it.
+///
+/// The window opens without blocking. Lines appended through the returned
+/// [`LogHandle`] are added to the view, which follows new output unless the
+/// user scrolls up, keeps the most recent 10,000 lines, and can be filtered
+/// by text and by level (detected from words such as `ERROR` or `warn:`).
+///
+/// # Example
+///
+/// ```no_run
+/// let mut log = html_view::log_window("Training")?;
+/// for epoch in 0..100 {
+///     log.append(format!("INFO epoch {epoch} finished"))?;
+/// }
+/// log.append_html("<b>Done</b>")?;
+/// log.wait()?;
+/// # Ok::<(), html_view::ViewerError>(())
+/// ```
+pub fn log_window<S: Into<String>>(title: S) -> Result<LogHandle, ViewerError> {
+    let mut options = ViewerOptions::log();
+    options.window.title = Some(title.into());
+    options.wait = ViewerWaitMode::NonBlocking;
+
+    match open(options)? {
+        ViewerResult::NonBlocking(handle) => Ok(LogHandle::new(handle)),
+        ViewerResult::Blocking(_) => unreachable!("log_window uses NonBlocking mode"),
+    }
+}
+
 /// Display HTML with custom window configuration.
 ///
 /// This is a convenience function that allows you to customize the window
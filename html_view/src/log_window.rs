@@ -0,0 +1,45 @@
+//! Live log windows.
+
+use crate::{ViewerError, ViewerExitStatus, ViewerHandle};
+use html_view_shared::LogEntry;
+
+/// A viewer showing an append-only log, returned by
+/// [`log_window`](crate::log_window).
+///
+/// Each append sends only the new entries, so the cost of an append does not
+/// grow with the size of the log. The window follows new lines unless the
+/// user scrolls up, and keeps the most recent lines only.
+#[derive(Debug)]
+pub struct LogHandle {
+    handle: ViewerHandle,
+}
+
+impl LogHandle {
+    pub(crate) fn new(handle: ViewerHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Append a line of text. Newlines within `line` start new lines.
+    pub fn append<S: Into<String>>(&mut self, line: S) -> Result<(), ViewerError> {
+        self.handle.append_log(vec![LogEntry::Text(line.into())])
+    }
+
+    /// Append an HTML fragment as a single entry.
+    ///
+    /// The fragment is inserted into the page as is, so it must come from a
+    /// trusted source.
+    pub fn append_html<S: Into<String>>(&mut self, fragment: S) -> Result<(), ViewerError> {
+        self.handle
+            .append_log(vec![LogEntry::Html(fragment.into())])
+    }
+
+    /// The underlying viewer handle, for example to poll its events.
+    pub fn handle(&mut self) -> &mut ViewerHandle {
+        &mut self.handle
+    }
+
+    /// Block until the user closes the log window.
+    pub fn wait(self) -> Result<ViewerExitStatus, ViewerError> {
+        self.handle.wait()
+    }
+}
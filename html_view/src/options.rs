@@ -1,4 +1,8 @@
-use html_view_shared::{BehaviourOptions, EnvironmentOptions, ViewerContent, WindowOptions};
+use html_view_shared::{
+    AudioSource, BehaviourOptions, DiagramKind, DiffMode, EnvironmentOptions, Layout, TableSource,
+    ViewerContent, WindowOptions,
+};
+use std::collections::HashMap;
 
 /// Options for configuring a viewer instance.
 ///
@@ -67,6 +71,11 @@ pub struct ViewerOptions {
     /// In [`ViewerWaitMode::Blocking`] mode, the call blocks until the window closes.
     /// In [`ViewerWaitMode::NonBlocking`] mode, returns immediately with a handle.
     pub wait: ViewerWaitMode,
+
+    /// How far the viewer must get before a non-blocking `open` returns.
+    ///
+    /// Ignored in [`ViewerWaitMode::Blocking`] mode.
+    pub wait_until: WaitUntil,
 }
 
 /// Determines whether the viewer call blocks or returns immediately.
@@ -120,6 +129,38 @@ pub enum ViewerWaitMode {
     NonBlocking,
 }
 
+/// The startup stage a non-blocking [`open`](crate::open) waits for before
+/// returning its handle.
+///
+/// # Example
+///
+/// ```no_run
+/// use html_view::{ViewerOptions, ViewerResult, WaitUntil};
+///
+/// let result = ViewerOptions::new()
+///     .non_blocking()
+///     .wait_until(WaitUntil::ContentLoaded)
+///     .show_html("<h1>Ready</h1>")
+///     .unwrap();
+///
+/// if let ViewerResult::NonBlocking(mut handle) = result {
+///     // The page has rendered, so refreshing no longer races startup
+///     handle.refresh_html("<h1>Updated</h1>").unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitUntil {
+    /// Return as soon as the viewer process has been spawned.
+    #[default]
+    Spawned,
+
+    /// Return once the window has been configured and shown.
+    WindowShown,
+
+    /// Return once the content has finished loading.
+    ContentLoaded,
+}
+
 impl ViewerOptions {
     /// Create options for displaying inline HTML with default settings.
     ///
@@ -149,6 +190,353 @@ impl ViewerOptions {
             environment: EnvironmentOptions::default(),
             dialog: html_view_shared::DialogOptions::default(),
             wait: ViewerWaitMode::Blocking,
+            wait_until: WaitUntil::Spawned,
+        }
+    }
+
+    /// Create options for displaying Markdown rendered to HTML.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    ///
+    /// let options = ViewerOptions::markdown("# Results\n\n- [x] converged");
+    /// ```
+    pub fn markdown<S: Into<String>>(source: S) -> Self {
+        Self {
+            content: ViewerContent::Markdown {
+                source: source.into(),
+                base_dir: None,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for displaying an image from a path or encoded bytes.
+    ///
+    /// The MIME type is detected from the data or file extension.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let from_file = ViewerOptions::image(PathBuf::from("plot.png"));
+    /// let from_memory = ViewerOptions::image(vec![0x89, b'P', b'N', b'G']);
+    /// ```
+    pub fn image<S: Into<html_view_shared::ImageSource>>(source: S) -> Self {
+        Self {
+            content: ViewerContent::Image {
+                source: source.into(),
+                mime: None,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for displaying an HTML page and its assets from memory.
+    ///
+    /// `files` maps paths relative to the bundle root to their contents, and
+    /// `entry` names the HTML file to open.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut files = HashMap::new();
+    /// files.insert(
+    ///     "index.html".to_string(),
+    ///     br#"<link rel="stylesheet" href="app.css"><h1>Hi</h1>"#.to_vec(),
+    /// );
+    /// files.insert("app.css".to_string(), b"h1 { color: teal; }".to_vec());
+    ///
+    /// let options = ViewerOptions::bundle("index.html", files);
+    /// ```
+    pub fn bundle<S: Into<String>>(entry: S, files: HashMap<String, Vec<u8>>) -> Self {
+        Self {
+            content: ViewerContent::Bundle {
+                entry: entry.into(),
+                files,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for displaying an HTML template rendered with `data`.
+    ///
+    /// The template uses [MiniJinja](https://docs.rs/minijinja) syntax with
+    /// HTML auto-escaping. Use
+    /// [`ViewerHandle::update_data`](crate::ViewerHandle::update_data) to
+    /// re-render it with new data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    /// use serde_json::json;
+    ///
+    /// let options = ViewerOptions::template(
+    ///     "<ul>{% for run in runs %}<li>{{ run.name }}: {{ run.score }}</li>{% endfor %}</ul>",
+    ///     json!({ "runs": [{ "name": "baseline", "score": 0.81 }] }),
+    /// );
+    /// ```
+    pub fn template<S: Into<String>>(template: S, data: serde_json::Value) -> Self {
+        Self {
+            content: ViewerContent::Template {
+                template: template.into(),
+                data,
+                engine: html_view_shared::TemplateEngine::default(),
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for exploring a JSON value as a collapsible tree.
+    ///
+    /// See [`inspect`](crate::inspect) for a shortcut taking any
+    /// `Serialize` value.
+    pub fn json(value: serde_json::Value) -> Self {
+        Self {
+            content: ViewerContent::Json { value },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for browsing tabular data in a sortable, filterable
+    /// grid.
+    ///
+    /// See [`table`](crate::table) for a shortcut taking any `Serialize`
+    /// rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::{TableSource, ViewerOptions};
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ViewerOptions::table(TableSource::File {
+    ///     path: PathBuf::from("results.csv"),
+    ///     delimiter: None,
+    /// });
+    /// ```
+    pub fn table(source: TableSource) -> Self {
+        Self {
+            content: ViewerContent::Table { source },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing highlighted source code with line numbers.
+    ///
+    /// `language` is a name, alias or file extension such as `rust` or
+    /// `py`; code in unknown languages is shown as plain text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    ///
+    /// let options = ViewerOptions::code("fn main() {}", Some("rust"));
+    /// ```
+    pub fn code<S: Into<String>>(source: S, language: Option<&str>) -> Self {
+        Self {
+            content: ViewerContent::Code {
+                source: source.into(),
+                language: language.map(str::to_string),
+                line_numbers: true,
+                highlight_lines: Vec::new(),
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing a unified line diff from `old` to `new`.
+    ///
+    /// See [`show_diff`](crate::show_diff) for a shortcut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    ///
+    /// let options = ViewerOptions::diff("a\nb\n", "a\nc\n");
+    /// ```
+    pub fn diff<A: Into<String>, B: Into<String>>(old: A, new: B) -> Self {
+        Self {
+            content: ViewerContent::Diff {
+                old: old.into(),
+                new: new.into(),
+                mode: DiffMode::default(),
+                language: None,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing terminal output with ANSI colours.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    ///
+    /// let options = ViewerOptions::ansi_text("\x1b[32mok\x1b[0m 12 tests passed");
+    /// ```
+    pub fn ansi_text<S: Into<String>>(text: S) -> Self {
+        Self {
+            content: ViewerContent::AnsiText { text: text.into() },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing audio as a waveform with playback and
+    /// selection. The spectrogram starts hidden; set
+    /// `ViewerContent::Audio::spectrogram` to show it initially.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ViewerOptions::audio(PathBuf::from("take-3.flac"));
+    /// ```
+    pub fn audio<S: Into<AudioSource>>(source: S) -> Self {
+        Self {
+            content: ViewerContent::Audio {
+                source: source.into(),
+                spectrogram: false,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for drawing a Graphviz DOT or Mermaid diagram.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::{DiagramKind, ViewerOptions};
+    ///
+    /// let options = ViewerOptions::diagram("graph TD\n  A --> B", DiagramKind::Mermaid);
+    /// ```
+    pub fn diagram<S: Into<String>>(source: S, kind: DiagramKind) -> Self {
+        Self {
+            content: ViewerContent::Diagram {
+                source: source.into(),
+                kind,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing several contents behind a tab strip,
+    /// given as `(id, content)` pairs. The id labels the tab and names it in
+    /// commands such as [`ViewerHandle::refresh_tab`](crate::ViewerHandle::refresh_tab).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::{ViewerContent, ViewerOptions};
+    ///
+    /// let options = ViewerOptions::tabs([
+    ///     ("Summary", ViewerOptions::markdown("# Run 12").content),
+    ///     (
+    ///         "Config",
+    ///         ViewerContent::Json {
+    ///             value: serde_json::json!({ "lr": 0.01 }),
+    ///         },
+    ///     ),
+    /// ]);
+    /// ```
+    pub fn tabs<I, S>(tabs: I) -> Self
+    where
+        I: IntoIterator<Item = (S, ViewerContent)>,
+        S: Into<String>,
+    {
+        Self {
+            content: ViewerContent::Tabs {
+                tabs: tabs
+                    .into_iter()
+                    .map(|(id, content)| (id.into(), content))
+                    .collect(),
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for several contents side by side in a grid, each in
+    /// its own frame. Cells can be replaced one at a time with
+    /// [`ViewerHandle::refresh_cell`](crate::ViewerHandle::refresh_cell).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::{Layout, ViewerOptions};
+    ///
+    /// let options = ViewerOptions::layout(Layout::hstack(["<h1>Before</h1>", "<h1>After</h1>"]));
+    /// ```
+    pub fn layout(layout: Layout) -> Self {
+        Self {
+            content: ViewerContent::Layout { layout },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for showing several contents one at a time, stepped
+    /// through with the arrow keys or the buttons below them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    ///
+    /// let epochs = (1..=3).map(|epoch| {
+    ///     ViewerOptions::inline_html(format!("<h1>Epoch {epoch}</h1>")).content
+    /// });
+    /// let options = ViewerOptions::slides(epochs);
+    /// ```
+    pub fn slides<I: IntoIterator<Item = ViewerContent>>(slides: I) -> Self {
+        Self {
+            content: ViewerContent::Slides {
+                slides: slides.into_iter().collect(),
+                advance_seconds: None,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for an append-only log, fed with
+    /// [`ViewerHandle::append_log`](crate::ViewerHandle::append_log).
+    ///
+    /// See [`log_window`](crate::log_window) for a shortcut that opens the
+    /// window and returns a [`LogHandle`](crate::LogHandle).
+    pub fn log() -> Self {
+        Self {
+            content: ViewerContent::Log { max_lines: None },
+            ..Self::default()
+        }
+    }
+
+    /// Create options for serving an HTML application from a zip, tar or
+    /// tar.gz archive without extracting it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use html_view::ViewerOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ViewerOptions::archive(PathBuf::from("report.zip"));
+    /// ```
+    pub fn archive(path: std::path::PathBuf) -> Self {
+        Self {
+            content: ViewerContent::Archive { path, entry: None },
+            ..Self::default()
         }
     }
 
@@ -170,6 +558,7 @@ impl ViewerOptions {
             environment: EnvironmentOptions::default(),
             dialog: html_view_shared::DialogOptions::default(),
             wait: ViewerWaitMode::Blocking,
+            wait_until: WaitUntil::Spawned,
         }
     }
 
@@ -191,6 +580,7 @@ impl ViewerOptions {
             environment: EnvironmentOptions::default(),
             dialog: html_view_shared::DialogOptions::default(),
             wait: ViewerWaitMode::Blocking,
+            wait_until: WaitUntil::Spawned,
         }
     }
 
@@ -218,6 +608,7 @@ impl ViewerOptions {
             environment: EnvironmentOptions::default(),
             dialog: html_view_shared::DialogOptions::default(),
             wait: ViewerWaitMode::Blocking,
+            wait_until: WaitUntil::Spawned,
         }
     }
     /// Create a new builder for ViewerOptions.
@@ -351,12 +742,39 @@ impl ViewerOptionsBuilder {
         self
     }
 
+    /// Set the startup stage a non-blocking open waits for.
+    pub fn wait_until(mut self, stage: WaitUntil) -> Self {
+        self.options.wait_until = stage;
+        self
+    }
+
     /// Set window theme.
     pub fn theme(mut self, theme: html_view_shared::WindowTheme) -> Self {
         self.options.window.theme_enum = Some(theme);
         self
     }
 
+    /// Forward page console output as [`ViewerEvent::Console`](crate::ViewerEvent::Console).
+    pub fn capture_console(mut self) -> Self {
+        self.options.behaviour.capture_console = true;
+        self
+    }
+
+    /// Make uncaught page errors end the viewer with
+    /// [`ViewerExitReason::Error`](crate::ViewerExitReason::Error).
+    pub fn fail_on_page_error(mut self) -> Self {
+        self.options.behaviour.fail_on_page_error = true;
+        self
+    }
+
+    /// Keep the last `versions` contents shown through
+    /// [`ViewerHandle::refresh`](crate::ViewerHandle::refresh), with a
+    /// timeline to scrub back through them and a pin to compare two.
+    pub fn refresh_history(mut self, versions: usize) -> Self {
+        self.options.behaviour.refresh_history = Some(versions);
+        self
+    }
+
     /// Enable system notifications.
     pub fn enable_notifications(mut self) -> Self {
         self.options.behaviour.allow_notifications = true;
@@ -370,6 +788,66 @@ impl ViewerOptionsBuilder {
         self
     }
 
+    /// Centre the window on its monitor.
+    pub fn center(mut self) -> Self {
+        self.options.window.center = Some(html_view_shared::WindowCenter::Monitor);
+        self
+    }
+
+    /// Centre the window over a parent window's rectangle (logical pixels).
+    pub fn center_on_parent(mut self, x: i32, y: i32, width: u32, height: u32) -> Self {
+        self.options.window.center = Some(html_view_shared::WindowCenter::Parent {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Open the window on the monitor with the given index.
+    pub fn monitor(mut self, index: usize) -> Self {
+        self.options.window.monitor = Some(html_view_shared::MonitorSelector::Index(index));
+        self
+    }
+
+    /// Open the window on the monitor with the given name.
+    pub fn monitor_named<S: Into<String>>(mut self, name: S) -> Self {
+        self.options.window.monitor = Some(html_view_shared::MonitorSelector::Name(name.into()));
+        self
+    }
+
+    /// Size the window as a percentage of the monitor's work area.
+    ///
+    /// Each percentage must be greater than 0 and at most 100, and is only
+    /// used for a dimension not set with [`size`](Self::size) or restored
+    /// via [`state_key`](Self::state_key).
+    pub fn size_percent(mut self, width: f64, height: f64) -> Self {
+        self.options.window.width_percent = Some(width);
+        self.options.window.height_percent = Some(height);
+        self
+    }
+
+    /// Set the minimum window size.
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.options.window.min_width = Some(width);
+        self.options.window.min_height = Some(height);
+        self
+    }
+
+    /// Set the maximum window size.
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.options.window.max_width = Some(width);
+        self.options.window.max_height = Some(height);
+        self
+    }
+
+    /// Remember the window's size, position and zoom between runs under `key`.
+    pub fn state_key<S: Into<String>>(mut self, key: S) -> Self {
+        self.options.window.state_key = Some(key.into());
+        self
+    }
+
     /// Configure the custom toolbar.
     pub fn toolbar(mut self, toolbar: html_view_shared::ToolbarOptions) -> Self {
         self.options.window.toolbar = toolbar;
@@ -395,6 +873,176 @@ impl ViewerOptionsBuilder {
         crate::open(self.options)
     }
 
+    /// Open the viewer with Markdown content.
+    pub fn show_markdown<S: Into<String>>(
+        mut self,
+        source: S,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Markdown {
+            source: source.into(),
+            base_dir: None,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with an image from a path or encoded bytes.
+    pub fn show_image<S: Into<html_view_shared::ImageSource>>(
+        mut self,
+        source: S,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Image {
+            source: source.into(),
+            mime: None,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with an HTML page and its assets from memory.
+    pub fn show_bundle<S: Into<String>>(
+        mut self,
+        entry: S,
+        files: HashMap<String, Vec<u8>>,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Bundle {
+            entry: entry.into(),
+            files,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with an HTML application served from an archive.
+    pub fn show_archive(
+        mut self,
+        path: std::path::PathBuf,
+        entry: Option<String>,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Archive { path, entry };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with tabular data in a sortable, filterable grid.
+    pub fn show_table(
+        mut self,
+        source: TableSource,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Table { source };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with highlighted source code.
+    pub fn show_code<S: Into<String>>(
+        mut self,
+        source: S,
+        language: Option<&str>,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Code {
+            source: source.into(),
+            language: language.map(str::to_string),
+            line_numbers: true,
+            highlight_lines: Vec::new(),
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with a line diff from `old` to `new`.
+    pub fn show_diff<A: Into<String>, B: Into<String>>(
+        mut self,
+        old: A,
+        new: B,
+        mode: DiffMode,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Diff {
+            old: old.into(),
+            new: new.into(),
+            mode,
+            language: None,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with terminal output, rendering its ANSI colours.
+    pub fn show_ansi_text<S: Into<String>>(
+        mut self,
+        text: S,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::AnsiText { text: text.into() };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with audio, optionally showing its spectrogram
+    /// from the start.
+    pub fn show_audio<S: Into<AudioSource>>(
+        mut self,
+        source: S,
+        spectrogram: bool,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Audio {
+            source: source.into(),
+            spectrogram,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with a Graphviz DOT or Mermaid diagram.
+    pub fn show_diagram<S: Into<String>>(
+        mut self,
+        source: S,
+        kind: DiagramKind,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Diagram {
+            source: source.into(),
+            kind,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with several contents behind a tab strip.
+    pub fn show_tabs<I, S>(mut self, tabs: I) -> Result<crate::ViewerResult, crate::ViewerError>
+    where
+        I: IntoIterator<Item = (S, ViewerContent)>,
+        S: Into<String>,
+    {
+        self.options.content = ViewerOptions::tabs(tabs).content;
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with several contents shown one at a time, moving on
+    /// by itself every `advance_seconds` if set.
+    pub fn show_slides<I: IntoIterator<Item = ViewerContent>>(
+        mut self,
+        slides: I,
+        advance_seconds: Option<f64>,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Slides {
+            slides: slides.into_iter().collect(),
+            advance_seconds,
+        };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with several contents side by side in a grid.
+    pub fn show_layout(
+        mut self,
+        layout: Layout,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Layout { layout };
+        crate::open(self.options)
+    }
+
+    /// Open the viewer with an HTML template rendered with `data`.
+    pub fn show_template<S: Into<String>>(
+        mut self,
+        template: S,
+        data: serde_json::Value,
+    ) -> Result<crate::ViewerResult, crate::ViewerError> {
+        self.options.content = ViewerContent::Template {
+            template: template.into(),
+            data,
+            engine: html_view_shared::TemplateEngine::default(),
+        };
+        crate::open(self.options)
+    }
+
     /// Open the viewer with a local file.
     pub fn show_file(
         mut self,
@@ -421,13 +1069,14 @@ impl ViewerOptionsBuilder {
         crate::open(self.options)
     }
 
-    /// Set base directory for inline HTML (for resolving relative asset paths).
+    /// Set base directory for inline HTML or Markdown (for resolving relative asset paths).
     pub fn base_dir(mut self, dir: std::path::PathBuf) -> Self {
-        if let ViewerContent::InlineHtml { html, .. } = &self.options.content {
-            self.options.content = ViewerContent::InlineHtml {
-                html: html.clone(),
-                base_dir: Some(dir),
-            };
+        match &mut self.options.content {
+            ViewerContent::InlineHtml { base_dir, .. }
+            | ViewerContent::Markdown { base_dir, .. } => {
+                *base_dir = Some(dir);
+            }
+            _ => {}
         }
         self
     }
@@ -445,6 +1094,7 @@ impl Default for ViewerOptions {
             environment: EnvironmentOptions::default(),
             dialog: html_view_shared::DialogOptions::default(),
             wait: ViewerWaitMode::Blocking,
+            wait_until: WaitUntil::Spawned,
         }
     }
 }
@@ -1,13 +1,17 @@
-use crate::ViewerError;
+use crate::events::EventReader;
+use crate::stream::StreamSender;
+use crate::{ViewerError, WaitUntil};
 use html_view_shared::{
-    PROTOCOL_VERSION, ViewerCommand, ViewerCommandResponse, ViewerContent, ViewerExitReason,
-    ViewerExitStatus,
+    LogEntry, PROTOCOL_VERSION, ViewerCommand, ViewerCommandData, ViewerCommandResponse,
+    ViewerContent, ViewerEvent, ViewerExitReason, ViewerExitStatus, ViewerState,
 };
+use serde::Serialize;
 use std::path::PathBuf;
 use std::process::Child;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use url::Url;
 use uuid::Uuid;
 
 /// The result of opening a viewer.
@@ -26,8 +30,9 @@ pub struct ViewerHandle {
     /// Unique identifier for this viewer instance.
     pub id: Uuid,
 
-    /// The spawned child process.
-    child: Child,
+    /// The spawned child process, shared with the stream writer so it can
+    /// tell when the viewer exits.
+    child: Arc<Mutex<Child>>,
 
     /// Path to the result JSON file.
     result_path: PathBuf,
@@ -43,6 +48,35 @@ pub struct ViewerHandle {
 
     /// Optional path to the command response file.
     response_path: Option<PathBuf>,
+
+    /// Reader for the event file the viewer appends to.
+    events: Option<EventReader>,
+
+    /// Events read from the event file but not yet returned to the caller.
+    pending_events: Vec<ViewerEvent>,
+
+    /// Optional path to the file frames are streamed through.
+    stream_path: Option<PathBuf>,
+
+    /// Sender for streamed frames, started on the first call to `stream`.
+    stream: Option<StreamSender>,
+}
+
+/// Paths of the files a running viewer is driven through, each `None` if the
+/// viewer was launched without support for it.
+#[derive(Debug, Default)]
+pub(crate) struct IpcPaths {
+    /// Command file for sending runtime updates.
+    pub command_path: Option<PathBuf>,
+
+    /// File the viewer writes command responses to.
+    pub response_path: Option<PathBuf>,
+
+    /// Event file the viewer appends to.
+    pub event_path: Option<PathBuf>,
+
+    /// File frames are streamed through.
+    pub stream_path: Option<PathBuf>,
 }
 
 impl ViewerHandle {
@@ -52,20 +86,28 @@ impl ViewerHandle {
         child: Child,
         result_path: PathBuf,
         temp_dir: PathBuf,
-        command_path: Option<PathBuf>,
-        response_path: Option<PathBuf>,
+        paths: IpcPaths,
     ) -> Self {
         Self {
             id,
-            child,
+            child: Arc::new(Mutex::new(child)),
             result_path,
             temp_dir,
-            command_path,
+            command_path: paths.command_path,
             command_seq: Arc::new(AtomicU64::new(0)),
-            response_path,
+            response_path: paths.response_path,
+            events: paths.event_path.map(EventReader::new),
+            pending_events: Vec::new(),
+            stream_path: paths.stream_path,
+            stream: None,
         }
     }
 
+    /// Lock the child process.
+    fn child(&self) -> MutexGuard<'_, Child> {
+        self.child.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Try to check whether the viewer has finished and return its exit status.
     ///
     /// This is non-blocking. Returns `Ok(None)` if the process is still running.
@@ -90,7 +132,7 @@ impl ViewerHandle {
     /// }
     /// ```
     pub fn try_wait(&mut self) -> Result<Option<ViewerExitStatus>, ViewerError> {
-        match self.child.try_wait()? {
+        match self.child().try_wait()? {
             Some(_exit_status) => {
                 // Process has exited, read the result file
                 let result = self.read_result_file()?;
@@ -115,8 +157,8 @@ impl ViewerHandle {
     ///     println!("Viewer exited: {:?}", status);
     /// }
     /// ```
-    pub fn wait(mut self) -> Result<ViewerExitStatus, ViewerError> {
-        self.child.wait()?;
+    pub fn wait(self) -> Result<ViewerExitStatus, ViewerError> {
+        self.child().wait()?;
         self.read_result_file()
     }
 
@@ -136,14 +178,16 @@ impl ViewerHandle {
     /// }
     /// ```
     pub fn terminate(&mut self) -> Result<(), ViewerError> {
-        self.child.kill()?;
+        self.child().kill()?;
         Ok(())
     }
 
     /// Refresh the viewer with new content.
     ///
     /// This updates the displayed content without closing the window.
-    /// The window and behavior options remain unchanged.
+    /// The window and behavior options remain unchanged. With
+    /// `refresh_history` set on the options, earlier contents are kept on a
+    /// timeline instead of being replaced.
     ///
     /// # Example
     ///
@@ -164,31 +208,8 @@ impl ViewerHandle {
     /// }
     /// ```
     pub fn refresh(&mut self, content: ViewerContent) -> Result<(), ViewerError> {
-        // Check process is still alive first
-        if let Some(_) = self.try_wait()? {
-            return Err(ViewerError::CommandFailed("Process has exited".to_string()));
-        }
-
-        // Verify viewer supports refresh
-        let command_path = self.command_path.clone().ok_or_else(|| {
-            ViewerError::RefreshNotSupported(
-                "Viewer was launched without refresh support".to_string(),
-            )
-        })?;
-
-        // Create command with sequence number
-        let seq = self.command_seq.fetch_add(1, Ordering::SeqCst);
-        let command = ViewerCommand::Refresh { seq, content };
-
-        // Write command atomically (temp file + rename)
-        let temp_path = self.temp_dir.join(format!("command_{}.tmp", seq));
-        let command_json = serde_json::to_string(&command)
-            .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize command: {}", e)))?;
-        std::fs::write(&temp_path, &command_json)?;
-        std::fs::rename(&temp_path, &command_path)?;
-
-        // Wait for response
-        self.wait_for_response(seq, Duration::from_secs(5))
+        self.send_command(|seq| ViewerCommand::Refresh { seq, content })?;
+        Ok(())
     }
 
     /// Refresh the viewer with inline HTML (convenience method).
@@ -213,7 +234,492 @@ impl ViewerHandle {
         })
     }
 
-    fn wait_for_response(&mut self, seq: u64, timeout: Duration) -> Result<(), ViewerError> {
+    /// Re-render the displayed template with new data.
+    ///
+    /// Only the data is sent; the viewer keeps the template from the
+    /// [`ViewerContent::Template`] it is showing. Fails if the viewer is
+    /// showing other content.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    /// use serde_json::json;
+    ///
+    /// let template = "<h1>{{ title }}</h1><p>Loss: {{ loss }}</p>";
+    /// let result = ViewerOptions::new()
+    ///     .non_blocking()
+    ///     .show_template(template, json!({ "title": "Training", "loss": 1.0 }))
+    ///     .unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     for step in 1..10 {
+    ///         std::thread::sleep(std::time::Duration::from_secs(1));
+    ///         let data = json!({ "title": "Training", "loss": 1.0 / step as f64 });
+    ///         handle.update_data(&data).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn update_data<T: Serialize + ?Sized>(&mut self, data: &T) -> Result<(), ViewerError> {
+        let data = serde_json::to_value(data)
+            .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize data: {}", e)))?;
+        self.send_command(|seq| ViewerCommand::UpdateData { seq, data })?;
+        Ok(())
+    }
+
+    /// Start streaming frames to the viewer, or get another sender for the
+    /// stream already started.
+    ///
+    /// Unlike [`refresh`](Self::refresh), sending a frame does not wait for
+    /// the viewer or reload the page: frames update the page in place, and a
+    /// burst of frames is coalesced so only the latest one is applied. This
+    /// suits live meters and plots updated 30 to 60 times a second. See
+    /// [`StreamSender`] for the kinds of frames.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerEvent, ViewerOptions, ViewerResult};
+    ///
+    /// let page = r#"<h1 data-html-view-stream></h1>"#;
+    /// let result = ViewerOptions::new().non_blocking().show_html(page).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     let stream = handle.stream().unwrap();
+    ///     for step in 0..1000 {
+    ///         stream.send_html(format!("Step {}", step)).unwrap();
+    ///         std::thread::sleep(std::time::Duration::from_millis(2));
+    ///     }
+    ///     for event in handle.poll_events().unwrap() {
+    ///         if let ViewerEvent::StreamStats { applied, dropped, fps } = event {
+    ///             println!("{} applied, {} dropped, {:.0} fps", applied, dropped, fps);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn stream(&mut self) -> Result<StreamSender, ViewerError> {
+        if let Some(sender) = &self.stream {
+            return Ok(sender.clone());
+        }
+
+        if self.try_wait()?.is_some() {
+            return Err(ViewerError::CommandFailed("Process has exited".to_string()));
+        }
+        let stream_path = self.stream_path.clone().ok_or_else(|| {
+            ViewerError::RefreshNotSupported(
+                "Viewer was launched without stream support".to_string(),
+            )
+        })?;
+
+        let child = Arc::clone(&self.child);
+        let sender = StreamSender::start(stream_path, move || has_exited(&child))?;
+        self.stream = Some(sender.clone());
+        Ok(sender)
+    }
+
+    /// Send bytes to the page, which receives them as an `ArrayBuffer` in the
+    /// callbacks it registered for `channel` with `htmlView.onBinary`.
+    ///
+    /// The bytes travel through a file and reach the page without being
+    /// encoded as text, so this suits large arrays such as audio samples.
+    /// Bytes sent while no callback is registered for the channel are
+    /// dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    ///
+    /// let page = r#"<p id="size"></p>
+    /// <script>
+    ///   htmlView.onBinary('image', function (buffer) {
+    ///     document.getElementById('size').textContent = buffer.byteLength + ' bytes';
+    ///   });
+    /// </script>"#;
+    /// let result = ViewerOptions::new().non_blocking().show_html(page).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     let bytes = std::fs::read("photo.png").unwrap();
+    ///     handle.send_binary("image", &bytes).unwrap();
+    /// }
+    /// ```
+    pub fn send_binary(&mut self, channel: &str, bytes: &[u8]) -> Result<(), ViewerError> {
+        let path = self.temp_dir.join(format!("binary_{}.bin", Uuid::new_v4()));
+        std::fs::write(&path, bytes)?;
+
+        let channel = channel.to_string();
+        let sent = self.send_command(|seq| ViewerCommand::SendBinary {
+            seq,
+            channel,
+            path: path.clone(),
+        });
+        if sent.is_err() {
+            // The viewer removes the file once read, but may not have got to it
+            let _ = std::fs::remove_file(&path);
+        }
+        sent?;
+        Ok(())
+    }
+
+    /// Send 32-bit floats to the page through [`send_binary`](Self::send_binary),
+    /// in little-endian byte order so the page can read them with a
+    /// `Float32Array`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    ///
+    /// let page = r#"<p id="peak"></p>
+    /// <script>
+    ///   htmlView.onBinary('samples', function (buffer) {
+    ///     var samples = new Float32Array(buffer);
+    ///     var peak = samples.reduce(function (a, b) { return Math.max(a, Math.abs(b)); }, 0);
+    ///     document.getElementById('peak').textContent = 'Peak: ' + peak;
+    ///   });
+    /// </script>"#;
+    /// let result = ViewerOptions::new().non_blocking().show_html(page).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     let samples: Vec<f32> = (0..1_000_000)
+    ///         .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 44_100.0).sin())
+    ///         .collect();
+    ///     handle.send_f32("samples", &samples).unwrap();
+    /// }
+    /// ```
+    pub fn send_f32(&mut self, channel: &str, values: &[f32]) -> Result<(), ViewerError> {
+        self.send_binary(channel, &le_bytes(values, f32::to_le_bytes))
+    }
+
+    /// Send 16-bit integers, such as PCM samples, to the page through
+    /// [`send_binary`](Self::send_binary), in little-endian byte order so the
+    /// page can read them with an `Int16Array`.
+    pub fn send_i16(&mut self, channel: &str, values: &[i16]) -> Result<(), ViewerError> {
+        self.send_binary(channel, &le_bytes(values, i16::to_le_bytes))
+    }
+
+    /// Append entries to the [`ViewerContent::Log`] being shown.
+    ///
+    /// [`LogHandle`](crate::LogHandle) wraps this for the common case of
+    /// appending one line at a time.
+    pub fn append_log(&mut self, entries: Vec<LogEntry>) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::AppendLog { seq, entries })?;
+        Ok(())
+    }
+
+    /// Add a tab to the [`ViewerContent::Tabs`] being shown and select it.
+    ///
+    /// Fails if a tab with the same id is already open.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    ///
+    /// let reports = [("run-1", "<h1>Run 1</h1>"), ("run-2", "<h1>Run 2</h1>")];
+    /// let result = ViewerOptions::new().non_blocking().show_tabs(
+    ///     reports.map(|(id, html)| (id, ViewerOptions::inline_html(html).content)),
+    /// ).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     handle.add_tab("run-3", ViewerOptions::inline_html("<h1>Run 3</h1>").content).unwrap();
+    ///     handle.refresh_tab("run-1", ViewerOptions::inline_html("<h1>Run 1, again</h1>").content).unwrap();
+    ///     handle.close_tab("run-2").unwrap();
+    /// }
+    /// ```
+    pub fn add_tab<S: Into<String>>(
+        &mut self,
+        id: S,
+        content: ViewerContent,
+    ) -> Result<(), ViewerError> {
+        let id = id.into();
+        self.send_command(|seq| ViewerCommand::AddTab { seq, id, content })?;
+        Ok(())
+    }
+
+    /// Close a tab.
+    pub fn close_tab(&mut self, id: &str) -> Result<(), ViewerError> {
+        let id = id.to_string();
+        self.send_command(|seq| ViewerCommand::CloseTab { seq, id })?;
+        Ok(())
+    }
+
+    /// Select a tab.
+    pub fn select_tab(&mut self, id: &str) -> Result<(), ViewerError> {
+        let id = id.to_string();
+        self.send_command(|seq| ViewerCommand::SelectTab { seq, id })?;
+        Ok(())
+    }
+
+    /// Replace the content of one tab, leaving the others untouched.
+    pub fn refresh_tab(&mut self, id: &str, content: ViewerContent) -> Result<(), ViewerError> {
+        let id = id.to_string();
+        self.send_command(|seq| ViewerCommand::RefreshTab { seq, id, content })?;
+        Ok(())
+    }
+
+    /// Replace the content of one cell of the [`ViewerContent::Layout`] being
+    /// shown, leaving the others untouched. The cell is the one whose
+    /// top-left corner is at `row` and `column`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{Layout, ViewerOptions, ViewerResult};
+    ///
+    /// let layout = Layout::grid(1, 2)
+    ///     .cell(0, 0, "<p>Step 0</p>")
+    ///     .cell(0, 1, "<p>Notes</p>");
+    /// let result = ViewerOptions::new().non_blocking().show_layout(layout).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     for step in 1..10 {
+    ///         handle.refresh_cell_html(0, 0, format!("<p>Step {step}</p>")).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn refresh_cell(
+        &mut self,
+        row: usize,
+        column: usize,
+        content: ViewerContent,
+    ) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::RefreshCell {
+            seq,
+            row,
+            column,
+            content,
+        })?;
+        Ok(())
+    }
+
+    /// Replace one cell of the layout being shown with an HTML fragment.
+    pub fn refresh_cell_html<S: Into<String>>(
+        &mut self,
+        row: usize,
+        column: usize,
+        html: S,
+    ) -> Result<(), ViewerError> {
+        self.refresh_cell(
+            row,
+            column,
+            ViewerContent::InlineHtml {
+                html: html.into(),
+                base_dir: None,
+            },
+        )
+    }
+
+    /// Navigate the viewer to a new URL without restarting the process.
+    ///
+    /// The viewer checks the target against the [`BehaviourOptions`] it was
+    /// launched with: unless `allow_external_navigation` is set, the target
+    /// must have the origin of the content shown; remote URLs require
+    /// `allow_remote_content`, and must match `allowed_domains` when that list
+    /// is set.
+    ///
+    /// [`BehaviourOptions`]: html_view_shared::BehaviourOptions
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerWaitMode, ViewerResult};
+    /// use url::Url;
+    ///
+    /// let mut options = ViewerOptions::remote_url(Url::parse("https://example.com").unwrap());
+    /// options.wait = ViewerWaitMode::NonBlocking;
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = html_view::open(options).unwrap() {
+    ///     handle.navigate(Url::parse("https://example.com/about").unwrap()).unwrap();
+    ///     handle.back().unwrap();
+    /// }
+    /// ```
+    pub fn navigate(&mut self, url: Url) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::Navigate { seq, url })?;
+        Ok(())
+    }
+
+    /// Go back one entry in the viewer's navigation history.
+    pub fn back(&mut self) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::Back { seq })?;
+        Ok(())
+    }
+
+    /// Go forward one entry in the viewer's navigation history.
+    pub fn forward(&mut self) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::Forward { seq })?;
+        Ok(())
+    }
+
+    /// Reload the current page.
+    ///
+    /// If `ignore_cache` is true, the webview's cached browsing data is cleared
+    /// before reloading so that all resources are fetched again.
+    pub fn reload(&mut self, ignore_cache: bool) -> Result<(), ViewerError> {
+        self.send_command(|seq| ViewerCommand::Reload { seq, ignore_cache })?;
+        Ok(())
+    }
+
+    /// Get the URL currently displayed in the viewer.
+    ///
+    /// Inline HTML is loaded through a `data:` URL, so this is mostly useful
+    /// for local files, application directories and remote URLs.
+    pub fn current_url(&mut self) -> Result<Url, ViewerError> {
+        match self.send_command(|seq| ViewerCommand::CurrentUrl { seq })? {
+            Some(ViewerCommandData::CurrentUrl { url }) => Ok(url),
+            other => Err(ViewerError::InvalidResponse(format!(
+                "Expected current URL, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Query the current window geometry and page state.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerWaitMode, ViewerResult};
+    ///
+    /// let mut options = ViewerOptions::inline_html("<title>Report</title><h1>Hi</h1>");
+    /// options.wait = ViewerWaitMode::NonBlocking;
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = html_view::open(options).unwrap() {
+    ///     let state = handle.query_state().unwrap();
+    ///     println!(
+    ///         "{} at {}x{} ({:?})",
+    ///         state.title, state.geometry.width, state.geometry.height, state.load_state
+    ///     );
+    /// }
+    /// ```
+    pub fn query_state(&mut self) -> Result<ViewerState, ViewerError> {
+        match self.send_command(|seq| ViewerCommand::QueryState { seq })? {
+            Some(ViewerCommandData::State { state }) => Ok(state),
+            other => Err(ViewerError::InvalidResponse(format!(
+                "Expected viewer state, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Return the events reported by the viewer since the last call.
+    ///
+    /// This is non-blocking and returns an empty list if nothing new happened.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerEvent, ViewerOptions, ViewerResult};
+    ///
+    /// let result = ViewerOptions::new().non_blocking().show_html("<h1>Hi</h1>").unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     std::thread::sleep(std::time::Duration::from_secs(1));
+    ///     for event in handle.poll_events().unwrap() {
+    ///         if let ViewerEvent::LoadFailed { error, .. } = event {
+    ///             eprintln!("load failed: {}", error);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn poll_events(&mut self) -> Result<Vec<ViewerEvent>, ViewerError> {
+        self.read_events()?;
+        Ok(std::mem::take(&mut self.pending_events))
+    }
+
+    /// Block until the viewer reaches the given startup stage.
+    pub(crate) fn wait_for_stage(
+        &mut self,
+        stage: WaitUntil,
+        timeout: Duration,
+    ) -> Result<(), ViewerError> {
+        const INITIAL_DELAY_MS: u64 = 10;
+        const MAX_DELAY_MS: u64 = 100;
+
+        let start_time = Instant::now();
+        let mut delay_ms = INITIAL_DELAY_MS;
+
+        loop {
+            // Check for exit before reading so events written just before exiting are seen
+            let exited = self.child().try_wait()?.is_some();
+            self.read_events()?;
+
+            for event in &self.pending_events {
+                match (stage, event) {
+                    (WaitUntil::Spawned, _)
+                    | (WaitUntil::WindowShown, ViewerEvent::WindowShown)
+                    | (WaitUntil::ContentLoaded, ViewerEvent::Loaded { .. }) => return Ok(()),
+                    (WaitUntil::ContentLoaded, ViewerEvent::LoadFailed { url, error }) => {
+                        return Err(ViewerError::LoadFailed(match url {
+                            Some(url) => format!("{}: {}", url, error),
+                            None => error.clone(),
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+
+            if exited {
+                return Err(ViewerError::CommandFailed(format!(
+                    "viewer exited before reaching {:?}",
+                    stage
+                )));
+            }
+
+            if start_time.elapsed() >= timeout {
+                return Err(ViewerError::Timeout);
+            }
+
+            std::thread::sleep(Duration::from_millis(delay_ms));
+            delay_ms = (delay_ms * 2).min(MAX_DELAY_MS);
+        }
+    }
+
+    /// Move newly written events into the pending list.
+    fn read_events(&mut self) -> Result<(), ViewerError> {
+        if let Some(reader) = &mut self.events {
+            self.pending_events.extend(reader.read()?);
+        }
+        Ok(())
+    }
+
+    /// Write a command for the viewer and wait for its response.
+    fn send_command(
+        &mut self,
+        build: impl FnOnce(u64) -> ViewerCommand,
+    ) -> Result<Option<ViewerCommandData>, ViewerError> {
+        // Check process is still alive first
+        if self.try_wait()?.is_some() {
+            return Err(ViewerError::CommandFailed("Process has exited".to_string()));
+        }
+
+        // Verify viewer supports commands
+        let command_path = self.command_path.clone().ok_or_else(|| {
+            ViewerError::RefreshNotSupported(
+                "Viewer was launched without command support".to_string(),
+            )
+        })?;
+
+        // Create command with sequence number
+        let seq = self.command_seq.fetch_add(1, Ordering::SeqCst);
+        let command = build(seq);
+
+        // Write command atomically (temp file + rename)
+        let temp_path = self.temp_dir.join(format!("command_{}.tmp", seq));
+        let command_json = serde_json::to_string(&command)
+            .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize command: {}", e)))?;
+        std::fs::write(&temp_path, &command_json)?;
+        std::fs::rename(&temp_path, &command_path)?;
+
+        // Wait for response
+        self.wait_for_response(seq, Duration::from_secs(5))
+    }
+
+    fn wait_for_response(
+        &mut self,
+        seq: u64,
+        timeout: Duration,
+    ) -> Result<Option<ViewerCommandData>, ViewerError> {
         let response_path = self.response_path.as_ref().ok_or_else(|| {
             ViewerError::RefreshNotSupported("No response path configured".to_string())
         })?;
@@ -236,7 +742,8 @@ impl ViewerHandle {
             }
 
             // Bail out early if the viewer process has already exited.
-            if let Some(_status) = self.child.try_wait()? {
+            let exited = self.child().try_wait()?.is_some();
+            if exited {
                 // Try to surface the exit reason if available.
                 let message = match self.read_result_file() {
                     Ok(status) => format!(
@@ -256,7 +763,7 @@ impl ViewerHandle {
                 match serde_json::from_str::<ViewerCommandResponse>(&data) {
                     Ok(response) if response.seq == seq => {
                         if response.success {
-                            return Ok(());
+                            return Ok(response.data);
                         } else {
                             return Err(ViewerError::CommandFailed(
                                 response
@@ -328,6 +835,9 @@ impl ViewerHandle {
                 id: self.id,
                 reason: ViewerExitReason::ClosedByUser,
                 viewer_version: PROTOCOL_VERSION.to_string(),
+                geometry: None,
+                audio_selection: None,
+                slide: None,
             });
         }
 
@@ -432,9 +942,44 @@ fn check_version_compatibility(viewer_version: &str) -> Result<(), ViewerError>
     Ok(())
 }
 
+/// Concatenate the little-endian bytes of `values`, as typed arrays on the
+/// page read them.
+fn le_bytes<T: Copy, const N: usize>(values: &[T], to_le_bytes: fn(T) -> [u8; N]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|&value| to_le_bytes(value))
+        .collect()
+}
+
+/// Whether the viewer process has exited. A process locked elsewhere, such
+/// as by [`ViewerHandle::wait`], is taken to be running.
+fn has_exited(child: &Mutex<Child>) -> bool {
+    child
+        .try_lock()
+        .is_ok_and(|mut child| !matches!(child.try_wait(), Ok(None)))
+}
+
 impl Drop for ViewerHandle {
     fn drop(&mut self) {
         // Best effort cleanup - ignore errors
         let _ = std::fs::remove_dir_all(&self.temp_dir);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_le_bytes_layout() {
+        assert_eq!(
+            le_bytes(&[1.0f32, -2.5], f32::to_le_bytes),
+            [0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x20, 0xc0]
+        );
+        assert_eq!(
+            le_bytes(&[1i16, -2, 0x1234], i16::to_le_bytes),
+            [0x01, 0x00, 0xfe, 0xff, 0x34, 0x12]
+        );
+        assert!(le_bytes(&[] as &[f32], f32::to_le_bytes).is_empty());
+    }
+}
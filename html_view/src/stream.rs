@@ -0,0 +1,271 @@
+//! Streaming frames to a running viewer.
+
+use crate::ViewerError;
+use html_view_shared::{StreamFrame, StreamMessage};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Shortest time between two frames written for the viewer, capping the
+/// stream at 60 frames per second.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// How often an idle writer checks whether every sender was dropped or the
+/// viewer exited.
+const IDLE_CHECK: Duration = Duration::from_millis(100);
+
+/// Sends frames to a running viewer without waiting for it, returned by
+/// [`ViewerHandle::stream`](crate::ViewerHandle::stream).
+///
+/// Sending only replaces the frame waiting to be passed on, so a burst of
+/// frames is coalesced and the viewer gets the latest one. Frames reach the
+/// page at most 60 times a second and are applied on its next animation
+/// frame; while frames arrive, the viewer reports how many were applied and
+/// dropped as [`ViewerEvent::StreamStats`](crate::ViewerEvent::StreamStats).
+///
+/// Clones send to the same stream, so a sender can be moved to the thread
+/// producing the frames.
+#[derive(Debug, Clone)]
+pub struct StreamSender {
+    shared: Arc<Shared>,
+}
+
+/// State shared by the senders and the writer thread.
+#[derive(Debug, Default)]
+struct Shared {
+    slot: Mutex<Slot>,
+    ready: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    /// Number of the next frame sent.
+    next_seq: u64,
+
+    /// The latest frame, if it was not written yet.
+    pending: Option<StreamMessage>,
+
+    /// Why the stream stopped, once the viewer exited or writing a frame
+    /// failed.
+    error: Option<String>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Slot> {
+        self.slot.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl StreamSender {
+    /// Start the thread writing frames to `path`, which stops once
+    /// `exited` tells that the viewer exited.
+    pub(crate) fn start<F>(path: PathBuf, exited: F) -> Result<Self, ViewerError>
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+        let writer = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name("html_view-stream".to_string())
+            .spawn(move || write_frames(&writer, &path, exited))?;
+        Ok(Self { shared })
+    }
+
+    /// Send a frame, replacing the previous one if it was not passed on yet.
+    ///
+    /// This never blocks on the viewer. Fails once the stream has stopped,
+    /// for example because the viewer was closed.
+    pub fn send(&self, frame: StreamFrame) -> Result<(), ViewerError> {
+        let mut slot = self.shared.lock();
+        if let Some(error) = &slot.error {
+            return Err(ViewerError::CommandFailed(format!(
+                "stream stopped: {}",
+                error
+            )));
+        }
+        let seq = slot.next_seq;
+        slot.next_seq += 1;
+        slot.pending = Some(StreamMessage { seq, frame });
+        self.shared.ready.notify_one();
+        Ok(())
+    }
+
+    /// Send HTML replacing the content of the page's element marked with a
+    /// `data-html-view-stream` attribute, or of the whole body.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    ///
+    /// let page = r#"<h1>Level</h1><div data-html-view-stream></div>"#;
+    /// let result = ViewerOptions::new().non_blocking().show_html(page).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     let stream = handle.stream().unwrap();
+    ///     for step in 0..600 {
+    ///         let level = (step as f64 / 20.0).sin().abs() * 100.0;
+    ///         stream
+    ///             .send_html(format!("<meter max=100 value={:.0}></meter>", level))
+    ///             .unwrap();
+    ///         std::thread::sleep(std::time::Duration::from_millis(5));
+    ///     }
+    /// }
+    /// ```
+    pub fn send_html<S: Into<String>>(&self, html: S) -> Result<(), ViewerError> {
+        self.send(StreamFrame::Html { html: html.into() })
+    }
+
+    /// Send data to the callbacks the page registered with
+    /// `htmlView.onFrame`, which are called with the data and the frame
+    /// number.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use html_view::{ViewerOptions, ViewerResult};
+    ///
+    /// let page = r#"<progress id="loss" max="1"></progress>
+    /// <script>
+    ///   htmlView.onFrame(function (data) {
+    ///     document.getElementById('loss').value = data.loss;
+    ///   });
+    /// </script>"#;
+    /// let result = ViewerOptions::new().non_blocking().show_html(page).unwrap();
+    ///
+    /// if let ViewerResult::NonBlocking(mut handle) = result {
+    ///     let stream = handle.stream().unwrap();
+    ///     for step in 1..1000 {
+    ///         let loss = 1.0 / step as f64;
+    ///         stream.send_data(&serde_json::json!({ "loss": loss })).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn send_data<T: Serialize + ?Sized>(&self, data: &T) -> Result<(), ViewerError> {
+        let data = serde_json::to_value(data)
+            .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize data: {}", e)))?;
+        self.send(StreamFrame::Data { data })
+    }
+}
+
+/// Write the latest frame to `path` until every sender is dropped, the
+/// viewer exits or a write fails, waiting at least [`FRAME_INTERVAL`]
+/// between two frames so the frames sent meanwhile are coalesced.
+fn write_frames(shared: &Arc<Shared>, path: &Path, exited: impl Fn() -> bool) {
+    loop {
+        let message = {
+            let mut slot = shared.lock();
+            loop {
+                if let Some(message) = slot.pending.take() {
+                    break Some(message);
+                }
+                // Only this thread is left holding the stream
+                if Arc::strong_count(shared) == 1 {
+                    return;
+                }
+                let (guard, wait) = shared
+                    .ready
+                    .wait_timeout(slot, IDLE_CHECK)
+                    .unwrap_or_else(|e| e.into_inner());
+                slot = guard;
+                if wait.timed_out() {
+                    break None;
+                }
+            }
+        };
+
+        if exited() {
+            shared.lock().error = Some("the viewer has exited".to_string());
+            return;
+        }
+        let Some(message) = message else {
+            continue;
+        };
+
+        let started = Instant::now();
+        if let Err(e) = write_message(path, &message) {
+            shared.lock().error = Some(e.to_string());
+            return;
+        }
+        std::thread::sleep(FRAME_INTERVAL.saturating_sub(started.elapsed()));
+    }
+}
+
+/// Replace the stream file atomically (temp file + rename).
+fn write_message(path: &Path, message: &StreamMessage) -> std::io::Result<()> {
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, serde_json::to_vec(message)?)?;
+    std::fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn read_message(path: &Path) -> Option<StreamMessage> {
+        let data = std::fs::read(path).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    #[test]
+    fn test_stream_coalesces_to_latest_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stream.json");
+        let sender = StreamSender::start(path.clone(), || false).unwrap();
+
+        for i in 0..100 {
+            sender.clone().send_data(&i).unwrap();
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut latest = None;
+        while Instant::now() < deadline {
+            latest = read_message(&path).filter(|message| message.seq == 99);
+            if latest.is_some() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let latest = latest.expect("latest frame was not written");
+        assert_eq!(
+            latest.frame,
+            StreamFrame::Data {
+                data: serde_json::json!(99)
+            }
+        );
+    }
+
+    #[test]
+    fn test_stream_stops_when_writing_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("stream.json");
+        let sender = StreamSender::start(path, || false).unwrap();
+        sender.send_html("<p>1</p>").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while sender.send_html("<p>2</p>").is_ok() {
+            assert!(Instant::now() < deadline, "stream did not stop");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_stream_stops_when_viewer_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let exited = Arc::new(AtomicBool::new(false));
+        let viewer = Arc::clone(&exited);
+        let sender = StreamSender::start(dir.path().join("stream.json"), move || {
+            viewer.load(Ordering::SeqCst)
+        })
+        .unwrap();
+        sender.send_html("<p>1</p>").unwrap();
+
+        // Stops while idle, without another frame being sent
+        exited.store(true, Ordering::SeqCst);
+        std::thread::sleep(IDLE_CHECK * 3);
+        let err = sender.send_html("<p>2</p>").unwrap_err();
+        assert!(err.to_string().contains("viewer has exited"), "{}", err);
+    }
+}
@@ -0,0 +1,198 @@
+//! Conversion of `Serialize` rows into a [`TableSource`].
+
+use crate::ViewerError;
+use html_view_shared::TableSource;
+use serde::Serialize;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use std::fmt;
+
+/// Column used for rows that serialize to a scalar.
+const VALUE_COLUMN: &str = "value";
+
+/// Serialize `rows` into columns and row values.
+///
+/// Rows that serialize to objects contribute their keys as columns, in the
+/// order they are first seen; missing keys become empty cells. Rows that
+/// serialize to arrays (tuples) use their indices as columns, and scalars
+/// are placed in a single `value` column.
+pub(crate) fn table_source<I, T>(rows: I) -> Result<TableSource, ViewerError>
+where
+    I: IntoIterator<Item = T>,
+    T: Serialize,
+{
+    let mut columns: Vec<String> = Vec::new();
+    let mut records: Vec<Vec<(usize, Value)>> = Vec::new();
+
+    for row in rows {
+        // Read back from the serialized JSON rather than a `Value`, whose
+        // objects do not keep the order of their keys
+        let json = serde_json::to_string(&row)
+            .map_err(|e| ViewerError::SerdeError(format!("Failed to serialize row: {}", e)))?;
+        let Cells(cells) = serde_json::from_str(&json)
+            .map_err(|e| ViewerError::SerdeError(format!("Failed to read row: {}", e)))?;
+
+        let record = cells
+            .into_iter()
+            .map(|(name, value)| {
+                let index = match columns.iter().position(|column| *column == name) {
+                    Some(index) => index,
+                    None => {
+                        columns.push(name);
+                        columns.len() - 1
+                    }
+                };
+                (index, value)
+            })
+            .collect();
+        records.push(record);
+    }
+
+    let rows = records
+        .into_iter()
+        .map(|record| {
+            let mut row = vec![Value::Null; columns.len()];
+            for (index, value) in record {
+                row[index] = value;
+            }
+            row
+        })
+        .collect();
+
+    Ok(TableSource::Rows { columns, rows })
+}
+
+/// The cells of a row as column names and values, in serialization order.
+struct Cells(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Cells {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CellsVisitor)
+    }
+}
+
+struct CellsVisitor;
+
+impl CellsVisitor {
+    fn scalar<E>(value: Value) -> Result<Cells, E> {
+        Ok(Cells(vec![(VALUE_COLUMN.to_string(), value)]))
+    }
+}
+
+impl<'de> Visitor<'de> for CellsVisitor {
+    type Value = Cells;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a table row")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Cells, A::Error> {
+        let mut cells = Vec::new();
+        while let Some(cell) = map.next_entry()? {
+            cells.push(cell);
+        }
+        Ok(Cells(cells))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Cells, A::Error> {
+        let mut cells = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            cells.push((cells.len().to_string(), item));
+        }
+        Ok(Cells(cells))
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Cells, E> {
+        Self::scalar(v.into())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Cells, E> {
+        Self::scalar(v.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Cells, E> {
+        Self::scalar(v.into())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Cells, E> {
+        Self::scalar(v.into())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Cells, E> {
+        Self::scalar(v.into())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Cells, E> {
+        Self::scalar(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Run {
+        name: &'static str,
+        score: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<&'static str>,
+    }
+
+    #[test]
+    fn test_struct_rows_use_field_names() {
+        let runs = vec![
+            Run {
+                name: "a",
+                score: 0.5,
+                note: None,
+            },
+            Run {
+                name: "b",
+                score: 0.75,
+                note: Some("best"),
+            },
+        ];
+
+        let TableSource::Rows { columns, rows } = table_source(&runs).unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(columns, vec!["name", "score", "note"]);
+        assert_eq!(
+            rows,
+            vec![
+                vec![json!("a"), json!(0.5), Value::Null],
+                vec![json!("b"), json!(0.75), json!("best")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tuple_and_scalar_rows() {
+        let TableSource::Rows { columns, rows } = table_source([(1, "x"), (2, "y")]).unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(columns, vec!["0", "1"]);
+        assert_eq!(rows[1], vec![json!(2), json!("y")]);
+
+        let TableSource::Rows { columns, rows } = table_source([1, 2, 3]).unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(columns, vec!["value"]);
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn test_empty_rows() {
+        let rows: Vec<Run> = Vec::new();
+        assert_eq!(
+            table_source(rows).unwrap(),
+            TableSource::Rows {
+                columns: Vec::new(),
+                rows: Vec::new(),
+            }
+        );
+    }
+}
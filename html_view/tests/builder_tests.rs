@@ -51,6 +51,34 @@ fn test_builder_working_dir() {
     // Builder should accept PathBuf for working_dir
 }
 
+#[test]
+fn test_builder_placement_helpers() {
+    let _centered = ViewerOptions::new().center().size_percent(60.0, 70.0);
+    let _on_parent = ViewerOptions::new().center_on_parent(100, 100, 1280, 800);
+    let _on_monitor = ViewerOptions::new().monitor(1).min_size(400, 300);
+    let _named = ViewerOptions::new()
+        .monitor_named("DP-1")
+        .max_size(1920, 1080);
+}
+
+#[test]
+fn test_builder_state_key() {
+    let _builder = ViewerOptions::new().state_key("review-window");
+    // Builder should accept any string-like key
+}
+
+#[test]
+fn test_builder_page_diagnostics() {
+    let _builder = ViewerOptions::new().capture_console().fail_on_page_error();
+    // Should enable console forwarding and failing on page errors
+}
+
+#[test]
+fn test_builder_refresh_history() {
+    let _builder = ViewerOptions::new().non_blocking().refresh_history(20);
+    // Should keep the last 20 refreshed contents
+}
+
 #[test]
 fn test_builder_allow_remote_content() {
     let _builder = ViewerOptions::new().allow_remote_content();
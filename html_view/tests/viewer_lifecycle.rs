@@ -3,7 +3,7 @@
 //! These tests require the html_view_app binary to be built.
 //! They will be skipped if the binary is not available.
 
-use html_view::{ViewerOptions, ViewerWaitMode};
+use html_view::{AudioSource, DiagramKind, Layout, ViewerOptions, ViewerWaitMode};
 use html_view_shared::ViewerContent;
 
 #[test]
@@ -291,6 +291,93 @@ fn test_viewer_content_variants() {
         url: Url::parse("https://example.com").unwrap(),
     };
     assert!(matches!(url, ViewerContent::RemoteUrl { .. }));
+
+    let markdown = ViewerOptions::markdown("# Test").content;
+    assert!(matches!(
+        markdown,
+        ViewerContent::Markdown { ref source, base_dir: None } if source == "# Test"
+    ));
+
+    let image = ViewerOptions::image(PathBuf::from("/tmp/plot.png")).content;
+    assert!(matches!(
+        image,
+        ViewerContent::Image {
+            source: html_view::ImageSource::Path(_),
+            mime: None
+        }
+    ));
+
+    let json = ViewerOptions::json(serde_json::json!({ "a": [1, 2] })).content;
+    assert!(matches!(json, ViewerContent::Json { ref value } if value["a"][1] == 2));
+
+    let code = ViewerOptions::code("fn main() {}", Some("rust")).content;
+    assert!(matches!(
+        code,
+        ViewerContent::Code {
+            ref language,
+            line_numbers: true,
+            ref highlight_lines,
+            ..
+        } if language.as_deref() == Some("rust") && highlight_lines.is_empty()
+    ));
+
+    let diff = ViewerOptions::diff("a\n", "b\n").content;
+    assert!(matches!(
+        diff,
+        ViewerContent::Diff {
+            mode: html_view::DiffMode::Unified,
+            language: None,
+            ..
+        }
+    ));
+
+    let ansi = ViewerOptions::ansi_text("\x1b[31mred").content;
+    assert!(matches!(ansi, ViewerContent::AnsiText { ref text } if text.ends_with("red")));
+
+    let log = ViewerOptions::log().content;
+    assert!(matches!(log, ViewerContent::Log { max_lines: None }));
+
+    let audio = ViewerOptions::audio(AudioSource::mono(vec![0.0; 4], 8000)).content;
+    assert!(matches!(
+        audio,
+        ViewerContent::Audio {
+            source: AudioSource::Samples { channels: 1, .. },
+            spectrogram: false,
+        }
+    ));
+
+    let diagram = ViewerOptions::diagram("digraph { a -> b }", DiagramKind::Dot).content;
+    assert!(matches!(
+        diagram,
+        ViewerContent::Diagram {
+            kind: DiagramKind::Dot,
+            ..
+        }
+    ));
+
+    let tabs = ViewerOptions::tabs([
+        ("a", ViewerOptions::inline_html("<p>a</p>").content),
+        ("b", ViewerOptions::markdown("b").content),
+    ])
+    .content;
+    let ViewerContent::Tabs { tabs } = tabs else {
+        panic!("expected tabs");
+    };
+    assert_eq!(tabs[0].0, "a");
+    assert!(matches!(tabs[1].1, ViewerContent::Markdown { .. }));
+
+    let layout = ViewerOptions::layout(Layout::vstack(["<p>a</p>", "<p>b</p>"])).content;
+    let ViewerContent::Layout { layout } = layout else {
+        panic!("expected a layout");
+    };
+    assert_eq!((layout.rows, layout.columns), (2, 1));
+    assert_eq!(layout.cells[1].row, 1);
+
+    let slides = ViewerOptions::slides([ViewerOptions::markdown("a").content]).content;
+    assert!(matches!(
+        slides,
+        ViewerContent::Slides { ref slides, advance_seconds: None } if slides.len() == 1
+    ));
 }
 
 #[test]
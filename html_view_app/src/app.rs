@@ -1,44 +1,232 @@
+use crate::binary::BinaryStore;
+use crate::bundle::BundleStore;
+use crate::events::{EventSink, PageError, is_placeholder_url};
+use crate::history::HistoryList;
+use crate::layout::CellList;
+use crate::log_view::LogBuffer;
+use crate::tabs::TabList;
 use anyhow::{Context, Result};
+use html_view_shared::render::script_json;
 use html_view_shared::{
-    PROTOCOL_VERSION, ViewerCommand, ViewerCommandResponse, ViewerContent, ViewerExitReason,
-    ViewerExitStatus, ViewerRequest, WindowOptions,
+    AudioSelection, ConsoleLevel, DEFAULT_WINDOW_SIZE, LoadState, LogEntry, MonitorSelector,
+    PROTOCOL_VERSION, ViewerCommand, ViewerCommandData, ViewerCommandResponse, ViewerContent,
+    ViewerEvent, ViewerExitReason, ViewerExitStatus, ViewerRequest, ViewerState, WindowCenter,
+    WindowGeometry, WindowOptions, is_bundle_url,
 };
 use notify::{Event, RecursiveMode, Watcher};
 use std::path::PathBuf;
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::{AppHandle, LogicalPosition, LogicalSize, Manager, Position, State, WebviewWindow};
+use tauri::ipc::{CommandArg, CommandItem, InvokeError};
+use tauri::webview::PageLoadEvent;
+use tauri::{
+    AppHandle, LogicalPosition, LogicalSize, Manager, Monitor, PhysicalPosition, Position, Runtime,
+    Size, State, WebviewWindow, WebviewWindowBuilder, WindowEvent,
+};
 use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_notification::NotificationExt;
+use url::Url;
+
+/// Runtime state of the page shown in the main window.
+struct PageState {
+    /// Load state, updated from the webview's page load hook.
+    load_state: Mutex<LoadState>,
+
+    /// Document title, updated whenever the page changes it.
+    title: Mutex<String>,
+
+    /// Current webview zoom factor.
+    zoom: Mutex<f64>,
+
+    /// Content currently displayed, replaced on refresh.
+    content: Mutex<ViewerContent>,
+}
+
+impl PageState {
+    fn new(content: ViewerContent) -> Self {
+        Self {
+            load_state: Mutex::new(LoadState::default()),
+            title: Mutex::new(String::new()),
+            zoom: Mutex::new(1.0),
+            content: Mutex::new(content),
+        }
+    }
+}
+
+/// What the webview a command was invoked from shows, taken by a command
+/// as one argument.
+struct ShownContent<'r> {
+    url: Option<Url>,
+    page: State<'r, PageState>,
+    history: State<'r, HistoryList>,
+}
+
+impl ShownContent<'_> {
+    /// Whether the webview shows a built-in viewer that saves files as its
+    /// own bundle page. Frames of tabs, layouts, slides and the refresh
+    /// history share that origin with other content, so they do not count.
+    fn exports_files(&self) -> bool {
+        self.url.as_ref().is_some_and(is_bundle_url)
+            && !self.history.is_active()
+            && self.page.content.lock().as_deref().is_ok_and(exports_files)
+    }
+}
+
+impl<'r, R: Runtime> CommandArg<'r, R> for ShownContent<'r> {
+    fn from_command(command: CommandItem<'r, R>) -> Result<Self, InvokeError> {
+        let webview = command.message.webview_ref();
+        Ok(Self {
+            url: webview.url().ok(),
+            page: webview.state(),
+            history: webview.state(),
+        })
+    }
+}
 
 /// Run the Tauri application with the given request.
 pub fn run_app(request: ViewerRequest) -> Result<ViewerExitStatus> {
     // Store the request and exit reason in shared state
     let request_arc = Arc::new(request.clone());
     let exit_reason = Arc::new(Mutex::new(ViewerExitReason::ClosedByUser));
+    let final_geometry = Arc::new(Mutex::new(None));
+    let selection = Arc::new(Mutex::new(None::<AudioSelection>));
+    let slide = Arc::new(Mutex::new(None::<usize>));
 
     // Clone for use in closures
     let exit_reason_for_timeout = exit_reason.clone();
     let _request_for_timeout = request_arc.clone();
+    let final_geometry_for_close = final_geometry.clone();
+    let events = EventSink::new(request.event_path.as_ref());
 
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             toolbar_action,
             show_notification,
             show_message_dialog,
-            show_open_dialog
+            show_open_dialog,
+            export_file,
+            audio_selection,
+            slide_shown,
+            toggle_fullscreen,
+            zoom_step,
+            dom_content_loaded,
+            page_console,
+            page_error,
+            crate::log_view::log_entries,
+            crate::tabs::tab_list,
+            crate::tabs::tab_selected,
+            crate::tabs::tab_closed,
+            crate::history::history_list,
+            crate::stream::stream_stats,
+            crate::binary::binary_take
         ])
+        .manage(PageState::new(request.content.clone()))
+        .manage(events)
+        .manage(exit_reason.clone())
+        .manage(BundleStore::default())
+        .manage(BinaryStore::default())
+        .manage(LogBuffer::default())
+        .manage(TabList::default())
+        .manage(CellList::default())
+        .manage(HistoryList::default())
+        .manage(selection.clone())
+        .manage(slide.clone())
+        .register_uri_scheme_protocol(crate::bundle::SCHEME, crate::bundle::handle)
+        .on_page_load(|webview, payload| {
+            if is_placeholder_url(payload.url()) {
+                return;
+            }
+
+            let load_state = match payload.event() {
+                PageLoadEvent::Started => LoadState::Loading,
+                PageLoadEvent::Finished => LoadState::Loaded,
+            };
+            if let Ok(mut state) = webview.state::<PageState>().load_state.lock() {
+                *state = load_state;
+            }
+
+            match load_state {
+                // Bytes the previous page did not take will never be taken
+                LoadState::Loading => webview.state::<BinaryStore>().clear(),
+                LoadState::Loaded => webview.state::<EventSink>().emit(&ViewerEvent::Loaded {
+                    url: payload.url().clone(),
+                }),
+            }
+        })
         .setup(move |app| {
             app.manage(request_arc.clone());
 
-            let window = app
-                .get_webview_window("main")
-                .context("Main window not found")?;
+            // The main window is built here rather than from the config so that
+            // webview hooks such as title tracking can be attached to it.
+            let window_config = app
+                .config()
+                .app
+                .windows
+                .first()
+                .cloned()
+                .context("Main window config not found")?;
+            let mut builder = WebviewWindowBuilder::from_config(app.handle(), &window_config)?
+                .on_document_title_changed(|window, title| {
+                    if let Ok(mut state) = window.state::<PageState>().title.lock() {
+                        *state = title;
+                    }
+                })
+                .initialization_script(crate::window_state::ZOOM_SCRIPT)
+                .initialization_script(crate::events::LIFECYCLE_SCRIPT)
+                .initialization_script(crate::events::ERROR_SCRIPT)
+                .initialization_script(crate::stream::STREAM_SCRIPT)
+                .initialization_script(crate::binary::BINARY_SCRIPT);
+            if request_arc.behaviour.capture_console {
+                builder = builder.initialization_script(crate::events::CONSOLE_SCRIPT);
+            }
+            let window = builder.build().context("Failed to create main window")?;
+
+            // Restore saved geometry and zoom, letting explicit options win
+            let state_key = request_arc.window.state_key.clone();
+            let saved = state_key
+                .as_deref()
+                .and_then(|key| crate::window_state::load(app.handle(), key));
+            match &saved {
+                Some(saved) => {
+                    let options = crate::window_state::apply(&request_arc.window, saved);
+                    configure_window(&window, &options)?;
+                    set_zoom(&window, saved.zoom)?;
+                }
+                None => configure_window(&window, &request_arc.window)?,
+            }
+
+            // Capture the final geometry while the window still exists
+            let window_for_close = window.clone();
+            window.on_window_event(move |event| {
+                if let WindowEvent::CloseRequested { .. } = event
+                    && let Ok(geometry) = window_geometry(&window_for_close)
+                {
+                    if let Some(key) = &state_key {
+                        let zoom = current_zoom(&window_for_close);
+                        let app = window_for_close.app_handle();
+                        if let Err(e) = crate::window_state::save(app, key, &geometry, zoom) {
+                            eprintln!("Failed to save window state: {}", e);
+                        }
+                    }
+
+                    if let Ok(mut final_geometry) = final_geometry_for_close.lock() {
+                        *final_geometry = Some(geometry);
+                    }
+                }
+            });
 
-            configure_window(&window, &request_arc.window)?;
+            // The window starts hidden so it is only shown once configured
+            window.show()?;
+            app.state::<EventSink>().emit(&ViewerEvent::WindowShown);
 
-            crate::content_loader::load_content(&window, &request_arc)?;
+            if let Err(e) = crate::content_loader::load_content(&window, &request_arc) {
+                app.state::<EventSink>().emit(&ViewerEvent::LoadFailed {
+                    url: None,
+                    error: e.to_string(),
+                });
+                return Err(e.into());
+            }
 
             // Spawn command watcher if command_path is provided
             if let Some(command_path) = request_arc.command_path.clone() {
@@ -54,6 +242,17 @@ pub fn run_app(request: ViewerRequest) -> Result<ViewerExitStatus> {
                 });
             }
 
+            // Spawn stream watcher if stream_path is provided
+            if let Some(stream_path) = request_arc.stream_path.clone() {
+                let window_for_stream = window.clone();
+
+                std::thread::spawn(move || {
+                    if let Err(e) = crate::stream::watch_stream(stream_path, window_for_stream) {
+                        eprintln!("Stream watcher error: {}", e);
+                    }
+                });
+            }
+
             // Set up timeout if configured
             if let Some(timeout_secs) = request_arc.environment.timeout_seconds {
                 let window_for_timeout = window.clone();
@@ -62,8 +261,10 @@ pub fn run_app(request: ViewerRequest) -> Result<ViewerExitStatus> {
                 std::thread::spawn(move || {
                     std::thread::sleep(Duration::from_secs(timeout_secs));
 
-                    // Update exit reason
-                    if let Ok(mut reason) = exit_reason.lock() {
+                    // Update exit reason, keeping any page error already recorded
+                    if let Ok(mut reason) = exit_reason.lock()
+                        && matches!(*reason, ViewerExitReason::ClosedByUser)
+                    {
                         *reason = ViewerExitReason::TimedOut;
                     }
 
@@ -84,10 +285,16 @@ pub fn run_app(request: ViewerRequest) -> Result<ViewerExitStatus> {
 
     // Return the exit status
     let reason = exit_reason.lock().unwrap().clone();
+    let geometry = final_geometry.lock().unwrap().clone();
+    let audio_selection = *selection.lock().unwrap();
+    let slide = *slide.lock().unwrap();
     Ok(ViewerExitStatus {
         id: request.id,
         reason,
         viewer_version: PROTOCOL_VERSION.to_string(),
+        geometry,
+        audio_selection,
+        slide,
     })
 }
 
@@ -152,28 +359,228 @@ async fn show_open_dialog(
     Ok(file_path.map(|fp| fp.to_string()))
 }
 
+/// Save text produced by a built-in viewer, such as the table's CSV export
+/// or a diagram's SVG, to a file chosen in a save dialog.
+/// Returns false if the user cancelled.
+#[tauri::command]
+async fn export_file(
+    app: AppHandle,
+    request: State<'_, Arc<ViewerRequest>>,
+    shown: ShownContent<'_>,
+    file_name: String,
+    contents: String,
+) -> Result<bool, String> {
+    if !shown.exports_files() && !request.dialog.allow_file_dialogs {
+        return Err("File dialogs not allowed".to_string());
+    }
+
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .blocking_save_file()
+    else {
+        return Ok(false);
+    };
+    let path = file_path.into_path().map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    Ok(true)
+}
+
+/// Whether `content` is shown by a built-in viewer that saves files.
+fn exports_files(content: &ViewerContent) -> bool {
+    matches!(
+        content,
+        ViewerContent::Table { .. } | ViewerContent::Diagram { .. }
+    )
+}
+
+/// Record the selection made in the audio view. The last one is reported
+/// in the exit status.
+#[tauri::command]
+fn audio_selection(
+    events: State<'_, EventSink>,
+    state: State<'_, Arc<Mutex<Option<AudioSelection>>>>,
+    selection: Option<AudioSelection>,
+) {
+    if let Ok(mut current) = state.lock() {
+        *current = selection;
+    }
+    events.emit(&ViewerEvent::SelectionChanged { selection });
+}
+
+/// Record the slide shown in the slide view. The last one is reported in
+/// the exit status.
+#[tauri::command]
+fn slide_shown(
+    events: State<'_, EventSink>,
+    state: State<'_, Arc<Mutex<Option<usize>>>>,
+    index: usize,
+) {
+    if let Ok(mut current) = state.lock() {
+        *current = Some(index);
+    }
+    events.emit(&ViewerEvent::SlideChanged { index });
+}
+
+/// Toggle the window in or out of fullscreen and return whether it is now
+/// fullscreen.
+#[tauri::command]
+fn toggle_fullscreen(window: WebviewWindow) -> Result<bool, String> {
+    let fullscreen = !window.is_fullscreen().map_err(|e| e.to_string())?;
+    window
+        .set_fullscreen(fullscreen)
+        .map_err(|e| e.to_string())?;
+    Ok(fullscreen)
+}
+
+#[tauri::command]
+fn zoom_step(window: WebviewWindow, step: i32) -> Result<(), String> {
+    use crate::window_state::{MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
+
+    let zoom = if step == 0 {
+        1.0
+    } else {
+        (current_zoom(&window) + ZOOM_STEP * step as f64).clamp(MIN_ZOOM, MAX_ZOOM)
+    };
+
+    set_zoom(&window, zoom).map_err(|e| e.to_string())
+}
+
+/// Set the webview zoom factor and remember it.
+fn set_zoom(window: &WebviewWindow, zoom: f64) -> Result<()> {
+    window.set_zoom(zoom).context("Failed to set zoom")?;
+    if let Ok(mut state) = window.state::<PageState>().zoom.lock() {
+        *state = zoom;
+    }
+    Ok(())
+}
+
+/// The current webview zoom factor.
+fn current_zoom(window: &WebviewWindow) -> f64 {
+    window
+        .state::<PageState>()
+        .zoom
+        .lock()
+        .map(|zoom| *zoom)
+        .unwrap_or(1.0)
+}
+
+#[tauri::command]
+fn dom_content_loaded(events: State<'_, EventSink>, url: String) {
+    if let Ok(url) = Url::parse(&url)
+        && !is_placeholder_url(&url)
+    {
+        events.emit(&ViewerEvent::DomContentLoaded { url });
+    }
+}
+
+#[tauri::command]
+fn page_console(
+    events: State<'_, EventSink>,
+    state: State<'_, Arc<ViewerRequest>>,
+    level: ConsoleLevel,
+    message: String,
+) {
+    if state.behaviour.capture_console {
+        events.emit(&ViewerEvent::Console { level, message });
+    }
+}
+
+#[tauri::command]
+fn page_error(
+    events: State<'_, EventSink>,
+    state: State<'_, Arc<ViewerRequest>>,
+    exit_reason: State<'_, Arc<Mutex<ViewerExitReason>>>,
+    error: PageError,
+) {
+    // Only the first error becomes the exit reason
+    if state.behaviour.fail_on_page_error
+        && let Ok(mut reason) = exit_reason.lock()
+        && !matches!(*reason, ViewerExitReason::Error { .. })
+    {
+        *reason = ViewerExitReason::Error {
+            message: format!(
+                "Uncaught page error: {} ({}:{})",
+                error.message,
+                error.source.as_deref().unwrap_or("<unknown>"),
+                error.line.unwrap_or(0)
+            ),
+        };
+    }
+
+    events.emit(&error.into_event());
+}
+
 /// Configure the window based on WindowOptions.
 fn configure_window(window: &WebviewWindow, options: &WindowOptions) -> Result<()> {
+    options.check().map_err(|e| anyhow::anyhow!(e))?;
+
     // Set title
     if let Some(ref title) = options.title {
         window.set_title(title)?;
     }
 
-    // Set size
-    if let (Some(width), Some(height)) = (options.width, options.height) {
-        use tauri::Size;
-        window.set_size(Size::Logical(LogicalSize {
-            width: width as f64,
-            height: height as f64,
-        }))?;
+    // Resolve the target monitor
+    let monitor = match &options.monitor {
+        Some(selector) => select_monitor(window, selector)?,
+        None => window.current_monitor()?,
+    };
+    let work_area = monitor.as_ref().map(|monitor| {
+        monitor
+            .work_area()
+            .size
+            .to_logical::<f64>(monitor.scale_factor())
+    });
+
+    // Set size: an explicit or restored size wins, then a percentage of the
+    // work area, then the default
+    let (default_width, default_height) = DEFAULT_WINDOW_SIZE;
+    let width = dimension(
+        options.width,
+        options.width_percent,
+        work_area.map(|area| area.width),
+        default_width,
+    );
+    let height = dimension(
+        options.height,
+        options.height_percent,
+        work_area.map(|area| area.height),
+        default_height,
+    );
+    window.set_size(Size::Logical(LogicalSize {
+        width: clamp_dimension(width, options.min_width, options.max_width),
+        height: clamp_dimension(height, options.min_height, options.max_height),
+    }))?;
+
+    // Set size constraints
+    if options.min_width.is_some() || options.min_height.is_some() {
+        window.set_min_size(Some(Size::Logical(LogicalSize {
+            width: options.min_width.unwrap_or(0) as f64,
+            height: options.min_height.unwrap_or(0) as f64,
+        })))?;
+    }
+    if options.max_width.is_some() || options.max_height.is_some() {
+        // An unset maximum is bounded by the monitor instead
+        let (area_width, area_height) = work_area
+            .map(|area| (area.width, area.height))
+            .unwrap_or((f64::from(u16::MAX), f64::from(u16::MAX)));
+        window.set_max_size(Some(Size::Logical(LogicalSize {
+            width: options.max_width.map_or(area_width, f64::from),
+            height: options.max_height.map_or(area_height, f64::from),
+        })))?;
     }
 
-    // Set position
+    // Set position, or centre the window if requested
     if let (Some(x), Some(y)) = (options.x, options.y) {
         window.set_position(Position::Logical(LogicalPosition {
             x: x as f64,
             y: y as f64,
         }))?;
+    } else if options.center.is_some() || options.monitor.is_some() {
+        let center = options.center.clone().unwrap_or(WindowCenter::Monitor);
+        center_window(window, &center, monitor.as_ref())?;
     }
 
     // Set resizable
@@ -198,6 +605,76 @@ fn configure_window(window: &WebviewWindow, options: &WindowOptions) -> Result<(
     Ok(())
 }
 
+/// Find the monitor matching `selector`, falling back to the current monitor.
+fn select_monitor(window: &WebviewWindow, selector: &MonitorSelector) -> Result<Option<Monitor>> {
+    let mut monitors = window.available_monitors()?;
+    let index = match selector {
+        MonitorSelector::Index(index) => Some(*index).filter(|index| *index < monitors.len()),
+        MonitorSelector::Name(name) => monitors
+            .iter()
+            .position(|monitor| monitor.name() == Some(name)),
+    };
+
+    match index {
+        Some(index) => Ok(Some(monitors.swap_remove(index))),
+        None => {
+            eprintln!("Monitor {:?} not found, using current monitor", selector);
+            Ok(window.current_monitor()?)
+        }
+    }
+}
+
+/// Centre the window on a monitor's work area or over a parent rectangle.
+fn center_window(
+    window: &WebviewWindow,
+    center: &WindowCenter,
+    monitor: Option<&Monitor>,
+) -> Result<()> {
+    let outer_size = window.outer_size()?;
+
+    match center {
+        WindowCenter::Monitor => {
+            let Some(monitor) = monitor else {
+                window.center()?;
+                return Ok(());
+            };
+            let area = monitor.work_area();
+            window.set_position(Position::Physical(PhysicalPosition {
+                x: area.position.x + (area.size.width as i32 - outer_size.width as i32) / 2,
+                y: area.position.y + (area.size.height as i32 - outer_size.height as i32) / 2,
+            }))?;
+        }
+        WindowCenter::Parent {
+            x,
+            y,
+            width,
+            height,
+        } => {
+            let outer_size = outer_size.to_logical::<f64>(window.scale_factor()?);
+            window.set_position(Position::Logical(LogicalPosition {
+                x: *x as f64 + (*width as f64 - outer_size.width) / 2.0,
+                y: *y as f64 + (*height as f64 - outer_size.height) / 2.0,
+            }))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A window dimension in logical pixels: `size` if set, else `percent` of the
+/// work area dimension `area`, else `default`.
+fn dimension(size: Option<u32>, percent: Option<f64>, area: Option<f64>, default: u32) -> f64 {
+    size.map(f64::from)
+        .or_else(|| Some(area? * percent? / 100.0))
+        .unwrap_or(f64::from(default))
+}
+
+/// Clamp a logical dimension to optional minimum and maximum bounds.
+fn clamp_dimension(value: f64, min: Option<u32>, max: Option<u32>) -> f64 {
+    let value = min.map_or(value, |min| value.max(min as f64));
+    max.map_or(value, |max| value.min(max as f64))
+}
+
 /// Watch the command file for changes and process commands.
 fn watch_commands(
     command_path: PathBuf,
@@ -270,21 +747,75 @@ fn process_command_file(
     let command: ViewerCommand = serde_json::from_str(&data).context("Failed to parse command")?;
 
     // Execute command
-    let response = match &command {
-        ViewerCommand::Refresh { seq, content } => {
-            match execute_refresh(window, content, request) {
-                Ok(()) => ViewerCommandResponse {
-                    seq: *seq,
-                    success: true,
-                    error: None,
-                },
-                Err(e) => ViewerCommandResponse {
-                    seq: *seq,
-                    success: false,
-                    error: Some(e.to_string()),
-                },
-            }
+    let result = match &command {
+        ViewerCommand::Refresh { content, .. } => {
+            execute_refresh(window, content, request).map(|()| None)
         }
+        ViewerCommand::Navigate { url, .. } => {
+            execute_navigate(window, url, request).map(|()| None)
+        }
+        ViewerCommand::Back { .. } => window
+            .eval("history.back();")
+            .context("Failed to go back")
+            .map(|()| None),
+        ViewerCommand::Forward { .. } => window
+            .eval("history.forward();")
+            .context("Failed to go forward")
+            .map(|()| None),
+        ViewerCommand::Reload { ignore_cache, .. } => {
+            execute_reload(window, *ignore_cache).map(|()| None)
+        }
+        ViewerCommand::CurrentUrl { .. } => window
+            .url()
+            .context("Failed to get current URL")
+            .map(|url| Some(ViewerCommandData::CurrentUrl { url })),
+        ViewerCommand::QueryState { .. } => {
+            viewer_state(window).map(|state| Some(ViewerCommandData::State { state }))
+        }
+        ViewerCommand::UpdateData { data, .. } => {
+            execute_update_data(window, data, request).map(|()| None)
+        }
+        ViewerCommand::AppendLog { entries, .. } => {
+            execute_append_log(window, entries).map(|()| None)
+        }
+        ViewerCommand::AddTab { id, content, .. } => {
+            crate::tabs::add_tab(window, request, id, content).map(|()| None)
+        }
+        ViewerCommand::CloseTab { id, .. } => crate::tabs::close_tab(window, id).map(|()| None),
+        ViewerCommand::SelectTab { id, .. } => crate::tabs::select_tab(window, id).map(|()| None),
+        ViewerCommand::RefreshTab { id, content, .. } => {
+            crate::tabs::refresh_tab(window, request, id, content).map(|()| None)
+        }
+        ViewerCommand::RefreshCell {
+            row,
+            column,
+            content,
+            ..
+        } => crate::layout::refresh_cell(window, request, *row, *column, content).map(|()| None),
+        ViewerCommand::SendBinary {
+            channel,
+            path: data_path,
+            ..
+        } => path
+            .parent()
+            .context("Failed to get the command file's directory")
+            .and_then(|dir| crate::binary::send_binary(window, channel, data_path, dir))
+            .map(|()| None),
+    };
+
+    let response = match result {
+        Ok(data) => ViewerCommandResponse {
+            seq: command.seq(),
+            success: true,
+            error: None,
+            data,
+        },
+        Err(e) => ViewerCommandResponse {
+            seq: command.seq(),
+            success: false,
+            error: Some(e.to_string()),
+            data: None,
+        },
     };
 
     // Write response
@@ -308,15 +839,157 @@ fn execute_refresh(
 ) -> Result<()> {
     // Create temporary request with new content
     let refresh_request = ViewerRequest {
-        id: request.id,
         content: content.clone(),
-        window: request.window.clone(),
-        behaviour: request.behaviour.clone(),
-        environment: request.environment.clone(),
-        dialog: request.dialog.clone(),
-        command_path: request.command_path.clone(),
+        ..request.clone()
     };
 
     // Use existing content loader
-    crate::content_loader::load_content(window, &refresh_request)
+    crate::content_loader::load_content(window, &refresh_request)?;
+
+    if let Ok(mut current) = window.state::<PageState>().content.lock() {
+        *current = content.clone();
+    }
+    Ok(())
+}
+
+/// Re-render the current template content with new data.
+fn execute_update_data(
+    window: &WebviewWindow,
+    data: &serde_json::Value,
+    request: &ViewerRequest,
+) -> Result<()> {
+    let content = window
+        .state::<PageState>()
+        .content
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Content state is poisoned"))?
+        .clone();
+
+    match content {
+        ViewerContent::Template {
+            template, engine, ..
+        } => execute_refresh(
+            window,
+            &ViewerContent::Template {
+                template,
+                data: data.clone(),
+                engine,
+            },
+            request,
+        ),
+        _ => anyhow::bail!("The displayed content is not a template"),
+    }
+}
+
+/// Append entries to the current log content.
+fn execute_append_log(window: &WebviewWindow, entries: &[LogEntry]) -> Result<()> {
+    let is_log = matches!(
+        window.state::<PageState>().content.lock().as_deref(),
+        Ok(ViewerContent::Log { .. })
+    );
+    if !is_log {
+        anyhow::bail!("The displayed content is not a log");
+    }
+
+    let first = window.state::<LogBuffer>().push(entries);
+    // If the page is not ready yet it fetches the entries once it is
+    let js = format!(
+        "window.__htmlViewLog && window.__htmlViewLog.push({}, {});",
+        first,
+        script_json(entries)
+    );
+    window.eval(&js).context("Failed to append to the log")
+}
+
+/// Execute a navigate command.
+fn execute_navigate(window: &WebviewWindow, url: &Url, request: &ViewerRequest) -> Result<()> {
+    let home = window
+        .state::<PageState>()
+        .content
+        .lock()
+        .ok()
+        .and_then(|content| home_url(&content));
+    request
+        .behaviour
+        .check_navigation(url, home.as_ref())
+        .map_err(anyhow::Error::msg)?;
+
+    window.navigate(url.clone()).context("Failed to navigate")
+}
+
+/// The URL `content` is opened at, if it is a file, directory or remote
+/// page; other contents are shown from the bundle or an inline document.
+fn home_url(content: &ViewerContent) -> Option<Url> {
+    match content {
+        ViewerContent::LocalFile { path } => {
+            Url::from_file_path(std::fs::canonicalize(path).ok()?).ok()
+        }
+        ViewerContent::AppDir { root, .. } => {
+            Url::from_directory_path(std::fs::canonicalize(root).ok()?).ok()
+        }
+        ViewerContent::RemoteUrl { url } => Some(url.clone()),
+        _ => None,
+    }
+}
+
+/// Execute a reload command.
+fn execute_reload(window: &WebviewWindow, ignore_cache: bool) -> Result<()> {
+    if ignore_cache {
+        window
+            .clear_all_browsing_data()
+            .context("Failed to clear browsing data")?;
+    }
+
+    window.reload().context("Failed to reload")
+}
+
+/// Capture the current geometry of the window.
+fn window_geometry(window: &WebviewWindow) -> Result<WindowGeometry> {
+    let scale_factor = window.scale_factor()?;
+    let position = window.outer_position()?;
+    let size = window.inner_size()?;
+    let logical_position = position.to_logical::<i32>(scale_factor);
+    let logical_size = size.to_logical::<u32>(scale_factor);
+    let monitor = window
+        .current_monitor()?
+        .and_then(|monitor| monitor.name().cloned());
+
+    Ok(WindowGeometry {
+        x: logical_position.x,
+        y: logical_position.y,
+        width: logical_size.width,
+        height: logical_size.height,
+        physical_x: position.x,
+        physical_y: position.y,
+        physical_width: size.width,
+        physical_height: size.height,
+        scale_factor,
+        monitor,
+        maximised: window.is_maximized()?,
+        fullscreen: window.is_fullscreen()?,
+    })
+}
+
+/// Capture the current window and page state.
+fn viewer_state(window: &WebviewWindow) -> Result<ViewerState> {
+    let page = window.state::<PageState>();
+    let load_state = page
+        .load_state
+        .lock()
+        .map(|state| *state)
+        .unwrap_or_default();
+    let title = page
+        .title
+        .lock()
+        .map(|title| title.clone())
+        .unwrap_or_default();
+
+    Ok(ViewerState {
+        geometry: window_geometry(window)?,
+        focused: window.is_focused()?,
+        visible: window.is_visible()?,
+        title,
+        url: window.url()?,
+        load_state,
+    })
 }
@@ -0,0 +1,120 @@
+//! Bytes sent with `ViewerCommand::SendBinary`.
+//!
+//! The bytes are read from the file named in the command and kept in the
+//! managed [`BinaryStore`] under a key. The page is told the channel and key
+//! through `window.__htmlViewBinary`, and takes the bytes with
+//! [`binary_take`], whose raw IPC response reaches the page as an
+//! `ArrayBuffer` without being encoded as text. Bytes the page does not
+//! take within [`TAKE_TIMEOUT`], for example because it has no IPC access,
+//! are dropped.
+
+use anyhow::{Context, Result, bail};
+use html_view_shared::render::script_json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::ipc::Response;
+use tauri::{Manager, State, WebviewWindow};
+
+/// Initialization script that defines `htmlView.onBinary` and hands the
+/// bytes of each channel to its callbacks, in the order they were sent.
+pub const BINARY_SCRIPT: &str = r#"
+(function () {
+    var api = window.htmlView = window.htmlView || {};
+    var listeners = {};
+    var delivered = Promise.resolve();
+
+    window.__htmlViewBinary = function (channel, key) {
+        var internals = window.__TAURI_INTERNALS__;
+        if (!internals) return;
+        var taken = internals.invoke('binary_take', { key: key });
+        delivered = delivered
+            .then(function () { return taken; })
+            .then(function (buffer) {
+                (listeners[channel] || []).forEach(function (callback) {
+                    try {
+                        callback(buffer);
+                    } catch (e) {
+                        // Rethrow outside the loop so it is reported as a page error
+                        setTimeout(function () { throw e; });
+                    }
+                });
+            })
+            .catch(function () {});
+    };
+
+    api.onBinary = function (channel, callback) {
+        (listeners[channel] = listeners[channel] || []).push(callback);
+    };
+})();
+"#;
+
+/// How long bytes are kept for the page to take them.
+const TAKE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bytes waiting for the page to take them, with when they were stored.
+#[derive(Default)]
+pub struct BinaryStore {
+    buffers: Mutex<HashMap<u64, (Instant, Vec<u8>)>>,
+    next_key: AtomicU64,
+}
+
+impl BinaryStore {
+    /// Drop the bytes the page did not take, for example because it was
+    /// navigated away before it could.
+    pub fn clear(&self) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            buffers.clear();
+        }
+    }
+}
+
+/// Read the bytes in `path` and tell the page they are ready on `channel`.
+///
+/// `path` must name a file directly in `dir`, the request's temporary
+/// directory, as the command could otherwise read and remove any file.
+pub fn send_binary(window: &WebviewWindow, channel: &str, path: &Path, dir: &Path) -> Result<()> {
+    let canonical = path.canonicalize().context("Failed to read binary data")?;
+    let dir = dir
+        .canonicalize()
+        .context("Failed to resolve the temporary directory")?;
+    if canonical.parent() != Some(dir.as_path()) {
+        bail!(
+            "Binary data must be in the viewer's temporary directory: {}",
+            path.display()
+        );
+    }
+
+    let bytes = std::fs::read(&canonical).context("Failed to read binary data")?;
+    let _ = std::fs::remove_file(&canonical);
+
+    let store = window.state::<BinaryStore>();
+    let key = store.next_key.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut buffers) = store.buffers.lock() {
+        // The page could not take these, so they would never be removed
+        let now = Instant::now();
+        buffers.retain(|_, (stored, _)| now.duration_since(*stored) < TAKE_TIMEOUT);
+        buffers.insert(key, (now, bytes));
+    }
+
+    let js = format!(
+        "window.__htmlViewBinary && window.__htmlViewBinary({}, {});",
+        script_json(channel),
+        key
+    );
+    window.eval(&js).context("Failed to send binary data")
+}
+
+/// Take the bytes stored under `key`, as an `ArrayBuffer` on the page.
+#[tauri::command]
+pub fn binary_take(store: State<'_, BinaryStore>, key: u64) -> Result<Response, String> {
+    store
+        .buffers
+        .lock()
+        .ok()
+        .and_then(|mut buffers| buffers.remove(&key))
+        .map(|(_, bytes)| Response::new(bytes))
+        .ok_or_else(|| format!("Binary data {} is no longer available", key))
+}
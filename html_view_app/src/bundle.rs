@@ -0,0 +1,178 @@
+//! Serving of in-memory [`ViewerContent::Bundle`] and
+//! [`ViewerContent::Archive`] files.
+//!
+//! Files live in the managed [`BundleStore`] and are served to the webview
+//! through the `bundle` URI scheme, so pages can reference their assets by
+//! relative path. Frames of a [`ViewerContent::Tabs`] or
+//! [`ViewerContent::Layout`] page are served from the store too, each under
+//! its own path prefix, which may also map to a directory on disk.
+//!
+//! [`ViewerContent::Bundle`]: html_view_shared::ViewerContent::Bundle
+//! [`ViewerContent::Archive`]: html_view_shared::ViewerContent::Archive
+//! [`ViewerContent::Tabs`]: html_view_shared::ViewerContent::Tabs
+//! [`ViewerContent::Layout`]: html_view_shared::ViewerContent::Layout
+
+use html_view_shared::render::{bundle_path, mime_for_path};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::http::{Request, Response, StatusCode, header};
+use tauri::{Manager, Runtime, UriSchemeContext};
+use url::Url;
+
+/// URI scheme the bundle files are served from.
+pub const SCHEME: &str = "bundle";
+
+/// Files of the bundle currently being displayed.
+#[derive(Default)]
+pub struct BundleStore {
+    files: Mutex<HashMap<String, Vec<u8>>>,
+
+    /// Directories served under a path prefix, read when requested.
+    mounts: Mutex<HashMap<String, PathBuf>>,
+}
+
+impl BundleStore {
+    /// Replace the served files, normalising their paths. Files whose path
+    /// would escape the bundle root are dropped.
+    pub fn replace(&self, files: HashMap<String, Vec<u8>>) {
+        let files = files
+            .into_iter()
+            .filter_map(|(path, bytes)| Some((bundle_path(&path)?, bytes)))
+            .collect();
+        if let Ok(mut current) = self.files.lock() {
+            *current = files;
+        }
+        if let Ok(mut mounts) = self.mounts.lock() {
+            mounts.clear();
+        }
+    }
+
+    /// Serve `bytes` at `path`, replacing any file already there.
+    pub fn insert(&self, path: &str, bytes: Vec<u8>) {
+        if let Some(path) = bundle_path(path)
+            && let Ok(mut current) = self.files.lock()
+        {
+            current.insert(path, bytes);
+        }
+    }
+
+    /// Replace the files under `prefix` with `files`, given relative to it,
+    /// and serve other paths under it from the directory `mount`, if any.
+    pub fn replace_prefix(
+        &self,
+        prefix: &str,
+        files: HashMap<String, Vec<u8>>,
+        mount: Option<PathBuf>,
+    ) {
+        self.remove_prefix(prefix);
+        if let Ok(mut current) = self.files.lock() {
+            current.extend(files.into_iter().filter_map(|(path, bytes)| {
+                Some((bundle_path(&format!("{prefix}/{path}"))?, bytes))
+            }));
+        }
+        if let Some(mount) = mount
+            && let Ok(mut mounts) = self.mounts.lock()
+        {
+            mounts.insert(prefix.to_string(), mount);
+        }
+    }
+
+    /// Stop serving anything under `prefix`.
+    pub fn remove_prefix(&self, prefix: &str) {
+        let dir = format!("{prefix}/");
+        if let Ok(mut current) = self.files.lock() {
+            current.retain(|path, _| !path.starts_with(&dir));
+        }
+        if let Ok(mut mounts) = self.mounts.lock() {
+            mounts.remove(prefix);
+        }
+    }
+
+    /// The file at a normalised `path`, from memory or a mounted directory.
+    fn get(&self, path: &str) -> Option<Vec<u8>> {
+        if let Some(body) = self
+            .files
+            .lock()
+            .ok()
+            .and_then(|files| files.get(path).cloned())
+        {
+            return Some(body);
+        }
+
+        let mounts = self.mounts.lock().ok()?;
+        mounts.iter().find_map(|(prefix, dir)| {
+            let relative = path.strip_prefix(prefix.as_str())?.strip_prefix('/')?;
+            std::fs::read(dir.join(relative)).ok()
+        })
+    }
+}
+
+/// URL of `path` within the bundle, in the form the platform's webview
+/// expects for custom protocols.
+pub fn bundle_url(path: &str) -> Url {
+    let path = bundle_path(path).unwrap_or_default();
+    let base = if cfg!(any(windows, target_os = "android")) {
+        format!("http://{SCHEME}.localhost/")
+    } else {
+        format!("{SCHEME}://localhost/")
+    };
+    let base = Url::parse(&base).expect("bundle base URL is valid");
+    base.join(&path).unwrap_or(base)
+}
+
+/// Protocol handler for the `bundle` scheme.
+pub fn handle<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+) -> Response<Vec<u8>> {
+    let request_path = percent_decode(request.uri().path());
+    let Some(mut path) = bundle_path(&request_path) else {
+        return Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Vec::new())
+            .unwrap_or_else(|_| Response::new(Vec::new()));
+    };
+    if path.is_empty() || request_path.ends_with('/') {
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str("index.html");
+    }
+
+    let body = ctx.app_handle().state::<BundleStore>().get(&path);
+
+    match body {
+        Some(body) => Response::builder()
+            .header(header::CONTENT_TYPE, mime_for_path(&path))
+            .body(body),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(format!("{} not found in bundle", path).into_bytes()),
+    }
+    .unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
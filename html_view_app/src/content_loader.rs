@@ -1,8 +1,52 @@
+use crate::bundle::{BundleStore, bundle_url};
+use crate::history::{HistoryList, push_version};
+use crate::layout::{CellList, Cells, cell_prefix};
+use crate::log_view::LogBuffer;
+use crate::tabs::{Tab, TabList};
 use anyhow::{Context, Result};
-use html_view_shared::{ToolbarOptions, ViewerContent, ViewerRequest};
-use tauri::WebviewWindow;
+use html_view_shared::render::{
+    DEFAULT_LOG_LINES, DecodedAudio, archive_entry, audio_files, decode_audio, image_mime,
+    read_archive, read_delimited, render_ansi, render_code, render_diagram, render_diff,
+    render_history, render_image, render_json, render_layout, render_log, render_markdown,
+    render_slides, render_table, render_tabs, render_template,
+};
+use html_view_shared::{
+    AudioSource, ImageSource, Layout, TableSource, ToolbarOptions, ViewerContent, ViewerRequest,
+    WindowTheme,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{Manager, WebviewWindow};
 use url::Url;
 
+/// Script giving pages in frames the viewer's IPC, which is only set up in
+/// the top document, so built-in views keep working inside tabs and layouts.
+const FRAME_SCRIPT: &str = r#"<script>
+if (window.parent !== window && !window.__TAURI_INTERNALS__) {
+    try { window.__TAURI_INTERNALS__ = window.parent.__TAURI_INTERNALS__; } catch (e) {}
+}
+</script>"#;
+
+/// Content rendered for display, before it is loaded into the window or a
+/// frame.
+enum Page {
+    /// An HTML document, with the directory relative URLs resolve against.
+    Html {
+        html: String,
+        base_dir: Option<PathBuf>,
+    },
+    /// Files served from the bundle protocol, opened at `entry`.
+    Bundle {
+        entry: String,
+        files: HashMap<String, Vec<u8>>,
+    },
+    /// A file on disk, opened in place so relative URLs resolve within `root`.
+    File { path: PathBuf, root: PathBuf },
+    /// A remote page.
+    Remote(Url),
+}
+
 /// Load content into the window based on ViewerContent type.
 pub fn load_content(window: &WebviewWindow, request: &ViewerRequest) -> Result<()> {
     let toolbar_html = if request.window.toolbar.show {
@@ -10,33 +54,62 @@ pub fn load_content(window: &WebviewWindow, request: &ViewerRequest) -> Result<(
     } else {
         None
     };
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+
+    if let Some(limit) = request.behaviour.refresh_history
+        && check_frame(&request.content).is_ok()
+    {
+        window.state::<TabList>().clear();
+        window.state::<CellList>().clear();
+        return load_history(window, request, limit);
+    }
+    window.state::<HistoryList>().clear();
 
     match &request.content {
-        ViewerContent::InlineHtml { html, base_dir: _ } => {
-            let mut final_html = html.clone();
-            if let Some(toolbar) = &toolbar_html {
-                final_html = inject_into_html(&final_html, toolbar, None);
-            }
-            load_inline_html(window, &final_html)?;
+        ViewerContent::Tabs { tabs } => {
+            window.state::<CellList>().clear();
+            return load_tabs(window, request, tabs, toolbar_html.as_deref());
+        }
+        ViewerContent::Layout { layout } => {
+            window.state::<TabList>().clear();
+            return load_layout(window, request, layout);
+        }
+        ViewerContent::Slides {
+            slides,
+            advance_seconds,
+        } => {
+            window.state::<TabList>().clear();
+            window.state::<CellList>().clear();
+            return load_slides(window, request, slides, *advance_seconds);
+        }
+        _ => {
+            window.state::<TabList>().clear();
+            window.state::<CellList>().clear();
+        }
+    }
+
+    match render_page(window, &request.content, request, &theme)? {
+        Page::Html { html, base_dir } => {
+            load_html(window, &html, base_dir.as_deref(), toolbar_html.as_deref())?;
         }
-        ViewerContent::LocalFile { path } => {
+        Page::Bundle { entry, files } => {
+            load_bundle(window, &entry, files, toolbar_html.as_deref())?;
+        }
+        Page::File { path, root } => {
             if let Some(toolbar) = &toolbar_html {
                 // Read file, inject base and toolbar
-                let content = std::fs::read_to_string(path).context("Failed to read HTML file")?;
-                let base_path = path
-                    .parent()
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or_else(|| std::path::PathBuf::from("."));
-                let abs_path = std::fs::canonicalize(&base_path)
-                    .context("Failed to canonicalize base path")?;
-                let base_url = Url::from_file_path(&abs_path)
-                    .map_err(|_| anyhow::anyhow!("Invalid file path {:?}", abs_path))?;
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {:?}", path))?;
+                let root =
+                    std::fs::canonicalize(&root).context("Failed to canonicalize base path")?;
+                let base_url = Url::from_file_path(&root)
+                    .map_err(|_| anyhow::anyhow!("Invalid file path {:?}", root))?;
                 let final_html = inject_into_html(&content, toolbar, Some(base_url.as_str()));
                 load_inline_html(window, &final_html)?;
             } else {
                 // Use file URL to ensure relative paths (images, css) work correctly
                 let abs_path =
-                    std::fs::canonicalize(path).context("Failed to canonicalize file path")?;
+                    std::fs::canonicalize(&path).context("Failed to canonicalize file path")?;
                 let url = Url::from_file_path(&abs_path)
                     .map_err(|_| anyhow::anyhow!("Invalid file path {:?}", abs_path))?;
                 window
@@ -44,34 +117,7 @@ pub fn load_content(window: &WebviewWindow, request: &ViewerRequest) -> Result<(
                     .context("Failed to navigate to local file")?;
             }
         }
-        ViewerContent::AppDir { root, entry } => {
-            let entry_file = entry.as_deref().unwrap_or("index.html");
-            let full_path = root.join(entry_file);
-
-            if let Some(toolbar) = &toolbar_html {
-                let content =
-                    std::fs::read_to_string(&full_path).context("Failed to read app entry file")?;
-                let root =
-                    std::fs::canonicalize(root).context("Failed to canonicalize app root")?;
-                let base_url =
-                    Url::from_file_path(root).map_err(|_| anyhow::anyhow!("Invalid file path"))?;
-                let final_html = inject_into_html(&content, toolbar, Some(base_url.as_str()));
-                load_inline_html(window, &final_html)?;
-            } else {
-                let abs_path = std::fs::canonicalize(&full_path)
-                    .context("Failed to canonicalize app entry file path")?;
-                let url = Url::from_file_path(&abs_path)
-                    .map_err(|_| anyhow::anyhow!("Invalid file path {:?}", abs_path))?;
-                window
-                    .navigate(url)
-                    .context("Failed to navigate to app entry file")?;
-            }
-        }
-        ViewerContent::RemoteUrl { url } => {
-            if !request.behaviour.allow_remote_content {
-                anyhow::bail!("Remote content is not allowed");
-            }
-
+        Page::Remote(url) => {
             if let Some(toolbar) = &toolbar_html {
                 // For remote URLs with toolbar, use iframe wrapper
                 let wrapper = format!(
@@ -107,6 +153,434 @@ pub fn load_content(window: &WebviewWindow, request: &ViewerRequest) -> Result<(
     Ok(())
 }
 
+/// Render `content` into a page.
+fn render_page(
+    window: &WebviewWindow,
+    content: &ViewerContent,
+    request: &ViewerRequest,
+    theme: &WindowTheme,
+) -> Result<Page> {
+    let page = match content {
+        ViewerContent::InlineHtml { html, base_dir } => Page::Html {
+            html: html.clone(),
+            base_dir: base_dir.clone(),
+        },
+        ViewerContent::LocalFile { path } => Page::File {
+            path: path.clone(),
+            root: path
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| PathBuf::from(".")),
+        },
+        ViewerContent::AppDir { root, entry } => Page::File {
+            path: root.join(entry.as_deref().unwrap_or("index.html")),
+            root: root.clone(),
+        },
+        ViewerContent::RemoteUrl { url } => {
+            if !request.behaviour.allow_remote_content {
+                anyhow::bail!("Remote content is not allowed");
+            }
+            Page::Remote(url.clone())
+        }
+        ViewerContent::Markdown { source, base_dir } => Page::Html {
+            html: render_markdown(source, theme),
+            base_dir: base_dir.clone(),
+        },
+        ViewerContent::Image { source, mime } => {
+            let (data, path) = match source {
+                ImageSource::Path(path) => (
+                    Cow::Owned(std::fs::read(path).context("Failed to read image file")?),
+                    Some(path.as_path()),
+                ),
+                ImageSource::Bytes(bytes) => (Cow::Borrowed(bytes.as_slice()), None),
+            };
+            let mime = mime.as_deref().unwrap_or_else(|| image_mime(&data, path));
+            html_page(render_image(&data, mime, theme))
+        }
+        ViewerContent::Bundle { entry, files } => Page::Bundle {
+            entry: entry.clone(),
+            files: files.clone(),
+        },
+        ViewerContent::Template {
+            template,
+            data,
+            engine,
+        } => html_page(
+            render_template(template, data, *engine)
+                .map_err(|e| anyhow::anyhow!("Failed to render template: {:#}", e))?,
+        ),
+        ViewerContent::Json { value } => html_page(render_json(value, theme)),
+        ViewerContent::Archive { path, entry } => {
+            let files =
+                read_archive(path).with_context(|| format!("Failed to read archive {:?}", path))?;
+            let entry = archive_entry(&files, entry.as_deref()).with_context(|| {
+                format!(
+                    "Entry file {} not found in archive",
+                    entry.as_deref().unwrap_or("index.html")
+                )
+            })?;
+            Page::Bundle { entry, files }
+        }
+        ViewerContent::Table { source } => html_page(match source {
+            TableSource::Rows { columns, rows } => render_table(columns, rows, theme),
+            TableSource::File { path, delimiter } => {
+                let (columns, rows) = read_delimited(path, *delimiter)
+                    .with_context(|| format!("Failed to read table {:?}", path))?;
+                render_table(&columns, &rows, theme)
+            }
+        }),
+        ViewerContent::Code {
+            source,
+            language,
+            line_numbers,
+            highlight_lines,
+        } => html_page(render_code(
+            source,
+            language.as_deref(),
+            *line_numbers,
+            highlight_lines,
+            theme,
+        )),
+        ViewerContent::Diff {
+            old,
+            new,
+            mode,
+            language,
+        } => html_page(render_diff(old, new, language.as_deref(), *mode, theme)),
+        ViewerContent::AnsiText { text } => html_page(render_ansi(text, theme)),
+        ViewerContent::Log { max_lines } => {
+            let max_lines = max_lines.unwrap_or(DEFAULT_LOG_LINES);
+            window.state::<LogBuffer>().reset(max_lines);
+            html_page(render_log(max_lines, theme))
+        }
+        ViewerContent::Audio {
+            source,
+            spectrogram,
+        } => {
+            let audio = match source {
+                AudioSource::Path(path) => decode_audio(path)
+                    .with_context(|| format!("Failed to decode audio {:?}", path))?,
+                AudioSource::Samples {
+                    samples,
+                    sample_rate,
+                    channels,
+                } => DecodedAudio {
+                    samples: samples.clone(),
+                    sample_rate: *sample_rate,
+                    channels: *channels,
+                },
+            };
+            Page::Bundle {
+                entry: "index.html".to_string(),
+                files: audio_files(&audio, *spectrogram, theme)
+                    .context("Failed to encode audio")?,
+            }
+        }
+        ViewerContent::Diagram { source, kind } => html_page(
+            render_diagram(source, *kind, theme)
+                .map_err(|e| anyhow::anyhow!("Failed to render diagram: {}", e))?,
+        ),
+        ViewerContent::Tabs { .. }
+        | ViewerContent::Layout { .. }
+        | ViewerContent::Slides { .. } => {
+            anyhow::bail!("Tabs, layouts and slides cannot be nested")
+        }
+    };
+
+    Ok(page)
+}
+
+/// A rendered HTML page with nothing to resolve relative URLs against.
+fn html_page(html: String) -> Page {
+    Page::Html {
+        html,
+        base_dir: None,
+    }
+}
+
+/// Load the tab page and a frame for each of `tabs`.
+fn load_tabs(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    tabs: &[(String, ViewerContent)],
+    toolbar: Option<&str>,
+) -> Result<()> {
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+    let mut page = render_tabs(&theme);
+    if let Some(toolbar) = toolbar {
+        page = inject_into_html(&page, toolbar, None);
+    }
+
+    // Frames are added to the store after the page replaces its contents
+    window.state::<BundleStore>().replace(HashMap::from([(
+        "index.html".to_string(),
+        page.into_bytes(),
+    )]));
+    let mut list = Vec::with_capacity(tabs.len());
+    for (id, content) in tabs {
+        check_frame(content)?;
+        if list.iter().any(|tab: &Tab| tab.id == *id) {
+            anyhow::bail!("Tab id {:?} is used more than once", id);
+        }
+        let prefix = window.state::<TabList>().next_prefix();
+        let src = load_frame(window, request, content, &prefix)
+            .map_err(|e| anyhow::anyhow!("Failed to load tab {:?}: {:#}", id, e))?;
+        list.push(Tab::new(id.clone(), content.clone(), prefix, src));
+    }
+    window.state::<TabList>().reset(list);
+
+    window
+        .navigate(bundle_url("index.html"))
+        .context("Failed to navigate to the tab page")
+}
+
+/// Check that `content` can be shown in a frame of a tab, layout or slide
+/// page.
+pub fn check_frame(content: &ViewerContent) -> Result<()> {
+    match content {
+        ViewerContent::Tabs { .. }
+        | ViewerContent::Layout { .. }
+        | ViewerContent::Slides { .. } => {
+            anyhow::bail!("Tabs, layouts and slides cannot be nested")
+        }
+        ViewerContent::Log { .. } => {
+            anyhow::bail!("A log cannot be shown in a tab, layout or slide")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Load the grid page of `layout` and a frame for each of its cells.
+fn load_layout(window: &WebviewWindow, request: &ViewerRequest, layout: &Layout) -> Result<()> {
+    layout.check().map_err(|e| anyhow::anyhow!(e))?;
+    let store = window.state::<BundleStore>();
+    store.replace(HashMap::new());
+
+    let mut srcs = Vec::with_capacity(layout.cells.len());
+    for (index, cell) in layout.cells.iter().enumerate() {
+        check_frame(&cell.content)?;
+        let src = load_frame(window, request, &cell.content, &cell_prefix(index)).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to load the cell at row {}, column {}: {:#}",
+                cell.row,
+                cell.column,
+                e
+            )
+        })?;
+        srcs.push(src);
+    }
+    store.insert(
+        "index.html",
+        layout_page(request, layout, &srcs).into_bytes(),
+    );
+    window.state::<CellList>().reset(Cells {
+        layout: layout.clone(),
+        srcs,
+    });
+
+    window
+        .navigate(bundle_url("index.html"))
+        .context("Failed to navigate to the layout page")
+}
+
+/// Show `request.content` as the newest version of the history, loading the
+/// history page first if it is not being shown.
+fn load_history(window: &WebviewWindow, request: &ViewerRequest, limit: usize) -> Result<()> {
+    let history = window.state::<HistoryList>();
+    if history.is_active() {
+        return push_version(window, request, &request.content, limit);
+    }
+
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+    let mut page = render_history(&theme);
+    if request.window.toolbar.show {
+        page = inject_into_html(&page, &generate_toolbar_html(&request.window.toolbar), None);
+    }
+    window.state::<BundleStore>().replace(HashMap::from([(
+        "index.html".to_string(),
+        page.into_bytes(),
+    )]));
+    history.start();
+    if let Err(e) = push_version(window, request, &request.content, limit) {
+        history.clear();
+        return Err(e);
+    }
+
+    window
+        .navigate(bundle_url("index.html"))
+        .context("Failed to navigate to the history page")
+}
+
+/// Load the slide page and a frame for each of `slides`.
+fn load_slides(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    slides: &[ViewerContent],
+    advance_seconds: Option<f64>,
+) -> Result<()> {
+    if slides.is_empty() {
+        anyhow::bail!("There are no slides to show");
+    }
+    if let Some(seconds) = advance_seconds
+        && !(seconds.is_finite() && seconds > 0.0)
+    {
+        anyhow::bail!("Slides must advance after a positive number of seconds");
+    }
+    let store = window.state::<BundleStore>();
+    store.replace(HashMap::new());
+
+    let mut srcs = Vec::with_capacity(slides.len());
+    for (index, content) in slides.iter().enumerate() {
+        check_frame(content)?;
+        let src = load_frame(window, request, content, &format!("slide/{index}")).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to load slide {} of {}: {:#}",
+                index + 1,
+                slides.len(),
+                e
+            )
+        })?;
+        srcs.push(src);
+    }
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+    let mut page = render_slides(&srcs, advance_seconds, &theme);
+    if request.window.toolbar.show {
+        page = inject_into_html(&page, &generate_toolbar_html(&request.window.toolbar), None);
+    }
+    store.insert("index.html", page.into_bytes());
+
+    window
+        .navigate(bundle_url("index.html"))
+        .context("Failed to navigate to the slide page")
+}
+
+/// Render the grid page of `layout`, whose cells' frames open `srcs`.
+pub fn layout_page(request: &ViewerRequest, layout: &Layout, srcs: &[String]) -> String {
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+    let page = render_layout(layout, srcs, &theme);
+    if request.window.toolbar.show {
+        inject_into_html(&page, &generate_toolbar_html(&request.window.toolbar), None)
+    } else {
+        page
+    }
+}
+
+/// Render `content` into the bundle under `prefix`, to be shown in a frame,
+/// and return the URL the frame opens.
+pub fn load_frame(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    content: &ViewerContent,
+    prefix: &str,
+) -> Result<String> {
+    let theme = request.window.theme_enum.clone().unwrap_or_default();
+    let store = window.state::<BundleStore>();
+    let src = match render_page(window, content, request, &theme)? {
+        Page::Html { html, base_dir } => {
+            // The base directory is served alongside, so relative URLs
+            // resolve within the bundle
+            let mount = base_dir
+                .map(|dir| {
+                    std::fs::canonicalize(&dir).context("Failed to canonicalize base directory")
+                })
+                .transpose()?;
+            let html = inject_frame_script(&html);
+            store.replace_prefix(
+                prefix,
+                HashMap::from([("index.html".to_string(), html.into_bytes())]),
+                mount,
+            );
+            bundle_url(&format!("{prefix}/index.html"))
+        }
+        Page::Bundle { entry, mut files } => {
+            if let Some(html) = files.get_mut(&entry) {
+                *html = inject_frame_script(&String::from_utf8_lossy(html)).into_bytes();
+            }
+            store.replace_prefix(prefix, files, None);
+            bundle_url(&format!("{prefix}/{entry}"))
+        }
+        Page::File { path, root } => {
+            let root = std::fs::canonicalize(&root).context("Failed to canonicalize base path")?;
+            let path = std::fs::canonicalize(&path)
+                .with_context(|| format!("Failed to canonicalize {:?}", path))?;
+            let entry = path
+                .strip_prefix(&root)
+                .map_err(|_| anyhow::anyhow!("{:?} is outside {:?}", path, root))?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            store.replace_prefix(prefix, HashMap::new(), Some(root));
+            bundle_url(&format!("{prefix}/{entry}"))
+        }
+        Page::Remote(url) => {
+            store.remove_prefix(prefix);
+            url
+        }
+    };
+
+    Ok(src.to_string())
+}
+
+/// Load an HTML page, injecting the toolbar and a base URL for `base_dir` so
+/// relative links and images resolve.
+fn load_html(
+    window: &WebviewWindow,
+    html: &str,
+    base_dir: Option<&Path>,
+    toolbar: Option<&str>,
+) -> Result<()> {
+    let base_url = base_dir
+        .map(|dir| {
+            let dir =
+                std::fs::canonicalize(dir).context("Failed to canonicalize base directory")?;
+            Url::from_directory_path(&dir)
+                .map_err(|_| anyhow::anyhow!("Invalid directory path {:?}", dir))
+        })
+        .transpose()?;
+
+    let final_html = inject_into_html(
+        html,
+        toolbar.unwrap_or_default(),
+        base_url.as_ref().map(Url::as_str),
+    );
+    load_inline_html(window, &final_html)
+}
+
+/// Serve `files` from the bundle protocol and navigate to `entry`.
+fn load_bundle(
+    window: &WebviewWindow,
+    entry: &str,
+    mut files: HashMap<String, Vec<u8>>,
+    toolbar: Option<&str>,
+) -> Result<()> {
+    if let Some(toolbar) = toolbar
+        && let Some(html) = files.get_mut(entry)
+    {
+        let page = String::from_utf8_lossy(html).into_owned();
+        *html = inject_into_html(&page, toolbar, None).into_bytes();
+    }
+
+    window.state::<BundleStore>().replace(files);
+    window
+        .navigate(bundle_url(entry))
+        .context("Failed to navigate to bundle entry")
+}
+
+/// Insert [`FRAME_SCRIPT`] at the start of the document head, or as early
+/// as possible without leaving quirks mode.
+fn inject_frame_script(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let at = ["<head>", "<html", "<!doctype"]
+        .iter()
+        .find_map(|tag| {
+            let start = lower.find(tag)?;
+            Some(start + lower[start..].find('>')? + 1)
+        })
+        .unwrap_or(0);
+    format!("{}{}{}", &html[..at], FRAME_SCRIPT, &html[at..])
+}
+
 /// Load inline HTML into the window using a data URL.
 fn load_inline_html(window: &WebviewWindow, html: &str) -> Result<()> {
     use base64::{Engine as _, engine::general_purpose};
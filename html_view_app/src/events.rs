@@ -0,0 +1,157 @@
+//! Reporting of viewer events back to the library.
+//!
+//! Events are appended to the request's event file as JSON lines, which the
+//! library reads incrementally from its `ViewerHandle`.
+
+use html_view_shared::{PageErrorKind, ViewerEvent};
+use serde::Deserialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use url::Url;
+
+/// Initialization script that reports `DOMContentLoaded` for every page.
+pub const LIFECYCLE_SCRIPT: &str = r#"
+document.addEventListener('DOMContentLoaded', function () {
+    if (window.__TAURI_INTERNALS__) {
+        window.__TAURI_INTERNALS__.invoke('dom_content_loaded', { url: location.href });
+    }
+});
+"#;
+
+/// Initialization script that forwards `console` calls to the `page_console`
+/// command. Only injected when `BehaviourOptions::capture_console` is set.
+pub const CONSOLE_SCRIPT: &str = r#"
+(function () {
+    function format(value) {
+        if (typeof value === 'string') return value;
+        if (value instanceof Error) return value.stack || String(value);
+        try {
+            var json = JSON.stringify(value);
+            return json === undefined ? String(value) : json;
+        } catch (e) {
+            return String(value);
+        }
+    }
+    ['debug', 'log', 'info', 'warn', 'error'].forEach(function (level) {
+        var original = console[level];
+        console[level] = function () {
+            var internals = window.__TAURI_INTERNALS__;
+            if (internals) {
+                var message = Array.prototype.map.call(arguments, format).join(' ');
+                internals.invoke('page_console', { level: level, message: message })
+                    .catch(function () {});
+            }
+            return original.apply(console, arguments);
+        };
+    });
+})();
+"#;
+
+/// Initialization script that reports uncaught exceptions and unhandled
+/// promise rejections to the `page_error` command.
+pub const ERROR_SCRIPT: &str = r#"
+(function () {
+    function report(payload) {
+        var internals = window.__TAURI_INTERNALS__;
+        if (internals) internals.invoke('page_error', { error: payload }).catch(function () {});
+    }
+    window.addEventListener('error', function (e) {
+        // Failed resource loads also fire 'error' but are not script errors
+        if (!(e instanceof ErrorEvent)) return;
+        report({
+            kind: 'exception',
+            message: e.message,
+            source: e.filename || null,
+            line: e.lineno || null,
+            column: e.colno || null,
+            stack: e.error && e.error.stack ? String(e.error.stack) : null
+        });
+    });
+    window.addEventListener('unhandledrejection', function (e) {
+        var reason = e.reason;
+        report({
+            kind: 'unhandled_rejection',
+            message: reason instanceof Error ? reason.message : String(reason),
+            source: null,
+            line: null,
+            column: null,
+            stack: reason && reason.stack ? String(reason.stack) : null
+        });
+    });
+})();
+"#;
+
+/// An error reported by [`ERROR_SCRIPT`], with the fields of
+/// [`ViewerEvent::PageError`].
+#[derive(Debug, Deserialize)]
+pub struct PageError {
+    pub kind: PageErrorKind,
+    pub message: String,
+    pub source: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub stack: Option<String>,
+}
+
+impl PageError {
+    /// The event reporting this error.
+    pub fn into_event(self) -> ViewerEvent {
+        ViewerEvent::PageError {
+            kind: self.kind,
+            message: self.message,
+            source: self.source,
+            line: self.line,
+            column: self.column,
+            stack: self.stack,
+        }
+    }
+}
+
+/// Appends events to the event file, if one was configured.
+pub struct EventSink {
+    file: Mutex<Option<File>>,
+}
+
+impl EventSink {
+    /// Create a sink writing to `path`, or a no-op sink if `path` is `None`.
+    pub fn new(path: Option<&PathBuf>) -> Self {
+        let file = path.and_then(|path| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| eprintln!("Failed to open event file: {}", e))
+                .ok()
+        });
+
+        Self {
+            file: Mutex::new(file),
+        }
+    }
+
+    /// Append an event as a single JSON line.
+    pub fn emit(&self, event: &ViewerEvent) {
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        let Some(file) = file.as_mut() else {
+            return;
+        };
+
+        let result = serde_json::to_string(event)
+            .map_err(std::io::Error::other)
+            .and_then(|json| writeln!(file, "{}", json))
+            .and_then(|()| file.flush());
+        if let Err(e) = result {
+            eprintln!("Failed to write event: {}", e);
+        }
+    }
+}
+
+/// Whether `url` is the app's own placeholder page, which is shown before the
+/// requested content is loaded and should not be reported.
+pub fn is_placeholder_url(url: &Url) -> bool {
+    url.scheme() == "tauri" || url.host_str() == Some("tauri.localhost")
+}
@@ -0,0 +1,131 @@
+//! Refreshed contents kept when
+//! [`BehaviourOptions::refresh_history`] is set.
+//!
+//! Each version is rendered into the bundle under its own path prefix and
+//! shown in a frame of the history page. The managed [`HistoryList`] holds
+//! the versions kept: the page fetches them with [`history_list`] once it
+//! has loaded, and is sent the whole list through
+//! `window.__htmlViewHistory.sync` whenever a refresh adds one.
+//!
+//! [`BehaviourOptions::refresh_history`]: html_view_shared::BehaviourOptions::refresh_history
+
+use crate::bundle::BundleStore;
+use crate::content_loader::load_frame;
+use anyhow::{Context, Result};
+use html_view_shared::render::script_json;
+use html_view_shared::{ViewerContent, ViewerRequest};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{Manager, State, WebviewWindow};
+
+/// A version of the content and the frame showing it.
+#[derive(Clone, Serialize)]
+pub struct Version {
+    /// Number of the version, counting every version shown so far.
+    key: u64,
+
+    /// URL the version's frame opens.
+    src: String,
+
+    /// When the version was shown, in milliseconds since the Unix epoch.
+    received: u64,
+}
+
+/// The versions kept, oldest first, if the window is showing a history.
+#[derive(Default)]
+pub struct HistoryList {
+    inner: Mutex<Option<VecDeque<Version>>>,
+    next_key: AtomicU64,
+}
+
+impl HistoryList {
+    /// Whether the window is showing a history.
+    pub fn is_active(&self) -> bool {
+        self.inner
+            .lock()
+            .map(|inner| inner.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Start an empty history.
+    pub fn start(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = Some(VecDeque::new());
+        }
+    }
+
+    /// Record that the window no longer shows a history.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = None;
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut VecDeque<Version>) -> Result<T>) -> Result<T> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("History state is poisoned"))?;
+        match inner.as_mut() {
+            Some(versions) => f(versions),
+            None => anyhow::bail!("The displayed content has no history"),
+        }
+    }
+}
+
+/// Bundle path prefix version `key` is served under.
+fn version_prefix(key: u64) -> String {
+    format!("history/{key}")
+}
+
+/// Add `content` as the newest version, dropping the oldest beyond `limit`,
+/// and bring the page up to date.
+pub fn push_version(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    content: &ViewerContent,
+    limit: usize,
+) -> Result<()> {
+    let list = window.state::<HistoryList>();
+    let key = list.next_key.fetch_add(1, Ordering::Relaxed);
+    let prefix = version_prefix(key);
+    let src = load_frame(window, request, content, &prefix)?;
+    let received = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+
+    let store = window.state::<BundleStore>();
+    let versions = list.with(|versions| {
+        versions.push_back(Version { key, src, received });
+        while versions.len() > limit.max(1) {
+            if let Some(dropped) = versions.pop_front() {
+                store.remove_prefix(&version_prefix(dropped.key));
+            }
+        }
+        Ok(versions.clone())
+    });
+    let versions = match versions {
+        Ok(versions) => versions,
+        Err(e) => {
+            store.remove_prefix(&prefix);
+            return Err(e);
+        }
+    };
+
+    let js = format!(
+        "window.__htmlViewHistory && window.__htmlViewHistory.sync({});",
+        script_json(&versions)
+    );
+    window.eval(&js).context("Failed to update the history")
+}
+
+/// The versions kept, fetched by the history page when it loads.
+#[tauri::command]
+pub fn history_list(list: State<'_, HistoryList>) -> Result<VecDeque<Version>, String> {
+    list.with(|versions| Ok(versions.clone()))
+        .map_err(|e| e.to_string())
+}
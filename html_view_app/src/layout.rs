@@ -0,0 +1,98 @@
+//! Cells of a [`ViewerContent::Layout`] page.
+//!
+//! Each cell's content is rendered into the bundle under a path prefix of
+//! its own, fixed for the cell, and shown in a frame of the grid page. The
+//! managed [`CellList`] tracks what the cells show, so one can be replaced
+//! without reloading the others.
+//!
+//! [`ViewerContent::Layout`]: html_view_shared::ViewerContent::Layout
+
+use crate::bundle::BundleStore;
+use crate::content_loader::{check_frame, layout_page, load_frame};
+use anyhow::{Context, Result};
+use html_view_shared::render::script_json;
+use html_view_shared::{Layout, ViewerContent, ViewerRequest};
+use std::sync::Mutex;
+use tauri::{Manager, WebviewWindow};
+
+/// The grid being shown and the URLs its frames open, in cell order.
+pub struct Cells {
+    pub layout: Layout,
+    pub srcs: Vec<String>,
+}
+
+/// The cells being shown, if the window is showing a layout.
+#[derive(Default)]
+pub struct CellList {
+    inner: Mutex<Option<Cells>>,
+}
+
+impl CellList {
+    /// Show `cells`.
+    pub fn reset(&self, cells: Cells) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = Some(cells);
+        }
+    }
+
+    /// Record that the window no longer shows a layout.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = None;
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut Cells) -> Result<T>) -> Result<T> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Layout state is poisoned"))?;
+        match inner.as_mut() {
+            Some(cells) => f(cells),
+            None => anyhow::bail!("The displayed content is not a layout"),
+        }
+    }
+}
+
+/// Bundle path prefix the content of the cell at `index` is served under.
+pub fn cell_prefix(index: usize) -> String {
+    format!("cell/{index}")
+}
+
+/// Replace the content of the cell whose top-left corner is at `row` and
+/// `column`.
+pub fn refresh_cell(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    row: usize,
+    column: usize,
+    content: &ViewerContent,
+) -> Result<()> {
+    check_frame(content)?;
+    let list = window.state::<CellList>();
+    let index = list.with(|cells| {
+        cells
+            .layout
+            .position(row, column)
+            .with_context(|| format!("No cell is at row {}, column {}", row, column))
+    })?;
+
+    // The prefix stays the same, so a page still loading picks up the new
+    // content whichever version of the grid it got
+    let src = load_frame(window, request, content, &cell_prefix(index))?;
+    let page = list.with(|cells| {
+        cells.layout.cells[index].content = content.clone();
+        cells.srcs[index] = src.clone();
+        Ok(layout_page(request, &cells.layout, &cells.srcs))
+    })?;
+    window
+        .state::<BundleStore>()
+        .insert("index.html", page.into_bytes());
+
+    let js = format!(
+        "window.__htmlViewLayout && window.__htmlViewLayout.update({}, {});",
+        index,
+        script_json(&src)
+    );
+    window.eval(&js).context("Failed to refresh the cell")
+}
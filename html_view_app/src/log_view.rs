@@ -0,0 +1,94 @@
+//! Entries of a [`ViewerContent::Log`], retained so the page can catch up.
+//!
+//! Entries appended before the log page has loaded (or while it reloads)
+//! would be lost if they were only pushed into the page. The managed
+//! [`LogBuffer`] numbers and keeps the most recent entries, and the page
+//! fetches any it missed with the [`log_entries`] command.
+//!
+//! [`ViewerContent::Log`]: html_view_shared::ViewerContent::Log
+
+use html_view_shared::LogEntry;
+use html_view_shared::render::DEFAULT_LOG_LINES;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::State;
+
+/// The most recent log entries, numbered from the first ever appended.
+pub struct LogBuffer {
+    inner: Mutex<Entries>,
+}
+
+struct Entries {
+    /// Index of the oldest retained entry.
+    first: u64,
+    entries: VecDeque<LogEntry>,
+    max_entries: usize,
+}
+
+/// Entries starting at index `first`, as sent to the page.
+#[derive(Serialize)]
+pub struct LogBatch {
+    first: u64,
+    entries: Vec<LogEntry>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Entries {
+                first: 0,
+                entries: VecDeque::new(),
+                max_entries: DEFAULT_LOG_LINES,
+            }),
+        }
+    }
+}
+
+impl LogBuffer {
+    /// Drop all entries and keep at most `max_entries` from now on.
+    pub fn reset(&self, max_entries: usize) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = Entries {
+                first: 0,
+                entries: VecDeque::new(),
+                max_entries: max_entries.max(1),
+            };
+        }
+    }
+
+    /// Append `entries` and return the index of the first of them.
+    pub fn push(&self, entries: &[LogEntry]) -> u64 {
+        let Ok(mut inner) = self.inner.lock() else {
+            return 0;
+        };
+        let index = inner.first + inner.entries.len() as u64;
+        inner.entries.extend(entries.iter().cloned());
+        let excess = inner.entries.len().saturating_sub(inner.max_entries);
+        inner.entries.drain(..excess);
+        inner.first += excess as u64;
+        index
+    }
+
+    /// Retained entries from index `from` on.
+    fn since(&self, from: u64) -> LogBatch {
+        let Ok(inner) = self.inner.lock() else {
+            return LogBatch {
+                first: from,
+                entries: Vec::new(),
+            };
+        };
+        let first = from.max(inner.first);
+        let skip = (first - inner.first) as usize;
+        LogBatch {
+            first,
+            entries: inner.entries.iter().skip(skip).cloned().collect(),
+        }
+    }
+}
+
+/// Entries the log page has not received yet, from index `from` on.
+#[tauri::command]
+pub fn log_entries(buffer: State<'_, LogBuffer>, from: u64) -> LogBatch {
+    buffer.since(from)
+}
@@ -3,10 +3,21 @@
 //! This binary is spawned by the html_view API crate to display HTML content.
 
 mod app;
+mod binary;
+mod bundle;
 mod content_loader;
+mod events;
+mod history;
+mod layout;
+mod log_view;
+mod stream;
+mod tabs;
+mod window_state;
 
 use clap::Parser;
-use html_view_shared::{PROTOCOL_VERSION, ViewerExitReason, ViewerExitStatus, ViewerRequest};
+use html_view_shared::{
+    PROTOCOL_VERSION, ViewerEvent, ViewerExitReason, ViewerExitStatus, ViewerRequest,
+};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -36,6 +47,13 @@ fn main() -> anyhow::Result<()> {
         use std::thread::sleep;
         use std::time::Duration;
 
+        // Report the startup stages so `wait_until` works against the fake viewer
+        let events = events::EventSink::new(request.event_path.as_ref());
+        events.emit(&ViewerEvent::WindowShown);
+        events.emit(&ViewerEvent::Loaded {
+            url: url::Url::parse("about:blank")?,
+        });
+
         let timeout = request.environment.timeout_seconds.unwrap_or(0);
         if timeout > 0 {
             sleep(Duration::from_secs(timeout));
@@ -43,6 +61,9 @@ fn main() -> anyhow::Result<()> {
                 id: request.id,
                 reason: html_view_shared::ViewerExitReason::TimedOut,
                 viewer_version: PROTOCOL_VERSION.to_string(),
+                geometry: None,
+                audio_selection: None,
+                slide: None,
             };
 
             let result_json = serde_json::to_string_pretty(&exit_status)?;
@@ -54,6 +75,9 @@ fn main() -> anyhow::Result<()> {
                 id: request.id,
                 reason: html_view_shared::ViewerExitReason::ClosedByUser,
                 viewer_version: PROTOCOL_VERSION.to_string(),
+                geometry: None,
+                audio_selection: None,
+                slide: None,
             };
             let result_json = serde_json::to_string_pretty(&exit_status)?;
             std::fs::write(&args.result_path, result_json)?;
@@ -72,6 +96,9 @@ fn main() -> anyhow::Result<()> {
                     message: e.to_string(),
                 },
                 viewer_version: PROTOCOL_VERSION.to_string(),
+                geometry: None,
+                audio_selection: None,
+                slide: None,
             }
         }
     };
@@ -0,0 +1,185 @@
+//! Frames streamed by the library through the request's stream file.
+//!
+//! The library replaces the stream file with its latest frame. The file is
+//! watched on its own thread, so a slow command does not hold frames back,
+//! and every frame newer than the last one forwarded is handed to the page
+//! through `window.__htmlViewStream`. [`STREAM_SCRIPT`] keeps only the newest
+//! frame the page was handed and applies it on the next animation frame,
+//! reporting how many frames were applied and dropped with [`stream_stats`].
+
+use crate::events::EventSink;
+use anyhow::{Context, Result};
+use html_view_shared::render::script_json;
+use html_view_shared::{StreamMessage, ViewerEvent};
+use notify::{Event, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use tauri::{State, WebviewWindow};
+
+/// Initialization script that applies streamed frames and defines
+/// `htmlView.onFrame` for pages that handle data frames themselves.
+pub const STREAM_SCRIPT: &str = r#"
+(function () {
+    var api = window.htmlView = window.htmlView || {};
+    var callbacks = [];
+    var pending = null;
+    var scheduled = false;
+    var first = null;
+    var last = null;
+    var applied = 0;
+    var timer = null;
+    var reported = { applied: 0, at: 0 };
+
+    function apply() {
+        scheduled = false;
+        var message = pending;
+        pending = null;
+        if (!message) return;
+        var frame = message.frame;
+        if (frame.type === 'html') {
+            var target = document.querySelector('[data-html-view-stream]') || document.body;
+            if (target) target.innerHTML = frame.html;
+        } else {
+            callbacks.forEach(function (callback) {
+                try {
+                    callback(frame.data, message.seq);
+                } catch (e) {
+                    // Rethrow outside the loop so it is reported as a page error
+                    setTimeout(function () { throw e; });
+                }
+            });
+        }
+        applied++;
+        last = message.seq;
+    }
+
+    function report() {
+        var now = performance.now();
+        if (applied === reported.applied) {
+            clearInterval(timer);
+            timer = null;
+            return;
+        }
+        var internals = window.__TAURI_INTERNALS__;
+        if (internals) {
+            internals.invoke('stream_stats', {
+                applied: applied,
+                dropped: last - first + 1 - applied,
+                fps: (applied - reported.applied) * 1000 / (now - reported.at)
+            }).catch(function () {});
+        }
+        reported = { applied: applied, at: now };
+    }
+
+    window.__htmlViewStream = function (message) {
+        if (first === null) first = message.seq;
+        // A frame still pending is dropped in favour of the newer one
+        pending = message;
+        if (!scheduled) {
+            scheduled = true;
+            requestAnimationFrame(apply);
+        }
+        if (timer === null) {
+            reported = { applied: applied, at: performance.now() };
+            timer = setInterval(report, 1000);
+        }
+    };
+
+    api.onFrame = function (callback) {
+        callbacks.push(callback);
+    };
+})();
+"#;
+
+/// Watch the stream file and forward new frames to the page.
+pub fn watch_stream(stream_path: PathBuf, window: WebviewWindow) -> Result<()> {
+    let (tx, rx) = channel();
+
+    // Create file watcher - must keep it alive for the duration
+    let mut _watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+        if let Err(e) = tx.send(res) {
+            eprintln!("Failed to send watch event: {}", e);
+        }
+    })
+    .context("Failed to create filesystem watcher")?;
+
+    // Watch the parent directory (since the file does not exist until the
+    // first frame is sent)
+    let watch_dir = stream_path
+        .parent()
+        .context("Failed to get parent directory")?;
+
+    _watcher
+        .watch(watch_dir, RecursiveMode::NonRecursive)
+        .context("Failed to watch directory")?;
+
+    let stream_filename = stream_path
+        .file_name()
+        .context("Failed to get stream filename")?
+        .to_owned();
+
+    let mut last_seq = None;
+    loop {
+        let mut changed = false;
+        match rx.recv() {
+            Ok(res) => {
+                // Handle everything queued meanwhile at once, so the file is
+                // read once however many events a burst of frames caused
+                for res in std::iter::once(res).chain(rx.try_iter()) {
+                    match res {
+                        Ok(event) => {
+                            changed |= event
+                                .paths
+                                .iter()
+                                .any(|p| p.file_name() == Some(stream_filename.as_ref()));
+                        }
+                        Err(e) => eprintln!("Watch error: {}", e),
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Channel error: {}", e);
+                break;
+            }
+        }
+
+        if changed && let Err(e) = forward_frame(&stream_path, &window, &mut last_seq) {
+            eprintln!("Failed to forward frame: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Hand the frame in the stream file to the page, unless it was already
+/// forwarded.
+fn forward_frame(path: &Path, window: &WebviewWindow, last_seq: &mut Option<u64>) -> Result<()> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        // Removed along with the temporary directory when the library exits
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).context("Failed to read stream file"),
+    };
+    let message: StreamMessage =
+        serde_json::from_slice(&data).context("Failed to parse streamed frame")?;
+    if last_seq.is_some_and(|last| message.seq <= last) {
+        return Ok(());
+    }
+    *last_seq = Some(message.seq);
+
+    let js = format!(
+        "window.__htmlViewStream && window.__htmlViewStream({});",
+        script_json(&message)
+    );
+    window.eval(&js).context("Failed to apply streamed frame")
+}
+
+/// Report the delivery of streamed frames measured by the page.
+#[tauri::command]
+pub fn stream_stats(events: State<'_, EventSink>, applied: u64, dropped: u64, fps: f64) {
+    events.emit(&ViewerEvent::StreamStats {
+        applied,
+        dropped,
+        fps,
+    });
+}
@@ -0,0 +1,242 @@
+//! Tabs of a [`ViewerContent::Tabs`] page.
+//!
+//! Each tab's content is rendered into the bundle under its own path prefix
+//! and shown in a frame of the tab page. The managed [`TabList`] tracks the
+//! tabs: the page fetches them with [`tab_list`] once it has loaded and
+//! reports tabs the user selects or closes, while changes made by commands
+//! are pushed into the page through `window.__htmlViewTabs`.
+//!
+//! [`ViewerContent::Tabs`]: html_view_shared::ViewerContent::Tabs
+
+use crate::bundle::BundleStore;
+use crate::content_loader::{check_frame, load_frame};
+use anyhow::{Context, Result};
+use html_view_shared::render::script_json;
+use html_view_shared::{ViewerContent, ViewerRequest};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{Manager, State, WebviewWindow};
+
+/// A tab and the frame showing it.
+#[derive(Clone, Serialize)]
+pub struct Tab {
+    pub id: String,
+
+    /// URL the tab's frame opens.
+    src: String,
+
+    #[serde(skip)]
+    content: ViewerContent,
+
+    /// Bundle path prefix the content is served under.
+    #[serde(skip)]
+    prefix: String,
+}
+
+impl Tab {
+    pub fn new(id: String, content: ViewerContent, prefix: String, src: String) -> Self {
+        Self {
+            id,
+            src,
+            content,
+            prefix,
+        }
+    }
+}
+
+/// The open tabs, in order, as sent to the page.
+#[derive(Clone, Serialize)]
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    selected: Option<String>,
+}
+
+impl Tabs {
+    fn position(&self, id: &str) -> Result<usize> {
+        self.tabs
+            .iter()
+            .position(|tab| tab.id == id)
+            .with_context(|| format!("No tab has id {:?}", id))
+    }
+
+    fn select(&mut self, id: &str) -> Result<()> {
+        self.position(id)?;
+        self.selected = Some(id.to_string());
+        Ok(())
+    }
+
+    /// Remove a tab, selecting the one that takes its place if it was
+    /// selected, as the page does.
+    fn remove(&mut self, id: &str) -> Result<Tab> {
+        let index = self.position(id)?;
+        let tab = self.tabs.remove(index);
+        if self.selected.as_deref() == Some(id) {
+            self.selected = self
+                .tabs
+                .get(index.min(self.tabs.len().saturating_sub(1)))
+                .map(|tab| tab.id.clone());
+        }
+        Ok(tab)
+    }
+}
+
+/// The tabs being shown, if the window is showing tabs.
+///
+/// Tabs changed after loading are only tracked here, not in the content
+/// the window was loaded with.
+#[derive(Default)]
+pub struct TabList {
+    inner: Mutex<Option<Tabs>>,
+    next_key: AtomicU64,
+}
+
+impl TabList {
+    /// A bundle path prefix not used by any tab yet.
+    pub fn next_prefix(&self) -> String {
+        format!("tab/{}", self.next_key.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Show `tabs`, selecting the first.
+    pub fn reset(&self, tabs: Vec<Tab>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let selected = tabs.first().map(|tab| tab.id.clone());
+            *inner = Some(Tabs { tabs, selected });
+        }
+    }
+
+    /// Record that the window no longer shows tabs.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = None;
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut Tabs) -> Result<T>) -> Result<T> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Tab state is poisoned"))?;
+        match inner.as_mut() {
+            Some(tabs) => f(tabs),
+            None => anyhow::bail!("The displayed content is not tabs"),
+        }
+    }
+}
+
+/// Add a tab after the others and select it.
+pub fn add_tab(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    id: &str,
+    content: &ViewerContent,
+) -> Result<()> {
+    check_frame(content)?;
+    let list = window.state::<TabList>();
+    list.with(|tabs| match tabs.position(id) {
+        Ok(_) => anyhow::bail!("A tab with id {:?} is already open", id),
+        Err(_) => Ok(()),
+    })?;
+
+    let prefix = list.next_prefix();
+    let src = load_frame(window, request, content, &prefix)?;
+    let js = format!(
+        "window.__htmlViewTabs && window.__htmlViewTabs.add({}, {});",
+        script_json(id),
+        script_json(&src)
+    );
+    list.with(|tabs| {
+        tabs.tabs
+            .push(Tab::new(id.to_string(), content.clone(), prefix, src));
+        tabs.selected = Some(id.to_string());
+        Ok(())
+    })?;
+    window.eval(&js).context("Failed to add the tab")
+}
+
+/// Close a tab.
+pub fn close_tab(window: &WebviewWindow, id: &str) -> Result<()> {
+    let tab = window.state::<TabList>().with(|tabs| tabs.remove(id))?;
+    window.state::<BundleStore>().remove_prefix(&tab.prefix);
+    let js = format!(
+        "window.__htmlViewTabs && window.__htmlViewTabs.close({});",
+        script_json(id)
+    );
+    window.eval(&js).context("Failed to close the tab")
+}
+
+/// Select a tab.
+pub fn select_tab(window: &WebviewWindow, id: &str) -> Result<()> {
+    window.state::<TabList>().with(|tabs| tabs.select(id))?;
+    let js = format!(
+        "window.__htmlViewTabs && window.__htmlViewTabs.select({});",
+        script_json(id)
+    );
+    window.eval(&js).context("Failed to select the tab")
+}
+
+/// Replace the content of a tab, leaving its place and the selection as
+/// they are.
+pub fn refresh_tab(
+    window: &WebviewWindow,
+    request: &ViewerRequest,
+    id: &str,
+    content: &ViewerContent,
+) -> Result<()> {
+    check_frame(content)?;
+    let list = window.state::<TabList>();
+    list.with(|tabs| tabs.position(id).map(|_| ()))?;
+
+    // A fresh prefix gives the frame a new URL, so nothing stale is reused
+    let prefix = list.next_prefix();
+    let src = load_frame(window, request, content, &prefix)?;
+    let js = format!(
+        "window.__htmlViewTabs && window.__htmlViewTabs.update({}, {});",
+        script_json(id),
+        script_json(&src)
+    );
+    let store = window.state::<BundleStore>();
+    let replaced = list.with(|tabs| {
+        let index = tabs.position(id)?;
+        let tab = &mut tabs.tabs[index];
+        tab.content = content.clone();
+        tab.src = src;
+        Ok(std::mem::replace(&mut tab.prefix, prefix.clone()))
+    });
+    match replaced {
+        Ok(old_prefix) => store.remove_prefix(&old_prefix),
+        Err(e) => {
+            store.remove_prefix(&prefix);
+            return Err(e);
+        }
+    }
+    window.eval(&js).context("Failed to refresh the tab")
+}
+
+/// The open tabs, fetched by the tab page when it loads.
+#[tauri::command]
+pub fn tab_list(list: State<'_, TabList>) -> Result<Tabs, String> {
+    list.with(|tabs| Ok(tabs.clone()))
+        .map_err(|e| e.to_string())
+}
+
+/// Record a tab selected in the page.
+#[tauri::command]
+pub fn tab_selected(list: State<'_, TabList>, id: String) -> Result<(), String> {
+    list.with(|tabs| tabs.select(&id))
+        .map_err(|e| e.to_string())
+}
+
+/// Record a tab closed in the page and stop serving its content.
+#[tauri::command]
+pub fn tab_closed(
+    list: State<'_, TabList>,
+    store: State<'_, BundleStore>,
+    id: String,
+) -> Result<(), String> {
+    let tab = list
+        .with(|tabs| tabs.remove(&id))
+        .map_err(|e| e.to_string())?;
+    store.remove_prefix(&tab.prefix);
+    Ok(())
+}
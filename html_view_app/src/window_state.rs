@@ -0,0 +1,144 @@
+//! Persistence of window geometry and zoom between runs.
+//!
+//! The state of each `WindowOptions::state_key` is stored in its own JSON
+//! file in the app's config directory, so viewers saving different keys at
+//! the same time cannot overwrite each other's state.
+
+use anyhow::{Context, Result};
+use html_view_shared::{WindowGeometry, WindowOptions};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Name of the directory holding the state files inside the app config
+/// directory.
+const STATE_DIR: &str = "window_state";
+
+/// Zoom factor bounds and step for the zoom shortcuts.
+pub const MIN_ZOOM: f64 = 0.3;
+pub const MAX_ZOOM: f64 = 3.0;
+pub const ZOOM_STEP: f64 = 0.1;
+
+/// Initialization script that maps Ctrl/Cmd + `+`, `-` and `0` to the
+/// `zoom_step` command so the zoom level can be tracked and persisted.
+pub const ZOOM_SCRIPT: &str = r#"
+window.addEventListener('keydown', function (e) {
+    if (!(e.ctrlKey || e.metaKey)) return;
+    var step = { '=': 1, '+': 1, '-': -1, '0': 0 }[e.key];
+    if (step === undefined || !window.__TAURI_INTERNALS__) return;
+    e.preventDefault();
+    window.__TAURI_INTERNALS__.invoke('zoom_step', { step: step });
+});
+"#;
+
+/// Saved state of a single window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedWindowState {
+    /// Outer X position in logical pixels.
+    pub x: i32,
+    /// Outer Y position in logical pixels.
+    pub y: i32,
+    /// Inner width in logical pixels.
+    pub width: u32,
+    /// Inner height in logical pixels.
+    pub height: u32,
+    /// Whether the window was maximised.
+    pub maximised: bool,
+    /// Webview zoom factor.
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+}
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+/// Load the saved state for `key`, if any.
+pub fn load(app: &AppHandle, key: &str) -> Option<SavedWindowState> {
+    let path = state_file(app, key).ok()?;
+    read(&path)
+}
+
+/// Save the state for `key`.
+///
+/// When the window is maximised, the previously saved size and position are
+/// kept so that unmaximising restores a sensible window.
+pub fn save(app: &AppHandle, key: &str, geometry: &WindowGeometry, zoom: f64) -> Result<()> {
+    let path = state_file(app, key)?;
+
+    let state = match read(&path) {
+        Some(previous) if geometry.maximised || geometry.fullscreen => SavedWindowState {
+            maximised: geometry.maximised,
+            zoom,
+            ..previous
+        },
+        _ => SavedWindowState {
+            x: geometry.x,
+            y: geometry.y,
+            width: geometry.width,
+            height: geometry.height,
+            maximised: geometry.maximised,
+            zoom,
+        },
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+    }
+
+    // Write atomically (temp file + rename)
+    let temp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(&state).context("Failed to serialize state")?;
+    std::fs::write(&temp_path, json).context("Failed to write window state")?;
+    std::fs::rename(&temp_path, &path).context("Failed to replace window state")?;
+
+    Ok(())
+}
+
+/// Merge saved state into the requested window options.
+///
+/// Values the caller set explicitly win; only options left unset are
+/// replaced by the saved ones.
+pub fn apply(options: &WindowOptions, saved: &SavedWindowState) -> WindowOptions {
+    let mut merged = options.clone();
+
+    merged.width = options.width.or(Some(saved.width));
+    merged.height = options.height.or(Some(saved.height));
+
+    if options.x.is_none() && options.y.is_none() {
+        merged.x = Some(saved.x);
+        merged.y = Some(saved.y);
+    }
+
+    merged.maximised = options.maximised || saved.maximised;
+
+    merged
+}
+
+fn state_file(app: &AppHandle, key: &str) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .context("Failed to resolve config directory")?;
+    Ok(dir.join(STATE_DIR).join(file_name(key)))
+}
+
+/// File name for `key`, keeping letters, digits, `-` and `_` and
+/// percent-encoding every other byte so distinct keys never share a file.
+fn file_name(key: &str) -> String {
+    let mut name = String::with_capacity(key.len() + 5);
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    name.push_str(".json");
+    name
+}
+
+fn read(path: &Path) -> Option<SavedWindowState> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+}
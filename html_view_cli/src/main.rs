@@ -1,10 +1,16 @@
 //! CLI tool for html_view - display HTML from the command line.
 
+use anyhow::Context;
 use clap::{Parser, Subcommand};
 use html_view::{
-    BehaviourOptions, EnvironmentOptions, ViewerContent, ViewerOptions, WindowOptions,
+    BehaviourOptions, DiagramKind, DiffMode, EnvironmentOptions, Layout, TableSource,
+    ViewerContent, ViewerOptions, WindowOptions,
+};
+use std::{
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
 };
-use std::{fmt::Display, path::PathBuf};
 use url::Url;
 
 #[derive(Parser)]
@@ -74,6 +80,88 @@ enum Commands {
         #[arg(long)]
         entry: Option<String>,
     },
+    /// Display an HTML application from a zip, tar or tar.gz archive
+    Archive {
+        /// Path to the archive
+        path: PathBuf,
+        /// Entry file (default: index.html)
+        #[arg(long)]
+        entry: Option<String>,
+    },
+    /// Display a CSV or TSV file as a sortable, filterable table
+    Table {
+        /// Path to the CSV or TSV file
+        path: PathBuf,
+        /// Field delimiter (default: tab for .tsv/.tab files, comma otherwise)
+        #[arg(long)]
+        delimiter: Option<char>,
+    },
+    /// Display a source file with syntax highlighting
+    Code {
+        /// Path to the source file
+        path: PathBuf,
+        /// Language (default: from the file extension)
+        #[arg(long)]
+        language: Option<String>,
+        /// Line to mark and scroll to (repeatable)
+        #[arg(long = "line")]
+        lines: Vec<usize>,
+    },
+    /// Display a line diff between two files
+    Diff {
+        /// Original file
+        old: PathBuf,
+        /// Changed file
+        new: PathBuf,
+        /// Show old and new side by side
+        #[arg(long)]
+        side_by_side: bool,
+    },
+    /// Display terminal output with ANSI colours, read from stdin or a file
+    Ansi {
+        /// File to read (default: stdin)
+        path: Option<PathBuf>,
+    },
+    /// Play an audio file over its waveform and print the selected range
+    Audio {
+        /// WAV, FLAC, MP3 or Ogg Vorbis file
+        path: PathBuf,
+        /// Show the spectrogram from the start
+        #[arg(long)]
+        spectrogram: bool,
+    },
+    /// Draw a Graphviz DOT or Mermaid diagram, read from stdin or a file
+    Diagram {
+        /// File to read (default: stdin)
+        path: Option<PathBuf>,
+        /// Parse as Mermaid (default: for .mmd/.mermaid files; DOT otherwise)
+        #[arg(long)]
+        mermaid: bool,
+    },
+    /// Display several local HTML files as tabs of one window
+    Tabs {
+        /// HTML files to open, one tab each, labelled by file name
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Display several local HTML files side by side in a grid
+    Layout {
+        /// HTML files to show, filling the grid row by row
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Number of columns (default: all files in one row)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        columns: Option<u16>,
+    },
+    /// Step through several local HTML files as slides
+    Slides {
+        /// HTML files to show, one slide each
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Move to the next slide every this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        advance: Option<f64>,
+    },
     /// Display a remote URL
     Url {
         /// URL to display
@@ -92,6 +180,42 @@ impl Display for Commands {
                 root.display(),
                 entry.as_deref().unwrap_or("index.html")
             ),
+            Commands::Archive { path, entry } => write!(
+                f,
+                "Archive Command with path: {} and entry: {}",
+                path.display(),
+                entry.as_deref().unwrap_or("index.html")
+            ),
+            Commands::Table { path, .. } => {
+                write!(f, "Table Command with path: {}", path.display())
+            }
+            Commands::Code { path, .. } => {
+                write!(f, "Code Command with path: {}", path.display())
+            }
+            Commands::Diff { old, new, .. } => write!(
+                f,
+                "Diff Command with old: {} and new: {}",
+                old.display(),
+                new.display()
+            ),
+            Commands::Ansi { path } => match path {
+                Some(path) => write!(f, "Ansi Command with path: {}", path.display()),
+                None => write!(f, "Ansi Command reading stdin"),
+            },
+            Commands::Audio { path, .. } => {
+                write!(f, "Audio Command with path: {}", path.display())
+            }
+            Commands::Diagram { path, .. } => match path {
+                Some(path) => write!(f, "Diagram Command with path: {}", path.display()),
+                None => write!(f, "Diagram Command reading stdin"),
+            },
+            Commands::Tabs { paths } => write!(f, "Tabs Command with {} files", paths.len()),
+            Commands::Layout { paths, .. } => {
+                write!(f, "Layout Command with {} files", paths.len())
+            }
+            Commands::Slides { paths, .. } => {
+                write!(f, "Slides Command with {} files", paths.len())
+            }
             Commands::Url { url } => write!(f, "Url Command with url: {}", url),
         }
     }
@@ -113,6 +237,95 @@ fn main() -> anyhow::Result<()> {
             root: root.to_path_buf(),
             entry: entry.to_owned(),
         },
+        Commands::Archive { path, entry } => ViewerContent::Archive {
+            path: path.to_path_buf(),
+            entry: entry.to_owned(),
+        },
+        Commands::Table { path, delimiter } => ViewerContent::Table {
+            source: TableSource::File {
+                path: path.to_path_buf(),
+                delimiter: *delimiter,
+            },
+        },
+        Commands::Code {
+            path,
+            language,
+            lines,
+        } => ViewerContent::Code {
+            source: read(path)?,
+            language: language.clone().or_else(|| extension(path)),
+            line_numbers: true,
+            highlight_lines: lines.clone(),
+        },
+        Commands::Diff {
+            old,
+            new,
+            side_by_side,
+        } => ViewerContent::Diff {
+            old: read(old)?,
+            new: read(new)?,
+            mode: if *side_by_side {
+                DiffMode::SideBySide
+            } else {
+                DiffMode::Unified
+            },
+            language: extension(new),
+        },
+        Commands::Ansi { path } => ViewerContent::AnsiText {
+            text: read_or_stdin(path.as_deref())?,
+        },
+        Commands::Audio { path, spectrogram } => ViewerContent::Audio {
+            source: path.clone().into(),
+            spectrogram: *spectrogram,
+        },
+        Commands::Diagram { path, mermaid } => ViewerContent::Diagram {
+            source: read_or_stdin(path.as_deref())?,
+            kind: if *mermaid
+                || path
+                    .as_deref()
+                    .and_then(extension)
+                    .is_some_and(|extension| extension == "mmd" || extension == "mermaid")
+            {
+                DiagramKind::Mermaid
+            } else {
+                DiagramKind::Dot
+            },
+        },
+        Commands::Tabs { paths } => ViewerContent::Tabs {
+            tabs: tab_labels(paths)
+                .into_iter()
+                .zip(paths)
+                .map(|(label, path)| {
+                    (
+                        label,
+                        ViewerContent::LocalFile {
+                            path: path.to_path_buf(),
+                        },
+                    )
+                })
+                .collect(),
+        },
+        Commands::Layout { paths, columns } => {
+            let columns = columns.map_or(paths.len(), usize::from);
+            let layout = paths.iter().enumerate().fold(
+                Layout::grid(paths.len().div_ceil(columns), columns),
+                |layout, (index, path)| {
+                    layout.cell_content(
+                        index / columns,
+                        index % columns,
+                        ViewerContent::LocalFile { path: path.clone() },
+                    )
+                },
+            );
+            ViewerContent::Layout { layout }
+        }
+        Commands::Slides { paths, advance } => ViewerContent::Slides {
+            slides: paths
+                .iter()
+                .map(|path| ViewerContent::LocalFile { path: path.clone() })
+                .collect(),
+            advance_seconds: *advance,
+        },
         Commands::Url { url } => ViewerContent::RemoteUrl {
             url: Url::parse(url)?,
         },
@@ -172,15 +385,69 @@ fn main() -> anyhow::Result<()> {
         environment,
         dialog: html_view::DialogOptions::default(),
         wait: html_view::ViewerWaitMode::Blocking,
+        wait_until: html_view::WaitUntil::Spawned,
     };
 
     // Open viewer
     match html_view::open(options)? {
         html_view::ViewerResult::Blocking(status) => {
             println!("Viewer exited: {:?}", status.reason);
+            if let Some(selection) = status.audio_selection {
+                println!("Selection: {:.3}s - {:.3}s", selection.start, selection.end);
+            }
+            if let Some(slide) = status.slide {
+                println!("Slide: {}", slide + 1);
+            }
         }
         _ => unreachable!(),
     }
 
     Ok(())
 }
+
+/// Read a text file for the `code`, `diff` and `ansi` subcommands.
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Read `path`, or stdin if it is missing or `-`.
+fn read_or_stdin(path: Option<&Path>) -> anyhow::Result<String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => read(path),
+        _ => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .context("Failed to read stdin")?;
+            Ok(text)
+        }
+    }
+}
+
+/// Tab labels for `paths`: their file names, or the whole path for names
+/// that occur more than once.
+fn tab_labels(paths: &[PathBuf]) -> Vec<String> {
+    let name = |path: &PathBuf| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+    paths
+        .iter()
+        .map(|path| {
+            let label = name(path);
+            if paths.iter().filter(|other| name(other) == label).count() > 1 {
+                path.display().to_string()
+            } else {
+                label
+            }
+        })
+        .collect()
+}
+
+/// The file extension, used as the highlighting language.
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_string)
+}
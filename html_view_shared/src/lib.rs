@@ -3,7 +3,12 @@
 //! This crate defines the wire protocol between the API crate and the Tauri app,
 //! including all request and response types that cross the process boundary.
 
+mod base64_bytes;
+#[cfg(feature = "render")]
+pub mod render;
+
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use url::Url;
 use uuid::Uuid;
@@ -40,6 +45,16 @@ pub struct ViewerRequest {
     /// Optional path to command file for runtime updates.
     #[serde(default)]
     pub command_path: Option<PathBuf>,
+
+    /// Optional path to a file the viewer appends [`ViewerEvent`]s to,
+    /// one JSON object per line.
+    #[serde(default)]
+    pub event_path: Option<PathBuf>,
+
+    /// Optional path to a file holding the latest [`StreamMessage`], replaced
+    /// as a whole for every frame streamed to the viewer.
+    #[serde(default)]
+    pub stream_path: Option<PathBuf>,
 }
 
 /// The type of content to display in the viewer.
@@ -77,6 +92,558 @@ pub enum ViewerContent {
         /// The URL to load.
         url: Url,
     },
+
+    /// Markdown source, rendered to HTML by the viewer.
+    ///
+    /// CommonMark with the GitHub extensions (tables, task lists,
+    /// strikethrough, footnotes), highlighted fenced code blocks and a
+    /// stylesheet following [`WindowOptions::theme_enum`].
+    Markdown {
+        /// The Markdown source.
+        source: String,
+
+        /// Optional base directory used to resolve relative links and images.
+        #[serde(default)]
+        base_dir: Option<PathBuf>,
+    },
+
+    /// An image, shown in a viewer page with fit/actual size, zoom and pan,
+    /// and a pixel coordinate and colour readout.
+    Image {
+        /// Where the image data comes from.
+        source: ImageSource,
+
+        /// MIME type such as `image/png`. If None, it is detected from the
+        /// file extension or the image data.
+        #[serde(default)]
+        mime: Option<String>,
+    },
+
+    /// An HTML page and its assets served from memory.
+    ///
+    /// Files are served to the webview by path from a custom protocol, so
+    /// relative references between them (scripts, stylesheets, fonts,
+    /// images, `fetch`) work as they would from a directory.
+    Bundle {
+        /// Path of the HTML entry file within `files`, e.g. "index.html".
+        entry: String,
+
+        /// File contents keyed by their path relative to the bundle root,
+        /// using `/` as the separator.
+        #[serde(with = "base64_bytes::map")]
+        files: HashMap<String, Vec<u8>>,
+    },
+
+    /// An HTML template rendered with JSON data by the viewer.
+    ///
+    /// After the first render, the data can be replaced on its own with
+    /// [`ViewerCommand::UpdateData`], without resending the template.
+    Template {
+        /// The template source.
+        template: String,
+
+        /// The data the template is rendered with, available as top-level
+        /// variables when it is an object.
+        #[serde(default)]
+        data: serde_json::Value,
+
+        /// The template engine used to render `template`.
+        #[serde(default)]
+        engine: TemplateEngine,
+    },
+
+    /// A JSON value shown in a collapsible tree explorer with search,
+    /// copy-path and expand-to-depth.
+    Json {
+        /// The value to explore.
+        value: serde_json::Value,
+    },
+
+    /// A static HTML application served straight out of a zip, tar or
+    /// tar.gz archive, without extracting it to disk.
+    Archive {
+        /// Path to the archive.
+        path: PathBuf,
+
+        /// The entry HTML file within the archive, defaults to "index.html".
+        /// Archives holding a single top-level directory are also searched
+        /// inside it.
+        #[serde(default)]
+        entry: Option<String>,
+    },
+
+    /// Tabular data shown in a grid with column sorting, per-column
+    /// filters, virtual scrolling and CSV export.
+    Table {
+        /// Where the rows come from.
+        source: TableSource,
+    },
+
+    /// Source code with syntax highlighting.
+    Code {
+        /// The code to show.
+        source: String,
+
+        /// Language name, alias or file extension, such as `rust` or `py`.
+        /// Unknown or missing languages are shown as plain text.
+        #[serde(default)]
+        language: Option<String>,
+
+        /// Whether to show line numbers.
+        #[serde(default = "default_true")]
+        line_numbers: bool,
+
+        /// 1-based line numbers to mark. The first one is scrolled into view.
+        #[serde(default)]
+        highlight_lines: Vec<usize>,
+    },
+
+    /// A line diff between two texts, with syntax highlighting and
+    /// unchanged runs collapsed.
+    Diff {
+        /// The original (or expected) text.
+        old: String,
+
+        /// The changed (or actual) text.
+        new: String,
+
+        /// How the diff is laid out initially. The page can switch modes.
+        #[serde(default)]
+        mode: DiffMode,
+
+        /// Language used to highlight both sides, as for
+        /// [`ViewerContent::Code`].
+        #[serde(default)]
+        language: Option<String>,
+    },
+
+    /// Terminal output with ANSI colour and style escape sequences, shown in
+    /// a searchable monospace view.
+    AnsiText {
+        /// The captured output.
+        text: String,
+    },
+
+    /// An append-only log, fed with [`ViewerCommand::AppendLog`]. The view
+    /// follows new lines unless scrolled up, and can be filtered by level
+    /// and text.
+    Log {
+        /// Lines retained before the oldest are dropped. Defaults to 10,000.
+        #[serde(default)]
+        max_lines: Option<usize>,
+    },
+
+    /// Audio shown as a zoomable waveform with playback and selection. The
+    /// selected time range is reported with
+    /// [`ViewerEvent::SelectionChanged`] and in
+    /// [`ViewerExitStatus::audio_selection`].
+    Audio {
+        /// The audio to show.
+        source: AudioSource,
+
+        /// Whether the spectrogram is shown initially. The page can toggle
+        /// it either way.
+        #[serde(default)]
+        spectrogram: bool,
+    },
+
+    /// A Graphviz DOT or Mermaid diagram, laid out offline and shown as SVG
+    /// with pan, zoom and saving.
+    ///
+    /// The viewer parses and lays out diagrams itself, so only part of each
+    /// language is supported; see [`DiagramKind`]. Sources outside it fail
+    /// to load with an error naming what is unsupported.
+    Diagram {
+        /// The diagram source.
+        source: String,
+
+        /// The language of `source`.
+        #[serde(default)]
+        kind: DiagramKind,
+    },
+
+    /// Several contents in one window, behind a tab strip.
+    ///
+    /// Each tab is shown in its own frame, so tabs keep their state while
+    /// others are selected and their styles do not collide. Tabs are named
+    /// by an id, also used as their label, which commands such as
+    /// [`ViewerCommand::RefreshTab`] refer to. Tabs cannot hold a
+    /// [`ViewerContent::Log`] or further tabs.
+    Tabs {
+        /// The tabs as `(id, content)`, in order. The first is selected.
+        tabs: Vec<(String, ViewerContent)>,
+    },
+
+    /// Several contents side by side in a grid, like subplots.
+    ///
+    /// Each cell is shown in its own frame, so styles do not collide, and can
+    /// be replaced on its own with [`ViewerCommand::RefreshCell`]. Cells
+    /// cannot hold a [`ViewerContent::Log`], tabs or a further layout.
+    Layout {
+        /// The grid and its cells.
+        layout: Layout,
+    },
+
+    /// Several contents shown one at a time, stepped through with the
+    /// keyboard or the buttons below them.
+    ///
+    /// Each slide is shown in its own frame. The slide shown is reported
+    /// with [`ViewerEvent::SlideChanged`] and in
+    /// [`ViewerExitStatus::slide`]. Slides cannot hold a
+    /// [`ViewerContent::Log`], tabs, a layout or further slides.
+    Slides {
+        /// The slides, in order. The first is shown initially.
+        slides: Vec<ViewerContent>,
+
+        /// If set, move to the next slide after this many seconds, stopping
+        /// at the last. The page can pause and resume it.
+        #[serde(default)]
+        advance_seconds: Option<f64>,
+    },
+}
+
+/// Source of the audio for [`ViewerContent::Audio`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioSource {
+    /// An audio file on disk: WAV, FLAC, MP3 or Ogg Vorbis.
+    Path(PathBuf),
+
+    /// Decoded samples held in memory.
+    Samples {
+        /// Interleaved samples, nominally in `-1.0..=1.0`.
+        #[serde(with = "base64_bytes::samples")]
+        samples: Vec<f32>,
+
+        /// Sample rate in Hz.
+        sample_rate: u32,
+
+        /// Number of interleaved channels.
+        #[serde(default = "default_channels")]
+        channels: u16,
+    },
+}
+
+impl AudioSource {
+    /// Single-channel samples at `sample_rate` Hz.
+    pub fn mono(samples: Vec<f32>, sample_rate: u32) -> Self {
+        Self::Samples {
+            samples,
+            sample_rate,
+            channels: 1,
+        }
+    }
+}
+
+impl From<PathBuf> for AudioSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&std::path::Path> for AudioSource {
+    fn from(path: &std::path::Path) -> Self {
+        Self::Path(path.to_path_buf())
+    }
+}
+
+/// A grid of cells for [`ViewerContent::Layout`].
+///
+/// Rows and columns share the window equally. A cell is placed by the row
+/// and column of its top-left corner, and may span several of each.
+///
+/// # Example
+///
+/// ```
+/// use html_view_shared::Layout;
+///
+/// // A wide plot above two smaller ones
+/// let layout = Layout::grid(2, 2)
+///     .cell(0, 0, "<h1>Overview</h1>")
+///     .span(1, 2)
+///     .cell(1, 0, "<p>Left</p>")
+///     .cell(1, 1, "<p>Right</p>");
+/// assert!(layout.check().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Layout {
+    /// Number of rows in the grid.
+    pub rows: usize,
+
+    /// Number of columns in the grid.
+    pub columns: usize,
+
+    /// The cells, in the order they were added.
+    #[serde(default)]
+    pub cells: Vec<LayoutCell>,
+}
+
+/// A cell of a [`Layout`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutCell {
+    /// Row of the cell's top-left corner, from 0.
+    pub row: usize,
+
+    /// Column of the cell's top-left corner, from 0.
+    pub column: usize,
+
+    /// Number of rows the cell covers.
+    #[serde(default = "default_span")]
+    pub row_span: usize,
+
+    /// Number of columns the cell covers.
+    #[serde(default = "default_span")]
+    pub column_span: usize,
+
+    /// What the cell shows.
+    pub content: ViewerContent,
+}
+
+fn default_span() -> usize {
+    1
+}
+
+impl Layout {
+    /// An empty grid of `rows` by `columns` cells.
+    pub fn grid(rows: usize, columns: usize) -> Self {
+        Self {
+            rows,
+            columns,
+            cells: Vec::new(),
+        }
+    }
+
+    /// HTML fragments side by side in one row.
+    pub fn hstack<I, S>(fragments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let fragments: Vec<String> = fragments.into_iter().map(Into::into).collect();
+        let mut layout = Self::grid(1, fragments.len());
+        for (column, html) in fragments.into_iter().enumerate() {
+            layout = layout.cell(0, column, html);
+        }
+        layout
+    }
+
+    /// HTML fragments stacked in one column.
+    pub fn vstack<I, S>(fragments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let fragments: Vec<String> = fragments.into_iter().map(Into::into).collect();
+        let mut layout = Self::grid(fragments.len(), 1);
+        for (row, html) in fragments.into_iter().enumerate() {
+            layout = layout.cell(row, 0, html);
+        }
+        layout
+    }
+
+    /// Show an HTML fragment in the cell at `row` and `column`.
+    pub fn cell<S: Into<String>>(self, row: usize, column: usize, html: S) -> Self {
+        self.cell_content(
+            row,
+            column,
+            ViewerContent::InlineHtml {
+                html: html.into(),
+                base_dir: None,
+            },
+        )
+    }
+
+    /// Show any content in the cell at `row` and `column`, replacing a cell
+    /// already placed there.
+    pub fn cell_content(mut self, row: usize, column: usize, content: ViewerContent) -> Self {
+        self.cells
+            .retain(|cell| (cell.row, cell.column) != (row, column));
+        self.cells.push(LayoutCell {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+            content,
+        });
+        self
+    }
+
+    /// Make the cell added last cover `rows` rows and `columns` columns.
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        if let Some(cell) = self.cells.last_mut() {
+            cell.row_span = rows;
+            cell.column_span = columns;
+        }
+        self
+    }
+
+    /// Index of the cell whose top-left corner is at `row` and `column`.
+    pub fn position(&self, row: usize, column: usize) -> Option<usize> {
+        self.cells
+            .iter()
+            .position(|cell| (cell.row, cell.column) == (row, column))
+    }
+
+    /// Check that every cell lies inside the grid and no two cells overlap.
+    pub fn check(&self) -> Result<(), String> {
+        if self.rows == 0 || self.columns == 0 {
+            return Err("A layout needs at least one row and one column".to_string());
+        }
+        let mut taken = vec![false; self.rows * self.columns];
+        for cell in &self.cells {
+            if cell.row_span == 0 || cell.column_span == 0 {
+                return Err(format!(
+                    "The cell at row {}, column {} spans no rows or columns",
+                    cell.row, cell.column
+                ));
+            }
+            let bottom = cell.row.saturating_add(cell.row_span);
+            let right = cell.column.saturating_add(cell.column_span);
+            if bottom > self.rows || right > self.columns {
+                return Err(format!(
+                    "The cell at row {}, column {} does not fit in the {}x{} grid",
+                    cell.row, cell.column, self.rows, self.columns
+                ));
+            }
+            for row in cell.row..bottom {
+                for column in cell.column..right {
+                    let slot = &mut taken[row * self.columns + column];
+                    if *slot {
+                        return Err(format!(
+                            "The cell at row {}, column {} overlaps another cell",
+                            cell.row, cell.column
+                        ));
+                    }
+                    *slot = true;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A time range selected in a [`ViewerContent::Audio`] view.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct AudioSelection {
+    /// Start of the selection in seconds.
+    pub start: f64,
+
+    /// End of the selection in seconds.
+    pub end: f64,
+}
+
+/// One entry appended to a [`ViewerContent::Log`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogEntry {
+    /// Plain text. Embedded newlines start new lines.
+    Text(String),
+
+    /// An HTML fragment shown as a single entry.
+    Html(String),
+}
+
+/// Layout of a [`ViewerContent::Diff`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffMode {
+    /// A single column with removed lines followed by added lines.
+    #[default]
+    Unified,
+
+    /// Old and new text in two aligned columns.
+    SideBySide,
+}
+
+/// Languages accepted by [`ViewerContent::Diagram`], each drawn by the
+/// viewer's own parser and layout.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagramKind {
+    /// Graphviz DOT: `graph` and `digraph`, with node and edge defaults,
+    /// shapes, records, HTML-like labels, colours and `rankdir`.
+    ///
+    /// Subgraphs and clusters are flattened into the graph, so clusters are
+    /// not drawn as boxes. Ports and layout hints such as `rank`, `pos` or
+    /// `splines` are ignored, and the layout is a layered one of the viewer's
+    /// own, not Graphviz's.
+    #[default]
+    Dot,
+
+    /// Mermaid flowcharts (`graph`/`flowchart`) and state diagrams
+    /// (`stateDiagram`/`stateDiagram-v2`).
+    ///
+    /// Flowcharts support node shapes, chained and `&` grouped links with
+    /// labels, and `style` statements; state diagrams support transitions,
+    /// `[*]`, aliases, descriptions and choice and fork states. Subgraphs and
+    /// composite states are flattened, and `class`, `classDef`, `click` and
+    /// other interaction statements are ignored. Other diagram types, such
+    /// as sequence, class, Gantt and ER diagrams, are not supported.
+    Mermaid,
+}
+
+/// Template engines available for [`ViewerContent::Template`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateEngine {
+    /// [MiniJinja](https://docs.rs/minijinja), a Jinja2 implementation.
+    /// HTML auto-escaping is enabled.
+    #[default]
+    Minijinja,
+}
+
+/// Source of the data for [`ViewerContent::Image`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageSource {
+    /// An image file on disk.
+    Path(PathBuf),
+
+    /// Encoded image data (PNG, JPEG, ...) held in memory.
+    Bytes(#[serde(with = "base64_bytes")] Vec<u8>),
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&std::path::Path> for ImageSource {
+    fn from(path: &std::path::Path) -> Self {
+        Self::Path(path.to_path_buf())
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+/// Source of the rows for [`ViewerContent::Table`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TableSource {
+    /// Rows held in memory, one value per column.
+    Rows {
+        /// Column names.
+        columns: Vec<String>,
+
+        /// Row values, in the same order as `columns`.
+        rows: Vec<Vec<serde_json::Value>>,
+    },
+
+    /// A CSV or TSV file whose first record is the header.
+    File {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// Field delimiter. Defaults to a tab for `.tsv` and `.tab` files
+        /// and a comma otherwise.
+        #[serde(default)]
+        delimiter: Option<char>,
+    },
 }
 
 /// Window configuration options.
@@ -85,10 +652,14 @@ pub struct WindowOptions {
     /// Window title. If None, a default title is used.
     pub title: Option<String>,
 
-    /// Window width in logical pixels.
+    /// Window width in logical pixels. If None, the width restored via
+    /// `state_key`, then `width_percent`, then [`DEFAULT_WINDOW_SIZE`] is
+    /// used.
     pub width: Option<u32>,
 
-    /// Window height in logical pixels.
+    /// Window height in logical pixels. If None, the height restored via
+    /// `state_key`, then `height_percent`, then [`DEFAULT_WINDOW_SIZE`] is
+    /// used.
     pub height: Option<u32>,
 
     /// Initial X position.
@@ -127,14 +698,87 @@ pub struct WindowOptions {
 
     /// Toolbar configuration.
     pub toolbar: ToolbarOptions,
+
+    /// Key under which the window's size, position, maximised state and zoom
+    /// are saved on close and restored on the next launch.
+    ///
+    /// Explicitly set `x`/`y`/`width`/`height` values take priority over the
+    /// restored ones.
+    #[serde(default)]
+    pub state_key: Option<String>,
+
+    /// Centre the window on its monitor or over a parent window.
+    ///
+    /// Ignored when `x`/`y` are set or a position is restored via `state_key`.
+    #[serde(default)]
+    pub center: Option<WindowCenter>,
+
+    /// Monitor to place the window on. If no position is given, the window
+    /// is centred on it.
+    #[serde(default)]
+    pub monitor: Option<MonitorSelector>,
+
+    /// Width as a percentage of the monitor's work area, greater than 0 and
+    /// at most 100.
+    ///
+    /// Only used when no width is set or restored via `state_key`.
+    #[serde(default)]
+    pub width_percent: Option<f64>,
+
+    /// Height as a percentage of the monitor's work area, greater than 0 and
+    /// at most 100.
+    ///
+    /// Only used when no height is set or restored via `state_key`.
+    #[serde(default)]
+    pub height_percent: Option<f64>,
+
+    /// Minimum window width in logical pixels.
+    #[serde(default)]
+    pub min_width: Option<u32>,
+
+    /// Minimum window height in logical pixels.
+    #[serde(default)]
+    pub min_height: Option<u32>,
+
+    /// Maximum window width in logical pixels.
+    #[serde(default)]
+    pub max_width: Option<u32>,
+
+    /// Maximum window height in logical pixels.
+    #[serde(default)]
+    pub max_height: Option<u32>,
+}
+
+/// Window size in logical pixels used for a dimension that is neither set
+/// nor restored.
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (1024, 768);
+
+impl WindowOptions {
+    /// Check that the size percentages lie in the range `0 < p <= 100`.
+    pub fn check(&self) -> Result<(), String> {
+        for (name, percent) in [
+            ("width_percent", self.width_percent),
+            ("height_percent", self.height_percent),
+        ] {
+            if let Some(percent) = percent
+                && !(percent > 0.0 && percent <= 100.0)
+            {
+                return Err(format!(
+                    "{} must be greater than 0 and at most 100, got {}",
+                    name, percent
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for WindowOptions {
     fn default() -> Self {
         Self {
             title: Some("HTML Viewer".to_string()),
-            width: Some(1024),
-            height: Some(768),
+            width: None,
+            height: None,
             x: None,
             y: None,
             resizable: true,
@@ -148,10 +792,49 @@ impl Default for WindowOptions {
             theme_enum: None,
             background_color: None,
             toolbar: ToolbarOptions::default(),
+            state_key: None,
+            center: None,
+            monitor: None,
+            width_percent: None,
+            height_percent: None,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
         }
     }
 }
 
+/// How to centre the window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WindowCenter {
+    /// Centre on the work area of the window's monitor.
+    Monitor,
+
+    /// Centre over a parent window, given in logical screen coordinates.
+    Parent {
+        /// Parent X position.
+        x: i32,
+        /// Parent Y position.
+        y: i32,
+        /// Parent width.
+        width: u32,
+        /// Parent height.
+        height: u32,
+    },
+}
+
+/// Selects a monitor by index or name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorSelector {
+    /// Index into the list of available monitors.
+    Index(usize),
+    /// Monitor name as reported by the OS (e.g. "DP-1").
+    Name(String),
+}
+
 /// Toolbar configuration options.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ToolbarOptions {
@@ -200,11 +883,14 @@ pub enum WindowTheme {
 /// Behaviour and security configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BehaviourOptions {
-    /// Whether navigation away from the initial content is allowed.
+    /// Whether navigation away from the content's own origin is allowed.
     pub allow_external_navigation: bool,
 
-    /// Optional allowlist of hostnames that can be navigated to.
-    /// Only applies if allow_external_navigation is true.
+    /// Optional allowlist of hostnames that remote (`http`/`https`) pages
+    /// can be navigated to, including their subdomains.
+    ///
+    /// Applies whether or not `allow_external_navigation` is set: without
+    /// it, a remote target must also have the content's own origin.
     pub allowed_domains: Option<Vec<String>>,
 
     /// Whether devtools are enabled.
@@ -215,12 +901,104 @@ pub struct BehaviourOptions {
 
     /// Whether system notifications are allowed.
     pub allow_notifications: bool,
+
+    /// Whether page `console` output is forwarded as [`ViewerEvent::Console`].
+    #[serde(default)]
+    pub capture_console: bool,
+
+    /// Whether an uncaught page error makes the viewer exit with
+    /// [`ViewerExitReason::Error`] instead of its normal exit reason.
+    #[serde(default)]
+    pub fail_on_page_error: bool,
+
+    /// Number of contents to keep when the content is refreshed, shown on a
+    /// timeline that can be scrubbed back through and with one version
+    /// pinned beside another to compare them. Contents that cannot be shown
+    /// in a frame, such as tabs or a log, are shown without history.
+    #[serde(default)]
+    pub refresh_history: Option<usize>,
+}
+
+impl BehaviourOptions {
+    /// Check whether a programmatic navigation to `url` is permitted.
+    ///
+    /// `home` is the URL of the content being shown, if it has one. Unless
+    /// `allow_external_navigation` is set, only pages of the built-in
+    /// viewers and pages of the same origin as `home` may be opened; a file
+    /// URL counts as the same origin when it is in the directory of `home`.
+    ///
+    /// Remote (`http`/`https`) targets also require `allow_remote_content`
+    /// and, when `allowed_domains` is set, a host that matches one of the
+    /// listed domains or one of their subdomains. Local `file`, `data` and
+    /// `about` URLs are allowed; any other scheme is rejected.
+    pub fn check_navigation(&self, url: &Url, home: Option<&Url>) -> Result<(), String> {
+        if is_bundle_url(url) {
+            return Ok(());
+        }
+        if !self.allow_external_navigation && !home.is_some_and(|home| same_origin(home, url)) {
+            return Err(format!(
+                "Navigation away from the content is not allowed: {}",
+                url
+            ));
+        }
+
+        match url.scheme() {
+            "file" | "data" | "about" => Ok(()),
+            "http" | "https" => {
+                if !self.allow_remote_content {
+                    return Err(format!("Remote content is not allowed: {}", url));
+                }
+
+                if let Some(domains) = &self.allowed_domains {
+                    let host = url.host_str().unwrap_or_default();
+                    let allowed = domains.iter().any(|domain| {
+                        host == domain
+                            || host
+                                .strip_suffix(domain.as_str())
+                                .is_some_and(|prefix| prefix.ends_with('.'))
+                    });
+                    if !allowed {
+                        return Err(format!("Domain {} is not in the allowed domains", host));
+                    }
+                }
+
+                Ok(())
+            }
+            scheme => Err(format!("Navigation to {} URLs is not allowed", scheme)),
+        }
+    }
+}
+
+/// Whether `url` is a page of the viewer's `bundle` scheme, which serves the
+/// built-in viewers. Platforms that do not support custom schemes in the
+/// webview reach it as `http://bundle.localhost`.
+pub fn is_bundle_url(url: &Url) -> bool {
+    match url.scheme() {
+        "bundle" => true,
+        "http" | "https" => url.host_str() == Some("bundle.localhost"),
+        _ => false,
+    }
+}
+
+/// Whether `url` has the same origin as `home`, taking a file URL to be of
+/// the same origin as the files in its directory.
+fn same_origin(home: &Url, url: &Url) -> bool {
+    if home.scheme() == "file" {
+        let dir = &home.path()[..home.path().rfind('/').map_or(0, |i| i + 1)];
+        return url.scheme() == "file" && url.path().starts_with(dir);
+    }
+    // Opaque origins, such as those of data URLs, never compare equal
+    home.origin() == url.origin()
 }
 
 /// Dialog configuration options.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DialogOptions {
     /// Whether file dialogs are allowed.
+    ///
+    /// The save dialog of a table or diagram view is always allowed
+    /// when the view is the window's own page, but needs this inside tabs,
+    /// layouts, slides or a refresh history.
     pub allow_file_dialogs: bool,
 
     /// Whether message dialogs are allowed.
@@ -250,6 +1028,101 @@ pub struct ViewerExitStatus {
     /// This is used to check compatibility with the library.
     #[serde(default = "default_version")]
     pub viewer_version: String,
+
+    /// The window geometry when the viewer closed, if it could be captured.
+    #[serde(default)]
+    pub geometry: Option<WindowGeometry>,
+
+    /// The time range selected in a [`ViewerContent::Audio`] view when the
+    /// viewer closed.
+    #[serde(default)]
+    pub audio_selection: Option<AudioSelection>,
+
+    /// Index of the slide shown by a [`ViewerContent::Slides`] view when the
+    /// viewer closed, counting from 0.
+    #[serde(default)]
+    pub slide: Option<usize>,
+}
+
+/// Position, size and display state of the viewer window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowGeometry {
+    /// Outer X position in logical pixels.
+    pub x: i32,
+
+    /// Outer Y position in logical pixels.
+    pub y: i32,
+
+    /// Inner width in logical pixels.
+    pub width: u32,
+
+    /// Inner height in logical pixels.
+    pub height: u32,
+
+    /// Outer X position in physical pixels.
+    pub physical_x: i32,
+
+    /// Outer Y position in physical pixels.
+    pub physical_y: i32,
+
+    /// Inner width in physical pixels.
+    pub physical_width: u32,
+
+    /// Inner height in physical pixels.
+    pub physical_height: u32,
+
+    /// Ratio of physical to logical pixels on the window's monitor.
+    pub scale_factor: f64,
+
+    /// Name of the monitor the window is on, if known.
+    pub monitor: Option<String>,
+
+    /// Whether the window is maximised.
+    pub maximised: bool,
+
+    /// Whether the window is in fullscreen mode.
+    pub fullscreen: bool,
+}
+
+/// A snapshot of the viewer window and the page it displays.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ViewerState {
+    /// Current window geometry.
+    pub geometry: WindowGeometry,
+
+    /// Whether the window has input focus.
+    pub focused: bool,
+
+    /// Whether the window is visible.
+    pub visible: bool,
+
+    /// The document title.
+    pub title: String,
+
+    /// The URL currently displayed.
+    pub url: Url,
+
+    /// Load state of the current page.
+    pub load_state: LoadState,
+}
+
+/// Load state of the page displayed in the viewer.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadState {
+    /// The page is still loading.
+    #[default]
+    Loading,
+    /// The page has finished loading.
+    Loaded,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_channels() -> u16 {
+    1
 }
 
 /// Default version for backward compatibility with old viewers that don't report version.
@@ -274,6 +1147,108 @@ pub enum ViewerExitReason {
     },
 }
 
+/// Events reported by a running viewer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ViewerEvent {
+    /// The window has been configured and shown.
+    WindowShown,
+
+    /// The page's DOM has been parsed.
+    DomContentLoaded {
+        /// URL of the page.
+        url: Url,
+    },
+
+    /// The page and its resources have finished loading.
+    Loaded {
+        /// URL of the page.
+        url: Url,
+    },
+
+    /// The content could not be loaded.
+    LoadFailed {
+        /// URL of the page, if one was resolved.
+        url: Option<Url>,
+        /// Error message.
+        error: String,
+    },
+
+    /// The page wrote to the console. Only reported if
+    /// `BehaviourOptions::capture_console` is enabled.
+    Console {
+        /// Console method that was called.
+        level: ConsoleLevel,
+        /// The formatted arguments.
+        message: String,
+    },
+
+    /// An uncaught exception or unhandled promise rejection in the page.
+    PageError {
+        /// Whether this was an exception or a promise rejection.
+        kind: PageErrorKind,
+        /// Error message.
+        message: String,
+        /// Script URL the error originated from, if known.
+        source: Option<String>,
+        /// Line number, if known.
+        line: Option<u32>,
+        /// Column number, if known.
+        column: Option<u32>,
+        /// JavaScript stack trace, if available.
+        stack: Option<String>,
+    },
+
+    /// The selection in a [`ViewerContent::Audio`] view changed.
+    SelectionChanged {
+        /// The selected range, or `None` if the selection was cleared.
+        selection: Option<AudioSelection>,
+    },
+
+    /// A [`ViewerContent::Slides`] view moved to another slide.
+    SlideChanged {
+        /// Index of the slide now shown, counting from 0.
+        index: usize,
+    },
+
+    /// Delivery of streamed frames over the last second, reported about once
+    /// a second while frames arrive.
+    StreamStats {
+        /// Frames applied to the page since the first frame it received.
+        applied: u64,
+        /// Frames sent but never applied because a newer frame replaced them.
+        dropped: u64,
+        /// Frames applied per second over the last report interval.
+        fps: f64,
+    },
+}
+
+/// Console method used by the page.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsoleLevel {
+    /// `console.debug`
+    Debug,
+    /// `console.log`
+    Log,
+    /// `console.info`
+    Info,
+    /// `console.warn`
+    Warn,
+    /// `console.error`
+    Error,
+}
+
+/// Kind of uncaught page error.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PageErrorKind {
+    /// An uncaught exception (`window.onerror`).
+    Exception,
+    /// A promise rejection with no handler.
+    UnhandledRejection,
+}
+
 /// Commands that can be sent to a running viewer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -285,6 +1260,149 @@ pub enum ViewerCommand {
         /// New content to display.
         content: ViewerContent,
     },
+
+    /// Navigate to a new URL, subject to the request's behaviour options.
+    Navigate {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// The URL to navigate to.
+        url: Url,
+    },
+
+    /// Go back one entry in the navigation history.
+    Back {
+        /// Sequence number for command ordering.
+        seq: u64,
+    },
+
+    /// Go forward one entry in the navigation history.
+    Forward {
+        /// Sequence number for command ordering.
+        seq: u64,
+    },
+
+    /// Reload the current page.
+    Reload {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Whether to clear cached browsing data before reloading.
+        #[serde(default)]
+        ignore_cache: bool,
+    },
+
+    /// Query the URL currently displayed.
+    CurrentUrl {
+        /// Sequence number for command ordering.
+        seq: u64,
+    },
+
+    /// Query the window geometry and page state.
+    QueryState {
+        /// Sequence number for command ordering.
+        seq: u64,
+    },
+
+    /// Re-render the displayed [`ViewerContent::Template`] with new data.
+    UpdateData {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// The new template data.
+        data: serde_json::Value,
+    },
+
+    /// Append entries to the [`ViewerContent::Log`] being shown.
+    AppendLog {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Entries to append, oldest first.
+        entries: Vec<LogEntry>,
+    },
+
+    /// Add a tab to the [`ViewerContent::Tabs`] being shown and select it.
+    AddTab {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Id of the new tab, which must not be in use.
+        id: String,
+        /// Content of the new tab.
+        content: ViewerContent,
+    },
+
+    /// Close a tab.
+    CloseTab {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Id of the tab.
+        id: String,
+    },
+
+    /// Select a tab.
+    SelectTab {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Id of the tab.
+        id: String,
+    },
+
+    /// Replace the content of a tab, leaving the others untouched.
+    RefreshTab {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Id of the tab.
+        id: String,
+        /// New content of the tab.
+        content: ViewerContent,
+    },
+
+    /// Replace the content of one cell of the [`ViewerContent::Layout`]
+    /// being shown, leaving the others untouched.
+    RefreshCell {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Row of the cell's top-left corner.
+        row: usize,
+        /// Column of the cell's top-left corner.
+        column: usize,
+        /// New content of the cell.
+        content: ViewerContent,
+    },
+
+    /// Deliver bytes to the page's `htmlView.onBinary` callbacks for a
+    /// channel, as an `ArrayBuffer`.
+    ///
+    /// The bytes are passed in a file rather than in the command, so they are
+    /// never encoded as text. The viewer removes the file once it has read it.
+    SendBinary {
+        /// Sequence number for command ordering.
+        seq: u64,
+        /// Channel the page's callbacks are registered for.
+        channel: String,
+        /// Path to the file holding the bytes.
+        path: PathBuf,
+    },
+}
+
+impl ViewerCommand {
+    /// The sequence number of this command.
+    pub fn seq(&self) -> u64 {
+        match self {
+            ViewerCommand::Refresh { seq, .. }
+            | ViewerCommand::Navigate { seq, .. }
+            | ViewerCommand::Back { seq }
+            | ViewerCommand::Forward { seq }
+            | ViewerCommand::Reload { seq, .. }
+            | ViewerCommand::CurrentUrl { seq }
+            | ViewerCommand::QueryState { seq }
+            | ViewerCommand::UpdateData { seq, .. }
+            | ViewerCommand::AppendLog { seq, .. }
+            | ViewerCommand::AddTab { seq, .. }
+            | ViewerCommand::CloseTab { seq, .. }
+            | ViewerCommand::SelectTab { seq, .. }
+            | ViewerCommand::RefreshTab { seq, .. }
+            | ViewerCommand::RefreshCell { seq, .. }
+            | ViewerCommand::SendBinary { seq, .. } => *seq,
+        }
+    }
 }
 
 /// Response to a viewer command.
@@ -296,4 +1414,53 @@ pub struct ViewerCommandResponse {
     pub success: bool,
     /// Error message if unsuccessful.
     pub error: Option<String>,
+    /// Data returned by query commands.
+    #[serde(default)]
+    pub data: Option<ViewerCommandData>,
+}
+
+/// Data returned in response to a query command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ViewerCommandData {
+    /// The URL currently displayed in the viewer.
+    CurrentUrl {
+        /// The current URL.
+        url: Url,
+    },
+
+    /// The current window and page state.
+    State {
+        /// The state snapshot.
+        state: ViewerState,
+    },
+}
+
+/// A frame streamed to a running viewer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamFrame {
+    /// HTML replacing the content of the page's element marked with a
+    /// `data-html-view-stream` attribute, or of the body if there is none.
+    Html {
+        /// The HTML to show.
+        html: String,
+    },
+
+    /// JSON data passed to the callbacks the page registered with
+    /// `htmlView.onFrame`.
+    Data {
+        /// The data of the frame.
+        data: serde_json::Value,
+    },
+}
+
+/// The latest streamed frame, as stored in the request's stream file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamMessage {
+    /// Number of the frame, counting every frame sent from 0, so that gaps
+    /// are frames replaced before they were shown.
+    pub seq: u64,
+    /// The frame itself.
+    pub frame: StreamFrame,
 }
@@ -0,0 +1,419 @@
+//! Terminal output with ANSI SGR colours and styles.
+
+use super::{escape_html, page};
+use crate::WindowTheme;
+use std::fmt::Write;
+
+const ANSI_STYLE: &str = r#"
+:root {
+    --ansi-0: #24292f; --ansi-1: #cf222e; --ansi-2: #116329; --ansi-3: #4d2d00;
+    --ansi-4: #0969da; --ansi-5: #8250df; --ansi-6: #1b7c83; --ansi-7: #6e7781;
+    --ansi-8: #57606a; --ansi-9: #a40e26; --ansi-10: #1a7f37; --ansi-11: #633c01;
+    --ansi-12: #218bff; --ansi-13: #a475f9; --ansi-14: #3192aa; --ansi-15: #8c959f;
+}
+:root[data-theme="dark"] {
+    --ansi-0: #484f58; --ansi-1: #ff7b72; --ansi-2: #3fb950; --ansi-3: #d29922;
+    --ansi-4: #58a6ff; --ansi-5: #bc8cff; --ansi-6: #39c5cf; --ansi-7: #b1bac4;
+    --ansi-8: #6e7681; --ansi-9: #ffa198; --ansi-10: #56d364; --ansi-11: #e3b341;
+    --ansi-12: #79c0ff; --ansi-13: #d2a8ff; --ansi-14: #56d4dd; --ansi-15: #ffffff;
+}
+@media (prefers-color-scheme: dark) {
+    :root[data-theme="system"] {
+        --ansi-0: #484f58; --ansi-1: #ff7b72; --ansi-2: #3fb950; --ansi-3: #d29922;
+        --ansi-4: #58a6ff; --ansi-5: #bc8cff; --ansi-6: #39c5cf; --ansi-7: #b1bac4;
+        --ansi-8: #6e7681; --ansi-9: #ffa198; --ansi-10: #56d364; --ansi-11: #e3b341;
+        --ansi-12: #79c0ff; --ansi-13: #d2a8ff; --ansi-14: #56d4dd; --ansi-15: #ffffff;
+    }
+}
+html, body { height: 100%; overflow: hidden; }
+#bar {
+    position: fixed; left: 0; right: 0; top: 0; height: 36px; box-sizing: border-box;
+    display: flex; align-items: center; gap: 8px; padding: 0 12px; font-size: 12px;
+    border-bottom: 1px solid var(--border);
+}
+#search {
+    width: 260px; font: inherit; color: var(--fg); background: var(--bg);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 6px;
+}
+#bar button, #bar label { font: inherit; color: var(--fg); }
+#bar button {
+    background: var(--surface); border: 1px solid var(--border); border-radius: 4px;
+    padding: 3px 10px; cursor: pointer;
+}
+#count { color: var(--muted); margin-right: auto; font-variant-numeric: tabular-nums; }
+#output {
+    position: fixed; left: 0; right: 0; top: 36px; bottom: 0; overflow: auto; margin: 0;
+    padding: 8px 12px; box-sizing: border-box; line-height: 18px; tab-size: 8;
+    font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px;
+}
+#output.wrap .line { white-space: pre-wrap; overflow-wrap: anywhere; }
+.line { white-space: pre; min-height: 18px; }
+.line.match { background: rgba(212, 167, 44, 0.18); }
+.line.current { background: rgba(212, 167, 44, 0.4); }
+::highlight(search) { background: rgba(212, 167, 44, 0.6); }
+"#;
+
+const ANSI_SCRIPT: &str = r#"
+(function () {
+    var output = document.getElementById('output');
+    var input = document.getElementById('search');
+    var counter = document.getElementById('count');
+    var lines = output.children;
+    var matches = [];
+    var current = -1;
+
+    function clear() {
+        matches.forEach(function (index) { lines[index].classList.remove('match', 'current'); });
+        matches = [];
+        current = -1;
+        if (window.CSS && CSS.highlights) CSS.highlights.delete('search');
+    }
+
+    // Marks the text of each match where the Custom Highlight API exists
+    function highlightRanges(needle) {
+        if (!(window.CSS && CSS.highlights && window.Highlight)) return;
+        var ranges = [];
+        matches.forEach(function (index) {
+            var walker = document.createTreeWalker(lines[index], NodeFilter.SHOW_TEXT);
+            var nodes = [], text = '';
+            for (var node = walker.nextNode(); node; node = walker.nextNode()) {
+                nodes.push([node, text.length]);
+                text += node.data;
+            }
+            var lower = text.toLowerCase();
+            for (var at = lower.indexOf(needle); at !== -1; at = lower.indexOf(needle, at + needle.length)) {
+                var range = document.createRange();
+                setBoundary(range, nodes, at, true);
+                setBoundary(range, nodes, at + needle.length, false);
+                ranges.push(range);
+            }
+        });
+        CSS.highlights.set('search', new Highlight(...ranges));
+    }
+
+    function setBoundary(range, nodes, offset, start) {
+        for (var i = nodes.length - 1; i >= 0; i--) {
+            if (nodes[i][1] <= offset) {
+                var local = offset - nodes[i][1];
+                if (start) range.setStart(nodes[i][0], local);
+                else range.setEnd(nodes[i][0], local);
+                return;
+            }
+        }
+    }
+
+    function search() {
+        clear();
+        var needle = input.value.toLowerCase();
+        if (needle) {
+            for (var i = 0; i < lines.length; i++) {
+                if (lines[i].textContent.toLowerCase().indexOf(needle) !== -1) {
+                    matches.push(i);
+                    lines[i].classList.add('match');
+                }
+            }
+            highlightRanges(needle);
+        }
+        if (matches.length) move(1);
+        else counter.textContent = needle ? 'No matches' : '';
+    }
+
+    function move(step) {
+        if (!matches.length) return;
+        if (current >= 0) lines[matches[current]].classList.remove('current');
+        current = (current + step + matches.length) % matches.length;
+        var line = lines[matches[current]];
+        line.classList.add('current');
+        line.scrollIntoView({ block: 'center' });
+        counter.textContent = (current + 1) + ' of ' + matches.length + ' lines';
+    }
+
+    var pending = null;
+    input.addEventListener('input', function () {
+        clearTimeout(pending);
+        pending = setTimeout(search, 200);
+    });
+    input.addEventListener('keydown', function (event) {
+        if (event.key === 'Enter') move(event.shiftKey ? -1 : 1);
+        if (event.key === 'Escape') { input.value = ''; search(); }
+    });
+    document.getElementById('next').addEventListener('click', function () { move(1); });
+    document.getElementById('prev').addEventListener('click', function () { move(-1); });
+    document.getElementById('wrap').addEventListener('change', function (event) {
+        output.classList.toggle('wrap', event.target.checked);
+    });
+    document.addEventListener('keydown', function (event) {
+        if ((event.ctrlKey || event.metaKey) && event.key === 'f') {
+            event.preventDefault();
+            input.focus();
+            input.select();
+        }
+    });
+})();
+"#;
+
+/// A terminal colour: one of the 256 palette entries or a 24-bit value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn css(self) -> String {
+        match self {
+            Self::Indexed(index @ 0..=15) => format!("var(--ansi-{index})"),
+            Self::Indexed(index @ 16..=231) => {
+                const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+                let index = index - 16;
+                let (r, g, b) = (index / 36, index / 6 % 6, index % 6);
+                Self::Rgb(LEVELS[r as usize], LEVELS[g as usize], LEVELS[b as usize]).css()
+            }
+            Self::Indexed(index) => {
+                let level = 8 + 10 * (index - 232);
+                Self::Rgb(level, level, level).css()
+            }
+            Self::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        }
+    }
+}
+
+/// Parse one SGR parameter; an empty or invalid one counts as 0.
+fn parse_param(param: &str) -> u16 {
+    param.parse().unwrap_or(0)
+}
+
+/// Read the colour of a `38`/`48` parameter from its arguments: `5` and a
+/// palette index, or `2` and the red, green and blue values.
+fn color(mut args: impl Iterator<Item = u16>) -> Option<Color> {
+    match args.next() {
+        Some(5) => args
+            .next()
+            .map(|index| Color::Indexed(index.min(255) as u8)),
+        Some(2) => {
+            let mut channel = || args.next().unwrap_or(0).min(255) as u8;
+            Some(Color::Rgb(channel(), channel(), channel()))
+        }
+        _ => None,
+    }
+}
+
+/// Graphic rendition state set by SGR sequences.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    blink: bool,
+    inverse: bool,
+    hidden: bool,
+    strike: bool,
+}
+
+impl Style {
+    /// Apply the parameters of one SGR (`ESC [ ... m`) sequence.
+    ///
+    /// Parameters are separated by `;`, and a parameter may carry its own
+    /// arguments separated by `:`, as in `4:3` or `38:2::r:g:b`.
+    fn apply(&mut self, params: &str) {
+        let mut groups = params.split(';');
+        while let Some(group) = groups.next() {
+            let mut fields = group.split(':').map(parse_param);
+            let param = fields.next().unwrap_or(0);
+            match param {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                // `4:0` turns underlining off; curly and other `4:n` styles show as a
+                // plain underline
+                4 | 21 => self.underline = fields.next() != Some(0),
+                5 | 6 => self.blink = true,
+                7 => self.inverse = true,
+                8 => self.hidden = true,
+                9 => self.strike = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                25 => self.blink = false,
+                27 => self.inverse = false,
+                28 => self.hidden = false,
+                29 => self.strike = false,
+                30..=37 => self.fg = Some(Color::Indexed(param as u8 - 30)),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(Color::Indexed(param as u8 - 40)),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(Color::Indexed(param as u8 - 90 + 8)),
+                100..=107 => self.bg = Some(Color::Indexed(param as u8 - 100 + 8)),
+                38 | 48 => {
+                    let color = if group.contains(':') {
+                        let mut args: Vec<u16> = fields.collect();
+                        // `38:2:cs:r:g:b` names a colour space, usually
+                        // left empty, which `38:2:r:g:b` omits
+                        if args.first() == Some(&2) && args.len() > 4 {
+                            args.remove(1);
+                        }
+                        color(args.into_iter())
+                    } else {
+                        color(groups.by_ref().map(parse_param))
+                    };
+                    if param == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn css(&self) -> String {
+        let (mut fg, mut bg) = (self.fg.map(Color::css), self.bg.map(Color::css));
+        if self.inverse {
+            (fg, bg) = (
+                Some(bg.unwrap_or_else(|| "var(--bg)".to_string())),
+                Some(fg.unwrap_or_else(|| "var(--fg)".to_string())),
+            );
+        }
+
+        let mut css = String::new();
+        if let Some(fg) = fg {
+            let _ = write!(css, "color:{fg};");
+        }
+        if let Some(bg) = bg {
+            let _ = write!(css, "background:{bg};");
+        }
+        if self.bold {
+            css.push_str("font-weight:bold;");
+        }
+        if self.dim {
+            css.push_str("opacity:0.6;");
+        }
+        if self.italic {
+            css.push_str("font-style:italic;");
+        }
+        match (self.underline, self.strike) {
+            (true, true) => css.push_str("text-decoration:underline line-through;"),
+            (true, false) => css.push_str("text-decoration:underline;"),
+            (false, true) => css.push_str("text-decoration:line-through;"),
+            (false, false) => {}
+        }
+        if self.hidden {
+            css.push_str("visibility:hidden;");
+        }
+        css
+    }
+}
+
+/// Convert terminal output to HTML, one `<div class="line">` per line.
+///
+/// SGR sequences (16, 256 and 24-bit colours, bold, dim, italic, underline,
+/// inverse, strikethrough) become styled spans; the 16 basic colours use the
+/// `--ansi-0` to `--ansi-15` CSS variables. Other escape sequences are
+/// dropped, and a carriage return within a line keeps only the text after
+/// it, as a progress bar would leave the terminal.
+pub fn ansi_to_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len() * 2);
+    let mut style = Style::default();
+
+    let mut line_html = String::new();
+
+    for line in text.lines() {
+        let mut open = false;
+        // A carriage return restarts the line; styles set before it still
+        // apply. A trailing '\r' is already removed by `lines`.
+        for segment in line.split('\r') {
+            line_html.clear();
+            open = false;
+
+            let mut rest = segment;
+            while !rest.is_empty() {
+                let plain = rest.find(['\x1b', '\x08']).unwrap_or(rest.len());
+                if plain > 0 {
+                    if !open && style != Style::default() {
+                        let _ = write!(line_html, r#"<span style="{}">"#, style.css());
+                        open = true;
+                    }
+                    line_html.push_str(&escape_html(&rest[..plain]));
+                    rest = &rest[plain..];
+                    continue;
+                }
+
+                let (sequence, after) = split_escape(rest);
+                rest = after;
+                if let Some(params) = sequence
+                    .strip_prefix("\x1b[")
+                    .and_then(|sequence| sequence.strip_suffix('m'))
+                {
+                    if open {
+                        line_html.push_str("</span>");
+                        open = false;
+                    }
+                    style.apply(params);
+                }
+            }
+        }
+
+        html.push_str(r#"<div class="line">"#);
+        html.push_str(&line_html);
+        if open {
+            html.push_str("</span>");
+        }
+        html.push_str("</div>\n");
+    }
+    html
+}
+
+/// Split the escape sequence (or backspace) at the start of `text` from the
+/// rest of it.
+fn split_escape(text: &str) -> (&str, &str) {
+    let bytes = text.as_bytes();
+    let end = match bytes.get(1) {
+        _ if bytes[0] == b'\x08' => 1,
+        // CSI: parameter and intermediate bytes, then a final byte
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|byte| (0x40..=0x7e).contains(byte))
+            .map_or(bytes.len(), |position| position + 3),
+        // OSC: terminated by BEL or ST (`ESC \`)
+        Some(b']') => {
+            let body = &text[2..];
+            match (body.find('\x07'), body.find("\x1b\\")) {
+                (Some(bel), Some(st)) if st < bel => st + 4,
+                (Some(bel), _) => bel + 3,
+                (None, Some(st)) => st + 4,
+                (None, None) => bytes.len(),
+            }
+        }
+        // Other two-byte escapes, such as `ESC (B`
+        Some(b'(' | b')') => 3.min(bytes.len()),
+        Some(_) => 2,
+        None => 1,
+    };
+    let end = (0..=end)
+        .rev()
+        .find(|&end| text.is_char_boundary(end))
+        .unwrap_or(0);
+    text.split_at(end)
+}
+
+/// Render terminal output as a scrollable monospace page with search.
+pub fn render_ansi(text: &str, theme: &WindowTheme) -> String {
+    let body = format!(
+        r#"<div id="bar">
+<input id="search" type="search" placeholder="Search (Enter for next)">
+<button id="prev" title="Previous match (Shift+Enter)">&uarr;</button>
+<button id="next" title="Next match (Enter)">&darr;</button>
+<span id="count"></span>
+<label><input id="wrap" type="checkbox"> Wrap lines</label>
+</div>
+<div id="output">{}</div>
+<script>{ANSI_SCRIPT}</script>"#,
+        ansi_to_html(text)
+    );
+    page(theme, ANSI_STYLE, &body)
+}
@@ -0,0 +1,200 @@
+//! Reading of zip and tar(.gz) archives into bundle files.
+
+use super::bundle_path;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Limits on what [`read_archive_with_limits`] reads, so that a corrupt or
+/// malicious archive, such as a small one that decompresses to many large
+/// files, cannot exhaust memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// Largest size of one file, in bytes.
+    pub max_entry_size: u64,
+
+    /// Largest size of all files together, in bytes.
+    pub max_total_size: u64,
+
+    /// Largest number of entries, counting directories and skipped ones.
+    pub max_entries: usize,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_entry_size: 256 * 1024 * 1024,
+            max_total_size: 1024 * 1024 * 1024,
+            max_entries: 100_000,
+        }
+    }
+}
+
+/// Read every file in the zip, tar or tar.gz archive at `path`, within the
+/// default [`ArchiveLimits`].
+///
+/// The format is detected from the file's contents. Files are keyed by
+/// their [`bundle_path`]; entries that would escape the archive root are
+/// skipped.
+pub fn read_archive(path: &Path) -> io::Result<HashMap<String, Vec<u8>>> {
+    read_archive_with_limits(path, &ArchiveLimits::default())
+}
+
+/// Read every file in the archive at `path` like [`read_archive`], failing
+/// with [`io::ErrorKind::InvalidData`] once it exceeds one of `limits`.
+pub fn read_archive_with_limits(
+    path: &Path,
+    limits: &ArchiveLimits,
+) -> io::Result<HashMap<String, Vec<u8>>> {
+    let mut file = BufReader::new(File::open(path)?);
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    let mut files = Files::new(limits);
+    match &magic[..read] {
+        [b'P', b'K', 3 | 5, 4 | 6] => read_zip(file, &mut files)?,
+        [0x1f, 0x8b, ..] => read_tar(GzDecoder::new(file), &mut files)?,
+        _ => read_tar(file, &mut files)?,
+    }
+    Ok(files.files)
+}
+
+/// Find the entry HTML file among archive `files`.
+///
+/// `entry` defaults to `index.html`. Archives of a single top-level
+/// directory, as produced by zipping a folder, are also searched inside
+/// that directory.
+pub fn archive_entry(files: &HashMap<String, Vec<u8>>, entry: Option<&str>) -> Option<String> {
+    let entry = bundle_path(entry.unwrap_or("index.html"))?;
+    if files.contains_key(&entry) {
+        return Some(entry);
+    }
+
+    let mut roots = files
+        .keys()
+        .map(|path| path.split_once('/').map(|(root, _)| root));
+    let root = roots.next()??;
+    if !roots.all(|other| other == Some(root)) {
+        return None;
+    }
+
+    let nested = format!("{}/{}", root, entry);
+    files.contains_key(&nested).then_some(nested)
+}
+
+fn read_zip<R: Read + Seek>(reader: R, files: &mut Files) -> io::Result<()> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(io::Error::other)?;
+    if archive.len() > files.limits.max_entries {
+        return Err(files.too_many_entries());
+    }
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(io::Error::other)?;
+        files.count_entry()?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = bundle_path(entry.name()) else {
+            continue;
+        };
+
+        let size = entry.size();
+        files.read(&mut entry, path, size)?;
+    }
+
+    Ok(())
+}
+
+fn read_tar<R: Read>(reader: R, files: &mut Files) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        files.count_entry()?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = bundle_path(&entry.path()?.to_string_lossy()) else {
+            continue;
+        };
+
+        let size = entry.size();
+        files.read(&mut entry, path, size)?;
+    }
+
+    Ok(())
+}
+
+/// Files read from an archive so far, and what they count against the
+/// limits.
+struct Files<'a> {
+    limits: &'a ArchiveLimits,
+    files: HashMap<String, Vec<u8>>,
+    entries: usize,
+    total_size: u64,
+}
+
+impl<'a> Files<'a> {
+    fn new(limits: &'a ArchiveLimits) -> Self {
+        Self {
+            limits,
+            files: HashMap::new(),
+            entries: 0,
+            total_size: 0,
+        }
+    }
+
+    fn too_many_entries(&self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Archive has more than {} entries", self.limits.max_entries),
+        )
+    }
+
+    fn count_entry(&mut self) -> io::Result<()> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(self.too_many_entries());
+        }
+        Ok(())
+    }
+
+    /// Read the file at `path`, which the archive declares to be `size`
+    /// bytes long.
+    fn read<R: Read>(&mut self, entry: R, path: String, size: u64) -> io::Result<()> {
+        let remaining = self.limits.max_total_size - self.total_size;
+        let limit = self.limits.max_entry_size.min(remaining);
+        let too_large = || {
+            let message = if limit == self.limits.max_entry_size {
+                format!(
+                    "Archive entry {} is larger than {} bytes",
+                    path, self.limits.max_entry_size
+                )
+            } else {
+                format!(
+                    "Archive files are larger than {} bytes in total",
+                    self.limits.max_total_size
+                )
+            };
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        };
+        if size > limit {
+            return Err(too_large());
+        }
+
+        // The declared size is not trusted, so neither preallocated nor
+        // relied on to stop reading
+        let mut data = Vec::new();
+        entry.take(limit + 1).read_to_end(&mut data)?;
+        if data.len() as u64 > limit {
+            return Err(too_large());
+        }
+
+        self.total_size += data.len() as u64;
+        self.files.insert(path, data);
+        Ok(())
+    }
+}
@@ -0,0 +1,721 @@
+//! Audio decoding and the waveform / spectrogram view.
+//!
+//! The view is served as a bundle: the page, the audio as 16-bit WAV (which
+//! the page both plays and draws) and a precomputed spectrogram.
+
+use super::{page, script_json};
+use crate::WindowTheme;
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// FFT size of the spectrogram; it has half as many frequency bins.
+const FFT_SIZE: usize = 1024;
+
+/// Upper bound on spectrogram columns, whatever the length of the audio.
+const MAX_COLUMNS: usize = 8192;
+
+/// Dynamic range of the spectrogram below its loudest bin.
+const DYNAMIC_RANGE_DB: f32 = 90.0;
+
+/// Decoded audio with interleaved samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedAudio {
+    /// Interleaved samples, nominally in `-1.0..=1.0`.
+    pub samples: Vec<f32>,
+
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+
+    /// Number of interleaved channels.
+    pub channels: u16,
+}
+
+impl DecodedAudio {
+    /// Number of samples per channel.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / usize::from(self.channels.max(1))
+    }
+}
+
+/// A magnitude spectrogram of the channel mixdown, in columns of `bins`
+/// values from the highest frequency to the lowest, scaled to `0..=255`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrogram {
+    /// Number of time columns.
+    pub columns: usize,
+
+    /// Number of frequency bins per column.
+    pub bins: usize,
+
+    /// Frames between the starts of consecutive columns.
+    pub hop: usize,
+
+    /// Column-major magnitudes.
+    pub data: Vec<u8>,
+}
+
+/// Decode the first audio track of a WAV, FLAC, MP3 or Ogg Vorbis file.
+pub fn decode_audio(path: &Path) -> io::Result<DecodedAudio> {
+    let file = File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(invalid_data)?
+        .format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| invalid_data("no audio track"))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track
+        .codec_params
+        .channels
+        .map_or(0, |channels| channels.count());
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(invalid_data)?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(DecodeError::ResetRequired) => break,
+            Err(e) => return Err(invalid_data(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count();
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+            // A corrupt packet only loses its own samples
+            Err(DecodeError::DecodeError(_)) => continue,
+            Err(e) => return Err(invalid_data(e)),
+        }
+    }
+
+    if sample_rate == 0 || channels == 0 {
+        return Err(invalid_data("unknown sample rate or channel count"));
+    }
+    Ok(DecodedAudio {
+        samples,
+        sample_rate,
+        channels: u16::try_from(channels).map_err(invalid_data)?,
+    })
+}
+
+fn invalid_data<E: ToString>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+/// Encode audio as a 16-bit PCM WAV file. Samples are clamped to
+/// `-1.0..=1.0`.
+///
+/// Fails if the sample rate is 0, or if the audio or its byte rate does not
+/// fit the 32-bit sizes of a WAV header, which limit it to about 4 GiB.
+pub fn encode_wav(audio: &DecodedAudio) -> io::Result<Vec<u8>> {
+    let channels = audio.channels.max(1);
+    if audio.sample_rate == 0 {
+        return Err(invalid_data("the sample rate is 0"));
+    }
+    let block_align = channels
+        .checked_mul(2)
+        .ok_or_else(|| invalid_data("too many channels for a WAV file"))?;
+    let byte_rate = audio
+        .sample_rate
+        .checked_mul(u32::from(block_align))
+        .ok_or_else(|| invalid_data("the sample rate is too high for a WAV file"))?;
+    let data_len = u32::try_from(audio.samples.len())
+        .ok()
+        .and_then(|samples| samples.checked_mul(2))
+        .filter(|len| len.checked_add(36).is_some())
+        .ok_or_else(|| invalid_data("the audio is too long for a WAV file"))?;
+
+    let mut wav = Vec::with_capacity(44 + audio.samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&audio.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in &audio.samples {
+        let value = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    Ok(wav)
+}
+
+/// Compute the spectrogram of the mixdown of `audio`.
+///
+/// Columns are Hann-windowed FFTs centred on every `hop` frames, where the
+/// hop grows with the length of the audio so there are at most 8192
+/// columns.
+pub fn spectrogram(audio: &DecodedAudio) -> Spectrogram {
+    let channels = usize::from(audio.channels.max(1));
+    let mono: Vec<f32> = audio
+        .samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+
+    let bins = FFT_SIZE / 2;
+    let hop = (FFT_SIZE / 4).max(mono.len().div_ceil(MAX_COLUMNS));
+    let columns = mono.len().div_ceil(hop);
+    let window: Vec<f32> = (0..FFT_SIZE)
+        .map(|i| {
+            let phase = 2.0 * std::f32::consts::PI * i as f32 / FFT_SIZE as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect();
+
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let mut buffer = vec![Complex::default(); FFT_SIZE];
+    let mut decibels = Vec::with_capacity(columns * bins);
+    for column in 0..columns {
+        let centre = column * hop + hop / 2;
+        for (i, value) in buffer.iter_mut().enumerate() {
+            let sample = (centre + i)
+                .checked_sub(FFT_SIZE / 2)
+                .and_then(|index| mono.get(index))
+                .copied()
+                .unwrap_or(0.0);
+            *value = Complex::new(sample * window[i], 0.0);
+        }
+        fft.process(&mut buffer);
+        decibels.extend(buffer[..bins].iter().rev().map(|bin| {
+            let magnitude = bin.norm() * 4.0 / FFT_SIZE as f32;
+            20.0 * (magnitude + 1e-10).log10()
+        }));
+    }
+
+    let loudest = decibels.iter().copied().fold(f32::MIN, f32::max);
+    let data = decibels
+        .iter()
+        .map(|db| {
+            ((db - loudest + DYNAMIC_RANGE_DB) / DYNAMIC_RANGE_DB * 255.0).clamp(0.0, 255.0) as u8
+        })
+        .collect();
+
+    Spectrogram {
+        columns,
+        bins,
+        hop,
+        data,
+    }
+}
+
+const AUDIO_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+body { display: flex; flex-direction: column; }
+#bar {
+    flex: none; height: 36px; box-sizing: border-box; display: flex; align-items: center;
+    gap: 8px; padding: 0 12px; font-size: 12px; border-bottom: 1px solid var(--border);
+}
+#bar button {
+    font: inherit; color: var(--fg); background: var(--surface); min-width: 28px;
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 8px; cursor: pointer;
+}
+#bar label { display: flex; align-items: center; gap: 4px; user-select: none; }
+#time, #selection { font-variant-numeric: tabular-nums; }
+#selection { color: var(--muted); margin-right: auto; }
+#overview { flex: none; height: 40px; border-bottom: 1px solid var(--border); cursor: pointer; }
+#view { flex: 1; position: relative; display: flex; flex-direction: column; min-height: 0; }
+#wave, #spec { flex: 1; min-height: 0; width: 100%; }
+#spec { border-top: 1px solid var(--border); }
+#spec.hidden { display: none; }
+#axis { flex: none; height: 22px; width: 100%; border-top: 1px solid var(--border); }
+#overlay { position: absolute; left: 0; top: 0; width: 100%; height: 100%; cursor: text; }
+canvas { display: block; }
+#status { position: absolute; left: 12px; top: 12px; color: var(--muted); font-size: 13px; }
+"#;
+
+const AUDIO_SCRIPT: &str = r#"
+(function () {
+    var info = JSON.parse(document.getElementById('data').textContent);
+    var BLOCK = 256;
+    var MIN_SPAN = 16;
+
+    var player = document.getElementById('player');
+    var overview = document.getElementById('overview');
+    var wave = document.getElementById('wave');
+    var spec = document.getElementById('spec');
+    var axis = document.getElementById('axis');
+    var overlay = document.getElementById('overlay');
+    var view = document.getElementById('view');
+    var status = document.getElementById('status');
+    var internals = window.__TAURI_INTERNALS__;
+
+    var channels = [];      // Float32Array per channel
+    var peaks = [];         // per channel: Float32Array of [min, max] per block
+    var frames = 0;
+    var specImage = null;
+    var start = 0, end = 1; // visible range in frames
+    var selection = null;   // [from, to] in frames
+    var ratio = window.devicePixelRatio || 1;
+
+    function css(name) {
+        return getComputedStyle(document.documentElement).getPropertyValue(name).trim();
+    }
+
+    function formatTime(seconds) {
+        var minutes = Math.floor(seconds / 60);
+        var rest = seconds - minutes * 60;
+        return minutes + ':' + (rest < 10 ? '0' : '') + rest.toFixed(3);
+    }
+
+    function parseWav(buffer) {
+        var bytes = new DataView(buffer);
+        var count = info.channels;
+        var total = (buffer.byteLength - 44) / 2;
+        frames = Math.floor(total / count);
+        for (var c = 0; c < count; c++) channels.push(new Float32Array(frames));
+        for (var i = 0, offset = 44; i < frames; i++) {
+            for (c = 0; c < count; c++, offset += 2) {
+                channels[c][i] = bytes.getInt16(offset, true) / 32767;
+            }
+        }
+        channels.forEach(function (data) {
+            var blocks = Math.ceil(data.length / BLOCK);
+            var p = new Float32Array(blocks * 2);
+            for (var b = 0; b < blocks; b++) {
+                var lo = 1, hi = -1;
+                for (var j = b * BLOCK, stop = Math.min(data.length, j + BLOCK); j < stop; j++) {
+                    var v = data[j];
+                    if (v < lo) lo = v;
+                    if (v > hi) hi = v;
+                }
+                p[b * 2] = lo;
+                p[b * 2 + 1] = hi;
+            }
+            peaks.push(p);
+        });
+    }
+
+    // Colour map from quiet (dark) to loud (bright)
+    var STOPS = [[0, 0, 4], [81, 18, 124], [183, 55, 121], [252, 137, 97], [252, 253, 191]];
+    function colour(value) {
+        var position = value / 255 * (STOPS.length - 1);
+        var i = Math.min(STOPS.length - 2, Math.floor(position));
+        var t = position - i;
+        return STOPS[i].map(function (a, k) { return Math.round(a + (STOPS[i + 1][k] - a) * t); });
+    }
+
+    function buildSpectrogram(bytes) {
+        var s = info.spectrogram;
+        var canvas = document.createElement('canvas');
+        canvas.width = Math.max(1, s.columns);
+        canvas.height = s.bins;
+        var ctx = canvas.getContext('2d');
+        var image = ctx.createImageData(canvas.width, canvas.height);
+        var palette = [];
+        for (var v = 0; v < 256; v++) palette.push(colour(v));
+        for (var x = 0; x < s.columns; x++) {
+            for (var y = 0; y < s.bins; y++) {
+                var rgb = palette[bytes[x * s.bins + y]];
+                var o = (y * canvas.width + x) * 4;
+                image.data[o] = rgb[0];
+                image.data[o + 1] = rgb[1];
+                image.data[o + 2] = rgb[2];
+                image.data[o + 3] = 255;
+            }
+        }
+        ctx.putImageData(image, 0, 0);
+        specImage = canvas;
+    }
+
+    function size(canvas) {
+        var width = Math.max(1, Math.round(canvas.clientWidth * ratio));
+        var height = Math.max(1, Math.round(canvas.clientHeight * ratio));
+        if (canvas.width !== width || canvas.height !== height) {
+            canvas.width = width;
+            canvas.height = height;
+        }
+        return canvas.getContext('2d');
+    }
+
+    function frameAt(x, width) { return start + x / width * (end - start); }
+    function xAt(frame, width) { return (frame - start) / (end - start) * width; }
+
+    // Min/max of channel c over [from, to), using block peaks when coarse
+    function range(c, from, to) {
+        var lo = 1, hi = -1, i, stop;
+        from = Math.max(0, Math.floor(from));
+        to = Math.min(frames, Math.ceil(to));
+        if (to - from >= BLOCK * 4) {
+            var p = peaks[c];
+            for (i = Math.floor(from / BLOCK), stop = Math.ceil(to / BLOCK); i < stop; i++) {
+                if (p[i * 2] < lo) lo = p[i * 2];
+                if (p[i * 2 + 1] > hi) hi = p[i * 2 + 1];
+            }
+        } else {
+            var data = channels[c];
+            for (i = from; i < to; i++) {
+                if (data[i] < lo) lo = data[i];
+                if (data[i] > hi) hi = data[i];
+            }
+        }
+        return lo > hi ? null : [lo, hi];
+    }
+
+    function drawChannels(ctx, width, height, from, to) {
+        var lane = height / channels.length;
+        var perPixel = (to - from) / width;
+        ctx.clearRect(0, 0, width, height);
+        channels.forEach(function (data, c) {
+            var middle = lane * c + lane / 2;
+            var scale = lane / 2 * 0.95;
+            ctx.fillStyle = css('--border');
+            ctx.fillRect(0, Math.round(middle), width, 1);
+            ctx.fillStyle = css('--link');
+            ctx.strokeStyle = css('--link');
+            if (perPixel >= 1) {
+                for (var x = 0; x < width; x++) {
+                    var r = range(c, from + x * perPixel, from + (x + 1) * perPixel);
+                    if (!r) continue;
+                    var top = middle - r[1] * scale;
+                    ctx.fillRect(x, top, 1, Math.max(1, (r[1] - r[0]) * scale));
+                }
+            } else {
+                // Zoomed in past one sample per pixel: connect the samples
+                ctx.beginPath();
+                for (var i = Math.max(0, Math.floor(from)); i <= Math.min(frames - 1, Math.ceil(to)); i++) {
+                    var px = (i - from) / perPixel;
+                    var py = middle - data[i] * scale;
+                    if (i === Math.max(0, Math.floor(from))) ctx.moveTo(px, py);
+                    else ctx.lineTo(px, py);
+                    if (perPixel < 0.1) ctx.fillRect(px - 2, py - 2, 4, 4);
+                }
+                ctx.lineWidth = ratio;
+                ctx.stroke();
+            }
+        });
+    }
+
+    function drawAxis() {
+        var ctx = size(axis);
+        var width = axis.width;
+        ctx.clearRect(0, 0, width, axis.height);
+        var seconds = (end - start) / info.sampleRate;
+        var steps = [0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1, 2, 5, 10, 15, 30, 60, 120, 300, 600];
+        var step = steps[steps.length - 1];
+        for (var i = 0; i < steps.length; i++) {
+            if (steps[i] / seconds * width >= 90 * ratio) { step = steps[i]; break; }
+        }
+        ctx.fillStyle = css('--muted');
+        ctx.font = (11 * ratio) + 'px system-ui, sans-serif';
+        var first = Math.ceil(start / info.sampleRate / step) * step;
+        for (var t = first; t <= end / info.sampleRate; t += step) {
+            var x = xAt(t * info.sampleRate, width);
+            ctx.fillRect(Math.round(x), 0, 1, 5 * ratio);
+            ctx.fillText(formatTime(t), x + 3 * ratio, 16 * ratio);
+        }
+    }
+
+    function drawSpectrogram() {
+        if (spec.classList.contains('hidden') || !specImage) return;
+        var ctx = size(spec);
+        var s = info.spectrogram;
+        ctx.imageSmoothingEnabled = true;
+        ctx.drawImage(specImage, start / s.hop, 0, Math.max((end - start) / s.hop, 0.01), s.bins,
+            0, 0, spec.width, spec.height);
+        ctx.fillStyle = 'rgba(255, 255, 255, 0.8)';
+        ctx.font = (11 * ratio) + 'px system-ui, sans-serif';
+        ctx.fillText((info.sampleRate / 2000).toFixed(1) + ' kHz', 4 * ratio, 14 * ratio);
+        ctx.fillText('0 Hz', 4 * ratio, spec.height - 4 * ratio);
+    }
+
+    function drawOverview() {
+        var ctx = size(overview);
+        drawChannels(ctx, overview.width, overview.height, 0, frames);
+        ctx.fillStyle = 'rgba(127, 127, 127, 0.25)';
+        var a = start / frames * overview.width, b = end / frames * overview.width;
+        ctx.fillRect(0, 0, a, overview.height);
+        ctx.fillRect(b, 0, overview.width - b, overview.height);
+    }
+
+    function drawOverlay() {
+        var ctx = size(overlay);
+        var width = overlay.width, height = overlay.height;
+        ctx.clearRect(0, 0, width, height);
+        if (selection) {
+            var a = xAt(selection[0], width), b = xAt(selection[1], width);
+            ctx.fillStyle = 'rgba(9, 105, 218, 0.18)';
+            ctx.fillRect(a, 0, b - a, height);
+            ctx.fillStyle = css('--link');
+            ctx.fillRect(Math.round(a), 0, ratio, height);
+            ctx.fillRect(Math.round(b), 0, ratio, height);
+        }
+        var x = xAt(player.currentTime * info.sampleRate, width);
+        ctx.fillStyle = '#cf222e';
+        ctx.fillRect(Math.round(x), 0, Math.max(1, ratio * 1.5), height);
+        document.getElementById('time').textContent =
+            formatTime(player.currentTime) + ' / ' + formatTime(frames / info.sampleRate);
+    }
+
+    function draw() {
+        if (!frames) return;
+        var ctx = size(wave);
+        drawChannels(ctx, wave.width, wave.height, start, end);
+        drawSpectrogram();
+        drawAxis();
+        drawOverview();
+        drawOverlay();
+    }
+
+    function setView(from, to) {
+        var span = Math.max(MIN_SPAN, Math.min(frames, to - from));
+        from = Math.max(0, Math.min(frames - span, from));
+        start = from;
+        end = from + span;
+        draw();
+    }
+
+    function zoom(factor, anchor) {
+        if (anchor === undefined) anchor = (start + end) / 2;
+        setView(anchor - (anchor - start) * factor, anchor + (end - anchor) * factor);
+    }
+
+    function showSelection() {
+        var text = '';
+        if (selection) {
+            var a = selection[0] / info.sampleRate, b = selection[1] / info.sampleRate;
+            text = formatTime(a) + ' – ' + formatTime(b) + ' (' + (b - a).toFixed(3) + ' s)';
+        }
+        document.getElementById('selection').textContent = text;
+    }
+
+    function report() {
+        showSelection();
+        if (!internals) return;
+        var value = selection ? {
+            start: selection[0] / info.sampleRate,
+            end: selection[1] / info.sampleRate
+        } : null;
+        internals.invoke('audio_selection', { selection: value }).catch(function () {});
+    }
+
+    // Dragging selects, clicking seeks and clears the selection
+    var dragFrom = null, dragX = 0;
+    overlay.addEventListener('mousedown', function (event) {
+        var rect = overlay.getBoundingClientRect();
+        dragX = event.clientX;
+        dragFrom = frameAt(event.clientX - rect.left, rect.width);
+    });
+    window.addEventListener('mousemove', function (event) {
+        if (dragFrom === null || Math.abs(event.clientX - dragX) < 3) return;
+        var rect = overlay.getBoundingClientRect();
+        var frame = Math.max(0, Math.min(frames, frameAt(event.clientX - rect.left, rect.width)));
+        selection = [Math.min(dragFrom, frame), Math.max(dragFrom, frame)];
+        showSelection();
+        drawOverlay();
+    });
+    window.addEventListener('mouseup', function (event) {
+        if (dragFrom === null) return;
+        if (Math.abs(event.clientX - dragX) < 3) {
+            player.currentTime = Math.max(0, dragFrom) / info.sampleRate;
+            if (selection) {
+                selection = null;
+                report();
+            }
+        } else {
+            report();
+        }
+        dragFrom = null;
+        drawOverlay();
+    });
+
+    view.addEventListener('wheel', function (event) {
+        event.preventDefault();
+        var rect = overlay.getBoundingClientRect();
+        if (event.shiftKey || Math.abs(event.deltaX) > Math.abs(event.deltaY)) {
+            var delta = (event.deltaX || event.deltaY) / rect.width * (end - start);
+            setView(start + delta, end + delta);
+        } else {
+            zoom(Math.exp(event.deltaY * 0.002), frameAt(event.clientX - rect.left, rect.width));
+        }
+    }, { passive: false });
+
+    function moveOverview(event) {
+        var rect = overview.getBoundingClientRect();
+        var centre = (event.clientX - rect.left) / rect.width * frames;
+        setView(centre - (end - start) / 2, centre + (end - start) / 2);
+    }
+    var overviewDrag = false;
+    overview.addEventListener('mousedown', function (event) { overviewDrag = true; moveOverview(event); });
+    window.addEventListener('mousemove', function (event) { if (overviewDrag) moveOverview(event); });
+    window.addEventListener('mouseup', function () { overviewDrag = false; });
+
+    function togglePlay() {
+        if (!player.paused) {
+            player.pause();
+            return;
+        }
+        var now = player.currentTime * info.sampleRate;
+        if (selection && (now < selection[0] || now >= selection[1] - 1)) {
+            player.currentTime = selection[0] / info.sampleRate;
+        }
+        player.play();
+    }
+
+    function tick() {
+        if (selection && player.currentTime * info.sampleRate >= selection[1]) {
+            player.pause();
+            player.currentTime = selection[1] / info.sampleRate;
+        }
+        // Keep the playhead in view
+        var now = player.currentTime * info.sampleRate;
+        if (!player.paused && (now > end || now < start)) {
+            setView(now, now + (end - start));
+        } else {
+            drawOverlay();
+        }
+        if (!player.paused) requestAnimationFrame(tick);
+    }
+    player.addEventListener('play', function () {
+        document.getElementById('play').textContent = '❚❚';
+        requestAnimationFrame(tick);
+    });
+    player.addEventListener('pause', function () {
+        document.getElementById('play').textContent = '▶';
+        drawOverlay();
+    });
+    player.addEventListener('seeked', drawOverlay);
+
+    document.getElementById('play').addEventListener('click', togglePlay);
+    document.getElementById('zoom-in').addEventListener('click', function () { zoom(0.5); });
+    document.getElementById('zoom-out').addEventListener('click', function () { zoom(2); });
+    document.getElementById('zoom-fit').addEventListener('click', function () { setView(0, frames); });
+    document.getElementById('zoom-selection').addEventListener('click', function () {
+        if (selection) setView(selection[0], selection[1]);
+    });
+    var toggle = document.getElementById('show-spectrogram');
+    toggle.checked = info.showSpectrogram;
+    spec.classList.toggle('hidden', !info.showSpectrogram);
+    toggle.addEventListener('change', function () {
+        spec.classList.toggle('hidden', !toggle.checked);
+        draw();
+    });
+    document.addEventListener('keydown', function (event) {
+        if (event.target.tagName === 'INPUT' && event.target.type !== 'checkbox') return;
+        if (event.key === ' ') { event.preventDefault(); togglePlay(); }
+        if (event.key === '+' || event.key === '=') zoom(0.5);
+        if (event.key === '-') zoom(2);
+        if (event.key === '0') setView(0, frames);
+        if (event.key === 'ArrowLeft') setView(start - (end - start) / 4, end - (end - start) / 4);
+        if (event.key === 'ArrowRight') setView(start + (end - start) / 4, end + (end - start) / 4);
+        if (event.key === 'Escape' && selection) { selection = null; report(); drawOverlay(); }
+    });
+    window.addEventListener('resize', function () {
+        ratio = window.devicePixelRatio || 1;
+        draw();
+    });
+
+    Promise.all([
+        fetch('audio.wav').then(function (r) { return r.arrayBuffer(); }),
+        fetch('spectrogram.bin').then(function (r) { return r.arrayBuffer(); })
+    ]).then(function (buffers) {
+        // Play from memory: media requests to the bundle scheme need ranges
+        player.src = URL.createObjectURL(new Blob([buffers[0]], { type: 'audio/wav' }));
+        parseWav(buffers[0]);
+        buildSpectrogram(new Uint8Array(buffers[1]));
+        status.remove();
+        setView(0, frames);
+    }, function (error) {
+        status.textContent = 'Failed to load audio: ' + error;
+    });
+})();
+"#;
+
+/// Build the files of the audio view for `audio`: `index.html`,
+/// `audio.wav` and `spectrogram.bin`.
+///
+/// `show_spectrogram` sets whether the spectrogram is visible initially.
+/// Selection changes are reported with the viewer's `audio_selection`
+/// command. Fails if the audio cannot be encoded by [`encode_wav`].
+pub fn audio_files(
+    audio: &DecodedAudio,
+    show_spectrogram: bool,
+    theme: &WindowTheme,
+) -> io::Result<HashMap<String, Vec<u8>>> {
+    let wav = encode_wav(audio)?;
+    let spectrogram = spectrogram(audio);
+    let info = script_json(&serde_json::json!({
+        "sampleRate": audio.sample_rate,
+        "channels": audio.channels.max(1),
+        "showSpectrogram": show_spectrogram,
+        "spectrogram": {
+            "columns": spectrogram.columns,
+            "bins": spectrogram.bins,
+            "hop": spectrogram.hop,
+        },
+    }));
+    let body = format!(
+        r#"<div id="bar">
+<button id="play" title="Play or pause (Space)">▶</button>
+<span id="time"></span>
+<span id="selection"></span>
+<button id="zoom-out" title="Zoom out (-)">−</button>
+<button id="zoom-in" title="Zoom in (+)">+</button>
+<button id="zoom-selection" title="Zoom to selection">Selection</button>
+<button id="zoom-fit" title="Show all (0)">All</button>
+<label><input id="show-spectrogram" type="checkbox"> Spectrogram</label>
+</div>
+<canvas id="overview"></canvas>
+<div id="view">
+<canvas id="wave"></canvas>
+<canvas id="spec"></canvas>
+<canvas id="axis"></canvas>
+<canvas id="overlay"></canvas>
+<span id="status">Loading…</span>
+</div>
+<audio id="player" preload="auto"></audio>
+<script id="data" type="application/json">{info}</script>
+<script>{AUDIO_SCRIPT}</script>"#
+    );
+
+    Ok(HashMap::from([
+        (
+            "index.html".to_string(),
+            page(theme, AUDIO_STYLE, &body).into_bytes(),
+        ),
+        ("audio.wav".to_string(), wav),
+        ("spectrogram.bin".to_string(), spectrogram.data),
+    ]))
+}
@@ -0,0 +1,71 @@
+//! Source code view with line numbers and marked lines.
+
+use super::{highlight_by_line, highlight_css, page};
+use crate::WindowTheme;
+
+/// Styles for line tables, shared with the diff view.
+///
+/// Line numbers and diff signs are drawn from `data-` attributes so copying
+/// a selection only copies the code.
+pub(super) const LINES_STYLE: &str = r#"
+table.lines {
+    border-collapse: collapse; width: 100%; background: var(--bg);
+    font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace;
+    font-size: 13px; line-height: 20px; tab-size: 4;
+}
+table.lines td { padding: 0 12px; vertical-align: top; white-space: pre; }
+table.lines td.ln {
+    width: 1%; min-width: 3ch; text-align: right; color: var(--muted); user-select: none;
+    border-right: 1px solid var(--border);
+}
+table.lines td.ln::before { content: attr(data-line); }
+table.lines tr.marked td { background: rgba(212, 167, 44, 0.22); }
+table.lines tr.marked td.ln { color: var(--fg); font-weight: 600; }
+"#;
+
+const CODE_STYLE: &str = r#"
+body { overflow: auto; }
+table.lines tr:first-child td { padding-top: 8px; }
+table.lines tr:last-child td { padding-bottom: 8px; }
+"#;
+
+const CODE_SCRIPT: &str = r#"
+(function () {
+    var marked = document.querySelector('tr.marked');
+    if (marked) marked.scrollIntoView({ block: 'center' });
+})();
+"#;
+
+/// Render `source` as a highlighted listing.
+///
+/// `language` is looked up as for [`highlight`](super::highlight).
+/// `highlight_lines` holds 1-based line numbers to mark; the first one is
+/// scrolled into view.
+pub fn render_code(
+    source: &str,
+    language: Option<&str>,
+    line_numbers: bool,
+    highlight_lines: &[usize],
+    theme: &WindowTheme,
+) -> String {
+    let mut body = String::from(r#"<table class="lines hl-code">"#);
+    body.push('\n');
+    for (index, html) in highlight_by_line(source, language).iter().enumerate() {
+        let line = index + 1;
+        let class = if highlight_lines.contains(&line) {
+            r#" class="marked""#
+        } else {
+            ""
+        };
+        body.push_str(&format!(r#"<tr id="L{line}"{class}>"#));
+        if line_numbers {
+            body.push_str(&format!(r#"<td class="ln" data-line="{line}"></td>"#));
+        }
+        body.push_str(&format!("<td>{html}</td></tr>\n"));
+    }
+    body.push_str("</table>\n");
+    body.push_str(&format!("<script>{CODE_SCRIPT}</script>"));
+
+    let style = format!("{LINES_STYLE}{CODE_STYLE}{}", highlight_css(theme));
+    page(theme, &style, &body)
+}
@@ -0,0 +1,675 @@
+//! Graphviz DOT parser.
+//!
+//! Follows the DOT grammar, including default attribute statements,
+//! subgraphs as edge endpoints and HTML-like labels. Subgraphs and clusters
+//! are flattened into the graph; ports and layout hints are ignored.
+
+use super::{DiagramError, Direction, Edge, Graph, Line, Shape, Style, colour, html_text};
+use std::collections::HashMap;
+
+type Attributes = HashMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An identifier, number, quoted string or HTML string.
+    Id {
+        text: String,
+        quoted: bool,
+    },
+    Open,
+    Close,
+    OpenBracket,
+    CloseBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    /// `->` (true) or `--` (false).
+    EdgeOp(bool),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Id { text, .. } => format!("`{}`", text),
+            Token::Open => "`{`".to_string(),
+            Token::Close => "`}`".to_string(),
+            Token::OpenBracket => "`[`".to_string(),
+            Token::CloseBracket => "`]`".to_string(),
+            Token::Equals => "`=`".to_string(),
+            Token::Semicolon => "`;`".to_string(),
+            Token::Comma => "`,`".to_string(),
+            Token::Colon => "`:`".to_string(),
+            Token::EdgeOp(true) => "`->`".to_string(),
+            Token::EdgeOp(false) => "`--`".to_string(),
+        }
+    }
+
+    /// Whether this is the unquoted keyword `keyword`, in any case.
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Id { text, quoted: false } if text.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, DiagramError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let at_line_start = std::mem::replace(&mut line_start, false);
+
+        // Comments, and preprocessor lines which Graphviz also skips
+        if (c == '#' && at_line_start) || (c == '/' && chars.get(i + 1) == Some(&'/')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let start_line = line;
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(DiagramError::new(start_line, "unterminated comment"));
+            }
+            i += 2;
+            continue;
+        }
+
+        let token_line = line;
+        let token = match c {
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '=' => Token::Equals,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                i += 1;
+                Token::EdgeOp(true)
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                i += 1;
+                Token::EdgeOp(false)
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    i += 1;
+                    match chars.get(i) {
+                        None => return Err(DiagramError::new(token_line, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                            text.push('"');
+                            i += 1;
+                        }
+                        // A backslash before a newline continues the line
+                        Some('\\') if chars.get(i + 1) == Some(&'\n') => {
+                            line += 1;
+                            i += 1;
+                        }
+                        Some(&c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                    }
+                }
+                Token::Id { text, quoted: true }
+            }
+            '<' => {
+                let mut depth = 0;
+                let start = i + 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(DiagramError::new(token_line, "unterminated HTML label"));
+                        }
+                        Some('<') => depth += 1,
+                        Some('>') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some('\n') => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                let html: String = chars[start..i].iter().collect();
+                Token::Id {
+                    text: html_text(&html),
+                    quoted: true,
+                }
+            }
+            '+' => Token::Id {
+                text: "+".to_string(),
+                quoted: false,
+            },
+            c if is_id_char(c) || c == '-' => {
+                let start = i;
+                while chars.get(i + 1).is_some_and(|&c| is_id_char(c)) {
+                    i += 1;
+                }
+                Token::Id {
+                    text: chars[start..=i].iter().collect(),
+                    quoted: false,
+                }
+            }
+            c => {
+                return Err(DiagramError::new(
+                    line,
+                    format!("unexpected character `{}`", c),
+                ));
+            }
+        };
+        i += 1;
+        tokens.push((token, token_line));
+    }
+
+    Ok(join_strings(tokens))
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || !c.is_ascii()
+}
+
+/// Join `"a" + "b"` into one string. The `+` is lexed as an identifier.
+fn join_strings(tokens: Vec<(Token, usize)>) -> Vec<(Token, usize)> {
+    let mut joined: Vec<(Token, usize)> = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some((token, line)) = tokens.next() {
+        if let Token::Id {
+            text: plus,
+            quoted: false,
+        } = &token
+            && plus == "+"
+            && matches!(joined.last(), Some((Token::Id { quoted: true, .. }, _)))
+            && let Some((Token::Id { quoted: true, .. }, _)) = tokens.peek()
+        {
+            let Some((Token::Id { text: next, .. }, _)) = tokens.next() else {
+                unreachable!()
+            };
+            if let Some((Token::Id { text, .. }, _)) = joined.last_mut() {
+                text.push_str(&next);
+            }
+            continue;
+        }
+        joined.push((token, line));
+    }
+    joined
+}
+
+/// Attributes in effect for new nodes and edges in a (sub)graph.
+#[derive(Clone, Default)]
+struct Scope {
+    node: Attributes,
+    edge: Attributes,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    directed: bool,
+    scopes: Vec<Scope>,
+    graph: Attributes,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(usize, usize, Attributes)>,
+    index: HashMap<String, usize>,
+    /// Every node reference in order, so subgraphs can list their nodes.
+    mentions: Vec<usize>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error(&self, expected: &str) -> DiagramError {
+        let found = match self.peek() {
+            Some(token) => token.describe(),
+            None => "end of input".to_string(),
+        };
+        DiagramError::new(
+            self.line(),
+            format!("expected {}, found {}", expected, found),
+        )
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<(), DiagramError> {
+        if self.peek() == Some(&token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn id(&mut self, expected: &str) -> Result<String, DiagramError> {
+        match self.peek() {
+            Some(Token::Id { text, .. }) => {
+                let text = text.clone();
+                self.pos += 1;
+                Ok(text)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("the graph scope is never popped")
+    }
+
+    fn parse_graph(&mut self) -> Result<(), DiagramError> {
+        if self.peek().is_some_and(|token| token.is_keyword("strict")) {
+            self.pos += 1;
+        }
+        self.directed = match self.peek() {
+            Some(token) if token.is_keyword("digraph") => true,
+            Some(token) if token.is_keyword("graph") => false,
+            _ => return Err(self.error("`graph` or `digraph`")),
+        };
+        self.pos += 1;
+        if matches!(self.peek(), Some(Token::Id { .. })) {
+            self.pos += 1;
+        }
+        self.expect(Token::Open, "`{`")?;
+        self.statements()?;
+        self.expect(Token::Close, "`}`")?;
+        if self.peek().is_some() {
+            return Err(self.error("end of input after the graph"));
+        }
+        Ok(())
+    }
+
+    fn statements(&mut self) -> Result<(), DiagramError> {
+        while !matches!(self.peek(), None | Some(Token::Close)) {
+            self.statement()?;
+            if self.peek() == Some(&Token::Semicolon) {
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self) -> Result<(), DiagramError> {
+        let token = self.peek().cloned();
+        match token {
+            Some(ref keyword @ Token::Id { quoted: false, .. })
+                if ["graph", "node", "edge"]
+                    .iter()
+                    .any(|word| keyword.is_keyword(word))
+                    && self.peek_at(1) == Some(&Token::OpenBracket) =>
+            {
+                self.pos += 1;
+                let attributes = self.attribute_lists()?;
+                if keyword.is_keyword("node") {
+                    self.scope().node.extend(attributes);
+                } else if keyword.is_keyword("edge") {
+                    self.scope().edge.extend(attributes);
+                } else if self.scopes.len() == 1 {
+                    self.graph.extend(attributes);
+                }
+            }
+            Some(Token::Id { .. }) if self.peek_at(1) == Some(&Token::Equals) => {
+                let key = self.id("an attribute name")?;
+                self.pos += 1;
+                let value = self.id("an attribute value")?;
+                if self.scopes.len() == 1 {
+                    self.graph.insert(key, value);
+                }
+            }
+            _ => {
+                let first = self.endpoint()?;
+                if matches!(self.peek(), Some(Token::EdgeOp(_))) {
+                    self.edge_statement(first)?;
+                } else if let Endpoint::Node(node) = first
+                    && self.peek() == Some(&Token::OpenBracket)
+                {
+                    let attributes = self.attribute_lists()?;
+                    self.nodes[node].1.extend(attributes);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn edge_statement(&mut self, first: Endpoint) -> Result<(), DiagramError> {
+        let mut endpoints = vec![first];
+        while let Some(Token::EdgeOp(_)) = self.peek() {
+            self.pos += 1;
+            endpoints.push(self.endpoint()?);
+        }
+        let mut attributes = self.scope().edge.clone();
+        if self.peek() == Some(&Token::OpenBracket) {
+            attributes.extend(self.attribute_lists()?);
+        }
+
+        for pair in endpoints.windows(2) {
+            for &from in pair[0].nodes() {
+                for &to in pair[1].nodes() {
+                    self.edges.push((from, to, attributes.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A node ID (with an ignored port) or a subgraph.
+    fn endpoint(&mut self) -> Result<Endpoint, DiagramError> {
+        match self.peek() {
+            Some(Token::Open) => Ok(Endpoint::Subgraph(self.subgraph()?)),
+            Some(token) if token.is_keyword("subgraph") => Ok(Endpoint::Subgraph(self.subgraph()?)),
+            Some(Token::Id { .. }) => {
+                let id = self.id("a node")?;
+                // Ports: `node:port` or `node:port:compass`
+                while self.peek() == Some(&Token::Colon) {
+                    self.pos += 1;
+                    self.id("a port")?;
+                }
+                Ok(Endpoint::Node(self.node(&id)))
+            }
+            _ => Err(self.error("a node, subgraph or attribute")),
+        }
+    }
+
+    /// A subgraph, returning the nodes it mentions.
+    fn subgraph(&mut self) -> Result<Vec<usize>, DiagramError> {
+        if self
+            .peek()
+            .is_some_and(|token| token.is_keyword("subgraph"))
+        {
+            self.pos += 1;
+            if matches!(self.peek(), Some(Token::Id { .. })) {
+                self.pos += 1;
+            }
+        }
+        self.expect(Token::Open, "`{`")?;
+
+        let scope = self.scope().clone();
+        self.scopes.push(scope);
+        let first_mention = self.mentions.len();
+        self.statements()?;
+        self.scopes.pop();
+        self.expect(Token::Close, "`}`")?;
+
+        let mut nodes = Vec::new();
+        for &node in &self.mentions[first_mention..] {
+            if !nodes.contains(&node) {
+                nodes.push(node);
+            }
+        }
+        Ok(nodes)
+    }
+
+    fn attribute_lists(&mut self) -> Result<Attributes, DiagramError> {
+        let mut attributes = Attributes::new();
+        while self.peek() == Some(&Token::OpenBracket) {
+            self.pos += 1;
+            while self.peek() != Some(&Token::CloseBracket) {
+                let key = self.id("an attribute name or `]`")?;
+                self.expect(Token::Equals, "`=`")?;
+                let value = self.id("an attribute value")?;
+                attributes.insert(key, value);
+                if matches!(self.peek(), Some(Token::Comma | Token::Semicolon)) {
+                    self.pos += 1;
+                }
+            }
+            self.pos += 1;
+        }
+        Ok(attributes)
+    }
+
+    fn node(&mut self, id: &str) -> usize {
+        let index = match self.index.get(id) {
+            Some(&index) => index,
+            None => {
+                let attributes = self.scope().node.clone();
+                self.nodes.push((id.to_string(), attributes));
+                self.index.insert(id.to_string(), self.nodes.len() - 1);
+                self.nodes.len() - 1
+            }
+        };
+        self.mentions.push(index);
+        index
+    }
+}
+
+enum Endpoint {
+    Node(usize),
+    Subgraph(Vec<usize>),
+}
+
+impl Endpoint {
+    fn nodes(&self) -> &[usize] {
+        match self {
+            Endpoint::Node(node) => std::slice::from_ref(node),
+            Endpoint::Subgraph(nodes) => nodes,
+        }
+    }
+}
+
+/// Parse a DOT graph.
+pub(super) fn parse(source: &str) -> Result<Graph, DiagramError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        directed: false,
+        scopes: vec![Scope::default()],
+        graph: Attributes::new(),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+        index: HashMap::new(),
+        mentions: Vec::new(),
+    };
+    parser.parse_graph()?;
+
+    let mut graph = Graph {
+        direction: parser
+            .graph
+            .get("rankdir")
+            .and_then(|value| Direction::parse(value))
+            .unwrap_or_default(),
+        label: parser
+            .graph
+            .get("label")
+            .map(|label| unescape(label, ""))
+            .filter(|label| !label.is_empty()),
+        ..Graph::default()
+    };
+    for (id, attributes) in &parser.nodes {
+        let index = graph.node(id, Shape::Ellipse);
+        let node = &mut graph.nodes[index];
+        let (shape, record) = attributes
+            .get("shape")
+            .map_or((Shape::Ellipse, false), |shape| node_shape(shape));
+        let styles = style_words(attributes);
+        node.shape = if shape == Shape::Box && styles.contains(&"rounded") {
+            Shape::Rounded
+        } else {
+            shape
+        };
+        let label = attributes.get("label").map_or("\\N", String::as_str);
+        node.label = if record {
+            record_label(label, id)
+        } else {
+            unescape(label, id)
+        };
+        node.style = style(attributes, &styles, true);
+    }
+    for (from, to, attributes) in parser.edges {
+        let dir = attributes
+            .get("dir")
+            .map(|dir| dir.to_ascii_lowercase())
+            .unwrap_or_else(|| if parser.directed { "forward" } else { "none" }.to_string());
+        let head = matches!(dir.as_str(), "forward" | "both")
+            && attributes
+                .get("arrowhead")
+                .is_none_or(|arrow| arrow != "none");
+        let tail = matches!(dir.as_str(), "back" | "both")
+            && attributes
+                .get("arrowtail")
+                .is_none_or(|arrow| arrow != "none");
+        let styles = style_words(&attributes);
+        graph.edges.push(Edge {
+            from,
+            to,
+            label: attributes
+                .get("label")
+                .or(attributes.get("xlabel"))
+                .map(|label| unescape(label, ""))
+                .filter(|label| !label.is_empty()),
+            head,
+            tail,
+            style: style(&attributes, &styles, false),
+        });
+    }
+    Ok(graph)
+}
+
+/// The shape for a Graphviz `shape`, and whether it is a record.
+fn node_shape(shape: &str) -> (Shape, bool) {
+    match shape.to_ascii_lowercase().as_str() {
+        "box" | "rect" | "rectangle" | "square" | "component" | "folder" | "tab" | "note"
+        | "box3d" | "cylinder" => (Shape::Box, false),
+        "record" => (Shape::Box, true),
+        "mrecord" => (Shape::Rounded, true),
+        "circle" => (Shape::Circle, false),
+        "doublecircle" => (Shape::DoubleCircle, false),
+        "diamond" | "mdiamond" => (Shape::Diamond, false),
+        "hexagon" | "octagon" | "doubleoctagon" => (Shape::Hexagon, false),
+        "point" => (Shape::Point, false),
+        "plaintext" | "plain" | "none" | "underline" => (Shape::Plain, false),
+        _ => (Shape::Ellipse, false),
+    }
+}
+
+fn style_words(attributes: &Attributes) -> Vec<&str> {
+    attributes
+        .get("style")
+        .map(|style| style.split(',').map(str::trim).collect())
+        .unwrap_or_default()
+}
+
+fn style(attributes: &Attributes, styles: &[&str], node: bool) -> Style {
+    let stroke = attributes.get("color").and_then(|value| colour(value));
+    let fill = if node && styles.contains(&"filled") {
+        attributes
+            .get("fillcolor")
+            .or(attributes.get("color"))
+            .and_then(|value| colour(value))
+            .or(Some("lightgrey".to_string()))
+    } else {
+        None
+    };
+    let line = if styles.contains(&"invis") {
+        Line::Invisible
+    } else if styles.contains(&"dashed") {
+        Line::Dashed
+    } else if styles.contains(&"dotted") {
+        Line::Dotted
+    } else if styles.contains(&"bold") {
+        Line::Bold
+    } else {
+        Line::Solid
+    };
+    Style {
+        stroke,
+        fill,
+        text: attributes.get("fontcolor").and_then(|value| colour(value)),
+        line,
+    }
+}
+
+/// Expand the escapes of a label: `\n`, `\l` and `\r` end lines and `\N`
+/// is the node name.
+fn unescape(label: &str, id: &str) -> String {
+    let mut text = String::new();
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'l' | 'r') => text.push('\n'),
+            Some('N') => text.push_str(id),
+            Some('G' | 'E' | 'T' | 'H') => {}
+            Some(c) => text.push(c),
+            None => text.push('\\'),
+        }
+    }
+    text.trim_end_matches('\n').to_string()
+}
+
+/// The text of a record label: one line per field, without ports.
+fn record_label(label: &str, id: &str) -> String {
+    let mut text = String::new();
+    let mut chars = label.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(&next) = chars.peek()
+                    && "{}|<> ".contains(next)
+                {
+                    text.push(next);
+                    chars.next();
+                } else {
+                    text.push('\\');
+                }
+            }
+            '|' => text.push('\n'),
+            '{' | '}' => {}
+            '<' => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            c => text.push(c),
+        }
+    }
+    let fields: Vec<String> = unescape(&text, id)
+        .lines()
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty())
+        .collect();
+    fields.join("\n")
+}
@@ -0,0 +1,802 @@
+//! Layered graph layout.
+//!
+//! The steps of `dot`, simplified: cycles are broken by reversing back
+//! edges, nodes are ranked by longest path, long edges are split by dummy
+//! nodes (one of which carries the edge label), ranks are ordered by
+//! barycentre sweeps that keep the fewest crossings, and each rank is placed
+//! by isotonic regression towards its neighbours while keeping node spacing.
+//!
+//! Everything is computed top to bottom and transformed for the other
+//! directions at the end.
+
+use super::{Direction, Graph, Shape};
+
+/// Height of a line of text.
+pub(super) const LINE_HEIGHT: f64 = 18.0;
+
+/// Space between ranks.
+const RANK_SEP: f64 = 48.0;
+
+/// Space between neighbouring nodes of a rank.
+const NODE_SEP: f64 = 28.0;
+
+/// Space between edges passing through a rank.
+const EDGE_SEP: f64 = 12.0;
+
+/// Width of a self-loop beside its node.
+const LOOP_WIDTH: f64 = 26.0;
+
+/// Space around the drawing.
+pub(super) const MARGIN: f64 = 16.0;
+
+/// Graphs with more positions than this skip the slower ordering passes.
+const LARGE: usize = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A node's centre and size in the final drawing.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct NodeBox {
+    pub centre: Point,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// An edge as a start point followed by cubic Bézier segments
+/// (`[control, control, end]`), and the centre of its label.
+#[derive(Debug, Clone)]
+pub(super) struct EdgePath {
+    pub start: Point,
+    pub curves: Vec<[Point; 3]>,
+    pub label: Option<Point>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Layout {
+    pub width: f64,
+    pub height: f64,
+    pub nodes: Vec<NodeBox>,
+    pub edges: Vec<EdgePath>,
+}
+
+/// Estimated width of text in the 14px sans-serif font used for labels.
+pub(super) fn text_width(text: &str) -> f64 {
+    text.split('\n')
+        .map(|line| line.chars().map(char_width).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+fn char_width(c: char) -> f64 {
+    match c {
+        'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' | 'I' | ' ' => 4.0,
+        'f' | 't' | 'r' | '(' | ')' | '[' | ']' | '-' | '"' | '/' => 5.0,
+        'm' | 'w' | 'M' | 'W' | '@' | '%' => 12.0,
+        'A'..='Z' => 9.5,
+        c if c.is_ascii() => 7.8,
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FF60}' => 14.0,
+        '\u{1F300}'..='\u{1FAFF}' => 16.0,
+        _ => 8.5,
+    }
+}
+
+/// Size of text: its width and the height of its lines.
+pub(super) fn text_size(text: &str) -> (f64, f64) {
+    (
+        text_width(text),
+        text.split('\n').count() as f64 * LINE_HEIGHT,
+    )
+}
+
+/// The drawn size of a node.
+fn node_size(shape: Shape, label: &str) -> (f64, f64) {
+    let (width, height) = if label.is_empty() {
+        (0.0, 0.0)
+    } else {
+        text_size(label)
+    };
+    match shape {
+        Shape::Point => (12.0, 12.0),
+        Shape::Plain => (width + 8.0, height + 4.0),
+        Shape::Box | Shape::Rounded => ((width + 24.0).max(54.0), (height + 14.0).max(36.0)),
+        Shape::Stadium => (width + height + 28.0, (height + 14.0).max(36.0)),
+        Shape::Hexagon => (width + 44.0, (height + 14.0).max(36.0)),
+        Shape::Ellipse => (
+            ((width + 16.0) * 1.3).max(60.0),
+            ((height + 8.0) * 1.35).max(36.0),
+        ),
+        Shape::Circle | Shape::DoubleCircle => {
+            let minimum = if label.is_empty() { 18.0 } else { 36.0 };
+            let ring = if shape == Shape::DoubleCircle {
+                8.0
+            } else {
+                0.0
+            };
+            let diameter = ((width + 16.0).hypot(height + 4.0)).max(minimum) + ring;
+            (diameter, diameter)
+        }
+        Shape::Diamond => {
+            if label.is_empty() {
+                (28.0, 28.0)
+            } else {
+                (width * 2.0 + 16.0, height * 2.0 + 12.0)
+            }
+        }
+    }
+}
+
+/// A position in a rank: a node, or a dummy on a long edge.
+struct Slot {
+    /// Size across the rank (x) and along it (y).
+    width: f64,
+    height: f64,
+    rank: usize,
+    dummy: bool,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    x: f64,
+}
+
+pub(super) fn layout(graph: &Graph) -> Layout {
+    let transposed = matches!(graph.direction, Direction::LeftRight | Direction::RightLeft);
+    let count = graph.nodes.len();
+    let sizes: Vec<(f64, f64)> = graph
+        .nodes
+        .iter()
+        .map(|node| node_size(node.shape, &node.label))
+        .collect();
+    // Sizes across and along the ranks
+    let across = |(width, height): (f64, f64)| {
+        if transposed {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    };
+
+    // Room beside nodes for their self-loops and loop labels
+    let mut loop_room = vec![0.0f64; count];
+    for edge in graph.edges.iter().filter(|edge| edge.from == edge.to) {
+        let label = edge.label.as_deref().map_or(0.0, |label| {
+            let (width, height) = text_size(label);
+            if transposed { height } else { width }
+        });
+        loop_room[edge.from] = loop_room[edge.from].max(LOOP_WIDTH + label + 8.0);
+    }
+
+    let links: Vec<usize> = (0..graph.edges.len())
+        .filter(|&index| graph.edges[index].from != graph.edges[index].to)
+        .collect();
+    let reversed = break_cycles(graph, &links);
+    // Oriented (upper, lower) ends of each link
+    let ends = |index: usize| {
+        let edge = &graph.edges[index];
+        if reversed[index] {
+            (edge.to, edge.from)
+        } else {
+            (edge.from, edge.to)
+        }
+    };
+    let labelled = links
+        .iter()
+        .any(|&index| graph.edges[index].label.is_some());
+    let min_length = if labelled { 2 } else { 1 };
+    let ranks = rank(count, &links, &ends, min_length);
+
+    let mut slots: Vec<Slot> = (0..count)
+        .map(|node| {
+            let (width, height) = across(sizes[node]);
+            Slot {
+                width: width + 2.0 * loop_room[node],
+                height,
+                rank: ranks[node],
+                dummy: false,
+                up: Vec::new(),
+                down: Vec::new(),
+                x: 0.0,
+            }
+        })
+        .collect();
+
+    // Chains of slots from the upper to the lower end of each link, and the
+    // slot carrying the link's label
+    let mut chains: Vec<Vec<usize>> = vec![Vec::new(); graph.edges.len()];
+    let mut label_slots: Vec<Option<usize>> = vec![None; graph.edges.len()];
+    for &index in &links {
+        let (upper, lower) = ends(index);
+        let mut chain = vec![upper];
+        for rank in ranks[upper] + 1..ranks[lower] {
+            slots.push(Slot {
+                width: 0.0,
+                height: 0.0,
+                rank,
+                dummy: true,
+                up: Vec::new(),
+                down: Vec::new(),
+                x: 0.0,
+            });
+            chain.push(slots.len() - 1);
+        }
+        chain.push(lower);
+
+        if let Some(label) = &graph.edges[index].label
+            && chain.len() > 2
+        {
+            let middle = chain[chain.len() / 2];
+            let (width, height) = across(text_size(label));
+            slots[middle].width = width + 8.0;
+            slots[middle].height = height + 4.0;
+            label_slots[index] = Some(middle);
+        }
+        for pair in chain.windows(2) {
+            slots[pair[0]].down.push(pair[1]);
+            slots[pair[1]].up.push(pair[0]);
+        }
+        chains[index] = chain;
+    }
+
+    let rank_count = slots.iter().map(|slot| slot.rank + 1).max().unwrap_or(0);
+    let mut layers = order(&slots, rank_count, count);
+    place(&mut slots, &mut layers);
+
+    // Rank positions along the layout
+    let rank_sep = RANK_SEP / f64::from(min_length as u32);
+    let mut rank_centres = Vec::with_capacity(rank_count);
+    let mut y = MARGIN;
+    for layer in &layers {
+        let height = layer
+            .iter()
+            .map(|&slot| slots[slot].height)
+            .fold(0.0, f64::max);
+        rank_centres.push(y + height / 2.0);
+        y += height + rank_sep;
+    }
+    let length = if rank_count == 0 {
+        2.0 * MARGIN
+    } else {
+        y - rank_sep + MARGIN
+    };
+    let breadth = slots
+        .iter()
+        .map(|slot| slot.x + slot.width / 2.0)
+        .fold(0.0, f64::max)
+        + MARGIN;
+
+    let position = |slot: usize| Point {
+        x: slots[slot].x,
+        y: rank_centres[slots[slot].rank],
+    };
+    // Map from top-to-bottom coordinates to the requested direction
+    let map = |point: Point| match graph.direction {
+        Direction::TopBottom => point,
+        Direction::BottomTop => Point {
+            x: point.x,
+            y: length - point.y,
+        },
+        Direction::LeftRight => Point {
+            x: point.y,
+            y: point.x,
+        },
+        Direction::RightLeft => Point {
+            x: length - point.y,
+            y: point.x,
+        },
+    };
+
+    let nodes: Vec<NodeBox> = (0..count)
+        .map(|node| NodeBox {
+            centre: map(position(node)),
+            width: sizes[node].0,
+            height: sizes[node].1,
+        })
+        .collect();
+    // Node extents across and along the ranks, for clipping edges
+    let half = |node: usize| {
+        let (width, height) = across(sizes[node]);
+        (width / 2.0, height / 2.0)
+    };
+
+    let edges = graph
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(index, edge)| {
+            if edge.from == edge.to {
+                return self_loop(
+                    position(edge.from),
+                    half(edge.from),
+                    graph.nodes[edge.from].shape,
+                    edge.label.as_deref().map(|label| {
+                        let (width, height) = text_size(label);
+                        if transposed { height } else { width }
+                    }),
+                    &map,
+                );
+            }
+
+            let mut points: Vec<Point> = chains[index].iter().map(|&slot| position(slot)).collect();
+            let (mut first, mut last) = (chains[index][0], *chains[index].last().unwrap());
+            if reversed[index] {
+                points.reverse();
+                std::mem::swap(&mut first, &mut last);
+            }
+            let count = points.len();
+            points[0] = clip(points[0], points[1], half(first), graph.nodes[first].shape);
+            points[count - 1] = clip(
+                points[count - 1],
+                points[count - 2],
+                half(last),
+                graph.nodes[last].shape,
+            );
+
+            let curves = points
+                .windows(2)
+                .map(|pair| {
+                    let (a, b) = (pair[0], pair[1]);
+                    let bend = (b.y - a.y) / 2.0;
+                    [
+                        map(Point {
+                            x: a.x,
+                            y: a.y + bend,
+                        }),
+                        map(Point {
+                            x: b.x,
+                            y: b.y - bend,
+                        }),
+                        map(b),
+                    ]
+                })
+                .collect();
+            EdgePath {
+                start: map(points[0]),
+                curves,
+                label: label_slots[index].map(|slot| map(position(slot))),
+            }
+        })
+        .collect();
+
+    let (width, height) = if transposed {
+        (length, breadth)
+    } else {
+        (breadth, length)
+    };
+    Layout {
+        width,
+        height,
+        nodes,
+        edges,
+    }
+}
+
+/// Find edges to reverse so the graph has no cycles: those that close a
+/// cycle in a depth-first search from the nodes in source order.
+fn break_cycles(graph: &Graph, links: &[usize]) -> Vec<bool> {
+    let count = graph.nodes.len();
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for &index in links {
+        outgoing[graph.edges[index].from].push(index);
+    }
+
+    // 0: unvisited, 1: on the search path, 2: finished
+    let mut state = vec![0u8; count];
+    let mut reversed = vec![false; graph.edges.len()];
+    for root in 0..count {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack = vec![(root, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let Some(&index) = outgoing[node].get(*next) else {
+                state[node] = 2;
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+            let target = graph.edges[index].to;
+            match state[target] {
+                0 => {
+                    state[target] = 1;
+                    stack.push((target, 0));
+                }
+                1 => reversed[index] = true,
+                _ => {}
+            }
+        }
+    }
+    reversed
+}
+
+/// Rank nodes by longest path from the sources, then move nodes with more
+/// outgoing than incoming links down towards their successors.
+fn rank(
+    count: usize,
+    links: &[usize],
+    ends: &impl Fn(usize) -> (usize, usize),
+    min_length: usize,
+) -> Vec<usize> {
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for &index in links {
+        let (upper, lower) = ends(index);
+        successors[upper].push(lower);
+        predecessors[lower].push(upper);
+    }
+
+    // Topological order, keeping source order among ready nodes
+    let mut incoming: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+    let mut ready: Vec<usize> = (0..count)
+        .rev()
+        .filter(|&node| incoming[node] == 0)
+        .collect();
+    let mut topological = Vec::with_capacity(count);
+    while let Some(node) = ready.pop() {
+        topological.push(node);
+        for &next in successors[node].iter().rev() {
+            incoming[next] -= 1;
+            if incoming[next] == 0 {
+                ready.push(next);
+            }
+        }
+    }
+
+    let mut ranks = vec![0usize; count];
+    for &node in &topological {
+        for &next in &successors[node] {
+            ranks[next] = ranks[next].max(ranks[node] + min_length);
+        }
+    }
+    for &node in topological.iter().rev() {
+        if successors[node].len() > predecessors[node].len()
+            && let Some(lowest) = successors[node].iter().map(|&next| ranks[next]).min()
+            && lowest - min_length > ranks[node]
+        {
+            ranks[node] = lowest - min_length;
+        }
+    }
+    ranks
+}
+
+/// Order each rank to reduce crossings.
+fn order(slots: &[Slot], rank_count: usize, node_count: usize) -> Vec<Vec<usize>> {
+    // Start from a depth-first order, which keeps chains together
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); rank_count];
+    let mut seen = vec![false; slots.len()];
+    for root in (0..node_count).filter(|&node| slots[node].up.is_empty()) {
+        let mut stack = vec![root];
+        while let Some(slot) = stack.pop() {
+            if std::mem::replace(&mut seen[slot], true) {
+                continue;
+            }
+            layers[slots[slot].rank].push(slot);
+            stack.extend(slots[slot].down.iter().rev());
+        }
+    }
+    for slot in 0..slots.len() {
+        if !seen[slot] {
+            layers[slots[slot].rank].push(slot);
+        }
+    }
+
+    let mut positions = vec![0usize; slots.len()];
+    let update = |layers: &[Vec<usize>], positions: &mut [usize]| {
+        for layer in layers {
+            for (position, &slot) in layer.iter().enumerate() {
+                positions[slot] = position;
+            }
+        }
+    };
+    update(&layers, &mut positions);
+
+    let large = slots.len() > LARGE;
+    let mut best = layers.clone();
+    let mut best_crossings = crossings(slots, &layers, &positions);
+    let iterations = if large { 4 } else { 24 };
+    for iteration in 0..iterations {
+        if best_crossings == 0 {
+            break;
+        }
+        let downward = iteration % 2 == 0;
+        let ranks: Vec<usize> = if downward {
+            (1..rank_count).collect()
+        } else {
+            (0..rank_count.saturating_sub(1)).rev().collect()
+        };
+        for rank in ranks {
+            let mut keyed: Vec<(f64, usize)> = layers[rank]
+                .iter()
+                .map(|&slot| {
+                    let neighbours = if downward {
+                        &slots[slot].up
+                    } else {
+                        &slots[slot].down
+                    };
+                    let key = if neighbours.is_empty() {
+                        positions[slot] as f64
+                    } else {
+                        neighbours.iter().map(|&n| positions[n] as f64).sum::<f64>()
+                            / neighbours.len() as f64
+                    };
+                    (key, slot)
+                })
+                .collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            layers[rank] = keyed.into_iter().map(|(_, slot)| slot).collect();
+            for (position, &slot) in layers[rank].iter().enumerate() {
+                positions[slot] = position;
+            }
+        }
+        if !large {
+            transpose(slots, &mut layers, &mut positions);
+        }
+
+        let count = crossings(slots, &layers, &positions);
+        if count < best_crossings {
+            best_crossings = count;
+            best = layers.clone();
+        }
+    }
+    best
+}
+
+/// Swap neighbours in a rank while that removes crossings.
+fn transpose(slots: &[Slot], layers: &mut [Vec<usize>], positions: &mut [usize]) {
+    // Crossings between the links of `a` and `b` when `a` is left of `b`
+    let pair_crossings = |a: usize, b: usize, positions: &[usize]| {
+        let mut count = 0;
+        for neighbours in [
+            (&slots[a].up, &slots[b].up),
+            (&slots[a].down, &slots[b].down),
+        ] {
+            for &na in neighbours.0 {
+                for &nb in neighbours.1 {
+                    if positions[na] > positions[nb] {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    };
+
+    for _ in 0..4 {
+        let mut improved = false;
+        for layer in layers.iter_mut() {
+            for i in 1..layer.len() {
+                let (a, b) = (layer[i - 1], layer[i]);
+                if pair_crossings(a, b, positions) > pair_crossings(b, a, positions) {
+                    layer.swap(i - 1, i);
+                    positions[a] = i;
+                    positions[b] = i - 1;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Total crossings between consecutive ranks.
+fn crossings(slots: &[Slot], layers: &[Vec<usize>], positions: &[usize]) -> usize {
+    let mut total = 0;
+    for pair in layers.windows(2) {
+        let mut links: Vec<(usize, usize)> = pair[0]
+            .iter()
+            .flat_map(|&slot| {
+                slots[slot]
+                    .down
+                    .iter()
+                    .map(move |&next| (positions[slot], positions[next]))
+            })
+            .collect();
+        links.sort_unstable();
+
+        // Count inversions of the lower ends with a Fenwick tree
+        let size = pair[1].len() + 1;
+        let mut tree = vec![0usize; size + 1];
+        for (seen, &(_, lower)) in links.iter().enumerate() {
+            let mut not_above = 0;
+            let mut i = lower + 1;
+            while i > 0 {
+                not_above += tree[i];
+                i -= i & i.wrapping_neg();
+            }
+            total += seen - not_above;
+            let mut i = lower + 1;
+            while i <= size {
+                tree[i] += 1;
+                i += i & i.wrapping_neg();
+            }
+        }
+    }
+    total
+}
+
+/// Assign x positions: pack each rank, then repeatedly pull slots towards
+/// the mean of their neighbours in the adjacent ranks.
+fn place(slots: &mut [Slot], layers: &mut [Vec<usize>]) {
+    let gap = |slots: &[Slot], a: usize, b: usize| {
+        let separation = match (slots[a].dummy, slots[b].dummy) {
+            (false, false) => NODE_SEP,
+            (true, true) => EDGE_SEP,
+            _ => (NODE_SEP + EDGE_SEP) / 2.0,
+        };
+        (slots[a].width + slots[b].width) / 2.0 + separation
+    };
+
+    for layer in layers.iter() {
+        let mut x = 0.0;
+        for (i, &slot) in layer.iter().enumerate() {
+            if i > 0 {
+                x += gap(slots, layer[i - 1], slot);
+            }
+            slots[slot].x = x;
+        }
+    }
+
+    for pass in 0..12 {
+        let order: Vec<usize> = if pass % 2 == 0 {
+            (0..layers.len()).collect()
+        } else {
+            (0..layers.len()).rev().collect()
+        };
+        for rank in order {
+            let layer = &layers[rank];
+            let desired: Vec<f64> = layer
+                .iter()
+                .map(|&slot| {
+                    let neighbours: Vec<usize> = match pass {
+                        11 => slots[slot]
+                            .up
+                            .iter()
+                            .chain(&slots[slot].down)
+                            .copied()
+                            .collect(),
+                        _ if pass % 2 == 0 => slots[slot].up.clone(),
+                        _ => slots[slot].down.clone(),
+                    };
+                    if neighbours.is_empty() {
+                        slots[slot].x
+                    } else {
+                        neighbours.iter().map(|&n| slots[n].x).sum::<f64>()
+                            / neighbours.len() as f64
+                    }
+                })
+                .collect();
+            // Dummies resist moving away from straight lines more
+            let weights: Vec<f64> = layer
+                .iter()
+                .map(|&slot| if slots[slot].dummy { 4.0 } else { 1.0 })
+                .collect();
+            let mut offsets = vec![0.0; layer.len()];
+            for i in 1..layer.len() {
+                offsets[i] = offsets[i - 1] + gap(slots, layer[i - 1], layer[i]);
+            }
+            let shifts = isotonic(
+                &desired
+                    .iter()
+                    .zip(&offsets)
+                    .map(|(desired, offset)| desired - offset)
+                    .collect::<Vec<_>>(),
+                &weights,
+            );
+            for (i, &slot) in layer.iter().enumerate() {
+                slots[slot].x = shifts[i] + offsets[i];
+            }
+        }
+    }
+
+    // Move the drawing against the margin
+    let left = slots
+        .iter()
+        .map(|slot| slot.x - slot.width / 2.0)
+        .fold(f64::INFINITY, f64::min);
+    if left.is_finite() {
+        for slot in slots.iter_mut() {
+            slot.x += MARGIN - left;
+        }
+    }
+}
+
+/// The non-decreasing sequence closest to `values` in weighted least
+/// squares, by pooling adjacent violators.
+fn isotonic(values: &[f64], weights: &[f64]) -> Vec<f64> {
+    // (total weight, weighted sum, length) of each pooled block
+    let mut blocks: Vec<(f64, f64, usize)> = Vec::new();
+    for (&value, &weight) in values.iter().zip(weights) {
+        blocks.push((weight, weight * value, 1));
+        while blocks.len() > 1 {
+            let last = blocks[blocks.len() - 1];
+            let previous = blocks[blocks.len() - 2];
+            if previous.1 / previous.0 <= last.1 / last.0 {
+                break;
+            }
+            blocks.pop();
+            let merged = blocks.last_mut().unwrap();
+            *merged = (merged.0 + last.0, merged.1 + last.1, merged.2 + last.2);
+        }
+    }
+    blocks
+        .into_iter()
+        .flat_map(|(weight, sum, length)| std::iter::repeat_n(sum / weight, length))
+        .collect()
+}
+
+/// Move `centre` to the border of its node on the way to `towards`.
+fn clip(
+    centre: Point,
+    towards: Point,
+    (half_width, half_height): (f64, f64),
+    shape: Shape,
+) -> Point {
+    let (dx, dy) = (towards.x - centre.x, towards.y - centre.y);
+    if dx == 0.0 && dy == 0.0 || half_width == 0.0 || half_height == 0.0 {
+        return centre;
+    }
+    let (x, y) = (dx / half_width, dy / half_height);
+    let scale = match shape {
+        Shape::Ellipse | Shape::Circle | Shape::DoubleCircle | Shape::Point => 1.0 / x.hypot(y),
+        Shape::Diamond => 1.0 / (x.abs() + y.abs()),
+        _ => 1.0 / x.abs().max(y.abs()),
+    };
+    if scale > 1.0 {
+        return centre;
+    }
+    Point {
+        x: centre.x + dx * scale,
+        y: centre.y + dy * scale,
+    }
+}
+
+/// A loop from the side of a node back to it, with its label beside it.
+fn self_loop(
+    centre: Point,
+    half: (f64, f64),
+    shape: Shape,
+    label_width: Option<f64>,
+    map: &impl Fn(Point) -> Point,
+) -> EdgePath {
+    let side = |dy: f64| {
+        clip(
+            centre,
+            Point {
+                x: centre.x + half.0,
+                y: centre.y + dy * half.1,
+            },
+            half,
+            shape,
+        )
+    };
+    let (start, end) = (side(-0.5), side(0.5));
+    let reach = centre.x + half.0 + LOOP_WIDTH;
+    EdgePath {
+        start: map(start),
+        curves: vec![[
+            map(Point {
+                x: reach,
+                y: start.y - 14.0,
+            }),
+            map(Point {
+                x: reach,
+                y: end.y + 14.0,
+            }),
+            map(end),
+        ]],
+        label: label_width.map(|width| {
+            map(Point {
+                x: reach + 4.0 + width / 2.0,
+                y: centre.y,
+            })
+        }),
+    }
+}
@@ -0,0 +1,510 @@
+//! Mermaid flowchart and state diagram parser.
+//!
+//! Covers node shapes, chained and `&` grouped links with labels, and `style`
+//! statements for flowcharts; transitions, `[*]`, aliases, descriptions and
+//! choice/fork states for state diagrams. Subgraphs and composite states are
+//! flattened, and class and interaction statements are ignored.
+
+use super::{DiagramError, Direction, Edge, Graph, Line, Shape, Style, colour, html_text};
+
+/// Parse a Mermaid flowchart or state diagram.
+pub(super) fn parse(source: &str) -> Result<Graph, DiagramError> {
+    let mut lines = source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("%%"))
+        .peekable();
+
+    // Front matter (`---` ... `---`) holds the title and configuration
+    let mut title = None;
+    if lines.peek().is_some_and(|(_, line)| *line == "---") {
+        lines.next();
+        for (_, line) in lines.by_ref() {
+            if line == "---" {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("title:") {
+                title = Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    let Some((number, header)) = lines.next() else {
+        return Err(DiagramError::new(1, "empty diagram"));
+    };
+    let (keyword, rest) = header
+        .split_once(char::is_whitespace)
+        .unwrap_or((header, ""));
+    let mut graph = Graph {
+        label: title.filter(|title| !title.is_empty()),
+        ..Graph::default()
+    };
+    match keyword {
+        "graph" | "flowchart" => {
+            // The direction may be followed by a first statement after `;`
+            let (direction, first) = rest.split_once(';').unwrap_or((rest, ""));
+            let direction = direction.trim();
+            if !direction.is_empty() {
+                graph.direction = Direction::parse(direction).ok_or_else(|| {
+                    DiagramError::new(number, format!("unknown direction `{}`", direction))
+                })?;
+            }
+            let mut flowchart = Flowchart { graph };
+            flowchart.line(number, first)?;
+            for (number, line) in lines {
+                flowchart.line(number, line)?;
+            }
+            Ok(flowchart.graph)
+        }
+        "stateDiagram" | "stateDiagram-v2" => {
+            let mut states = States { graph, note: false };
+            for (number, line) in lines {
+                states.line(number, line)?;
+            }
+            Ok(states.graph)
+        }
+        _ => Err(DiagramError::new(
+            number,
+            format!(
+                "unsupported Mermaid diagram `{}`; flowcharts and state diagrams are supported",
+                keyword
+            ),
+        )),
+    }
+}
+
+/// Split a line into statements at `;` outside quotes and brackets.
+fn statements(line: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut depth = 0i32;
+    let mut quoted = false;
+    let mut start = 0;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '[' | '(' | '{' if !quoted => depth += 1,
+            ']' | ')' | '}' if !quoted => depth -= 1,
+            ';' if !quoted && depth <= 0 => {
+                statements.push(&line[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&line[start..]);
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+/// Node and link text: quotes and Markdown backticks are removed and HTML
+/// line breaks kept.
+fn label_text(text: &str) -> String {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text);
+    let text = text
+        .strip_prefix('`')
+        .and_then(|text| text.strip_suffix('`'))
+        .unwrap_or(text);
+    html_text(text).replace("\\n", "\n")
+}
+
+struct Flowchart {
+    graph: Graph,
+}
+
+/// A link between node groups, such as `-- yes -->` or `-.->|maybe|`.
+struct Link {
+    label: Option<String>,
+    head: bool,
+    tail: bool,
+    line: Line,
+}
+
+/// Node shapes by their opening and closing delimiters, longest first.
+const SHAPES: &[(&str, &[&str], Shape)] = &[
+    ("(((", &[")))"], Shape::DoubleCircle),
+    ("((", &["))"], Shape::Circle),
+    ("([", &["])"], Shape::Stadium),
+    ("[[", &["]]"], Shape::Box),
+    ("[(", &[")]"], Shape::Box),
+    ("[/", &["/]", "\\]"], Shape::Box),
+    ("[\\", &["\\]", "/]"], Shape::Box),
+    ("{{", &["}}"], Shape::Hexagon),
+    ("(", &[")"], Shape::Rounded),
+    ("[", &["]"], Shape::Box),
+    ("{", &["}"], Shape::Diamond),
+    (">", &["]"], Shape::Box),
+];
+
+impl Flowchart {
+    fn line(&mut self, number: usize, line: &str) -> Result<(), DiagramError> {
+        for statement in statements(line) {
+            self.statement(number, statement)?;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, number: usize, statement: &str) -> Result<(), DiagramError> {
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        match keyword {
+            "style" => return self.style(statement),
+            "subgraph" | "end" | "direction" | "classDef" | "class" | "click" | "linkStyle"
+            | "accTitle" | "accDescr" | "title" => return Ok(()),
+            _ => {}
+        }
+
+        let mut cursor = Cursor {
+            text: statement,
+            pos: 0,
+            number,
+        };
+        let mut group = self.group(&mut cursor)?;
+        while let Some(link) = cursor.link()? {
+            let next = self.group(&mut cursor)?;
+            for &from in &group {
+                for &to in &next {
+                    self.graph.edges.push(Edge {
+                        from,
+                        to,
+                        label: link.label.clone(),
+                        head: link.head,
+                        tail: link.tail,
+                        style: Style {
+                            line: link.line,
+                            ..Style::default()
+                        },
+                    });
+                }
+            }
+            group = next;
+        }
+        cursor.skip_whitespace();
+        let rest = cursor.rest();
+        if !rest.is_empty() {
+            return Err(DiagramError::new(number, format!("unexpected `{}`", rest)));
+        }
+        Ok(())
+    }
+
+    /// Nodes joined with `&`.
+    fn group(&mut self, cursor: &mut Cursor) -> Result<Vec<usize>, DiagramError> {
+        let mut nodes = vec![self.node(cursor)?];
+        loop {
+            cursor.skip_whitespace();
+            if !cursor.eat("&") {
+                return Ok(nodes);
+            }
+            nodes.push(self.node(cursor)?);
+        }
+    }
+
+    /// A node ID, optionally followed by its shape and text.
+    fn node(&mut self, cursor: &mut Cursor) -> Result<usize, DiagramError> {
+        cursor.skip_whitespace();
+        let id = cursor.take_while(|c| c.is_alphanumeric() || c == '_');
+        if id.is_empty() {
+            return Err(cursor.error("a node"));
+        }
+        let index = self.graph.node(id, Shape::Box);
+
+        for &(open, closes, shape) in SHAPES {
+            if !cursor.rest().starts_with(open) {
+                continue;
+            }
+            cursor.pos += open.len();
+            let rest = cursor.rest();
+            // Quoted text may contain the closing delimiter
+            let search_from = if rest.trim_start().starts_with('"') {
+                let quote = rest.find('"').unwrap_or(0);
+                rest[quote + 1..].find('"').map_or(0, |end| quote + end + 2)
+            } else {
+                0
+            };
+            let Some((end, close)) = closes
+                .iter()
+                .filter_map(|close| {
+                    rest[search_from..]
+                        .find(close)
+                        .map(|end| (search_from + end, close))
+                })
+                .min()
+            else {
+                return Err(cursor.error(&format!("`{}` to close `{}`", closes[0], open)));
+            };
+            let node = &mut self.graph.nodes[index];
+            node.label = label_text(&rest[..end]);
+            node.shape = shape;
+            cursor.pos += end + close.len();
+            break;
+        }
+
+        // `:::class` is styling only
+        if cursor.eat(":::") {
+            cursor.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        }
+        Ok(index)
+    }
+
+    /// `style id fill:#f9f,stroke:#333,color:#000,stroke-dasharray:5 5`
+    fn style(&mut self, statement: &str) -> Result<(), DiagramError> {
+        let mut words = statement.splitn(3, char::is_whitespace).skip(1);
+        let (Some(id), Some(properties)) = (words.next(), words.next()) else {
+            return Ok(());
+        };
+        let index = self.graph.node(id, Shape::Box);
+        let style = &mut self.graph.nodes[index].style;
+        for property in properties.split(',') {
+            let Some((name, value)) = property.split_once(':') else {
+                continue;
+            };
+            match name.trim() {
+                "fill" => style.fill = colour(value),
+                "stroke" => style.stroke = colour(value),
+                "color" => style.text = colour(value),
+                "stroke-dasharray" => style.line = Line::Dashed,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+    number: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.rest().starts_with(text) {
+            self.pos += text.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, expected: &str) -> DiagramError {
+        let found = match self.rest().chars().next() {
+            Some(c) => format!("`{}`", c),
+            None => "end of line".to_string(),
+        };
+        DiagramError::new(
+            self.number,
+            format!("expected {}, found {}", expected, found),
+        )
+    }
+
+    /// A link such as `-->`, `---`, `-.->`, `==>`, `<-->`, `--o`, `~~~`, with
+    /// its label written inline (`-- text -->`) or after it (`-->|text|`).
+    fn link(&mut self) -> Result<Option<Link>, DiagramError> {
+        let start = self.pos;
+        self.skip_whitespace();
+        let tail = self.eat("<");
+        let body = self.take_while(|c| matches!(c, '-' | '=' | '.' | '~'));
+        if body.len() < 2 {
+            self.pos = start;
+            return Ok(None);
+        }
+
+        let mut label = None;
+        let mut body = body.to_string();
+        let mut head = self.arrowhead();
+        // `A -- text --> B`: the opening was `--`, `==` or `-.` with no head
+        if !head && matches!(body.as_str(), "--" | "==" | "-.") && self.rest().starts_with(' ') {
+            let rest = self.rest();
+            let Some(end) = ["-->", "---", "==>", "===", ".->", ".-", "--o", "--x"]
+                .iter()
+                .filter_map(|close| rest.find(close))
+                .min()
+            else {
+                return Err(self.error("the end of the link"));
+            };
+            label = Some(label_text(&rest[..end]));
+            self.pos += end;
+            body.push_str(self.take_while(|c| matches!(c, '-' | '=' | '.')));
+            head = self.arrowhead();
+        }
+
+        self.skip_whitespace();
+        if self.eat("|") {
+            let rest = self.rest();
+            let Some(end) = rest.find('|') else {
+                return Err(self.error("`|` to close the link text"));
+            };
+            label = Some(label_text(&rest[..end]));
+            self.pos += end + 1;
+        }
+
+        let line = if body.contains('~') {
+            Line::Invisible
+        } else if body.contains('.') {
+            Line::Dotted
+        } else if body.contains('=') {
+            Line::Bold
+        } else {
+            Line::Solid
+        };
+        Ok(Some(Link {
+            label: label.filter(|label| !label.is_empty()),
+            head,
+            tail,
+            line,
+        }))
+    }
+
+    /// `>`, or an `o`/`x` end which is not the start of a node ID.
+    fn arrowhead(&mut self) -> bool {
+        if self.eat(">") {
+            return true;
+        }
+        let mut chars = self.rest().chars();
+        if matches!(chars.next(), Some('o' | 'x')) && chars.next().is_none_or(|c| c.is_whitespace())
+        {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+}
+
+struct States {
+    graph: Graph,
+    /// Inside a multi-line `note`.
+    note: bool,
+}
+
+impl States {
+    fn line(&mut self, number: usize, line: &str) -> Result<(), DiagramError> {
+        if self.note {
+            self.note = line != "end note";
+            return Ok(());
+        }
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+        match keyword {
+            "note" => {
+                self.note = !line.contains(':');
+                return Ok(());
+            }
+            "direction" => {
+                if let Some(direction) = line.split_whitespace().nth(1).and_then(Direction::parse) {
+                    self.graph.direction = direction;
+                }
+                return Ok(());
+            }
+            "}" | "--" | "classDef" | "class" | "hide" | "scale" | "accTitle" | "accDescr" => {
+                return Ok(());
+            }
+            "state" => return self.state(number, line[5..].trim()),
+            _ => {}
+        }
+
+        if let Some((from, rest)) = line.split_once("-->") {
+            let (to, label) = match rest.split_once(':') {
+                Some((to, label)) => (to, Some(label_text(label))),
+                None => (rest, None),
+            };
+            let from = self.endpoint(number, from, true)?;
+            let to = self.endpoint(number, to, false)?;
+            self.graph.edges.push(Edge {
+                from,
+                to,
+                label: label.filter(|label| !label.is_empty()),
+                head: true,
+                tail: false,
+                style: Style::default(),
+            });
+        } else if let Some((id, description)) = line.split_once(':') {
+            let index = self.endpoint(number, id, false)?;
+            self.graph.nodes[index].label = label_text(description);
+        } else {
+            self.endpoint(number, line, false)?;
+        }
+        Ok(())
+    }
+
+    /// `state "Description" as Id`, `state Id <<choice>>` or `state Id {`.
+    fn state(&mut self, number: usize, rest: &str) -> Result<(), DiagramError> {
+        let rest = rest.trim_end_matches('{').trim();
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let Some((label, id)) = quoted.split_once('"') else {
+                return Err(DiagramError::new(
+                    number,
+                    "expected `\"` to close the state name",
+                ));
+            };
+            let id = id
+                .trim()
+                .strip_prefix("as")
+                .map(str::trim)
+                .unwrap_or_default();
+            let index = self.endpoint(number, id, false)?;
+            self.graph.nodes[index].label = label_text(label);
+            return Ok(());
+        }
+
+        let (id, kind) = match rest.split_once("<<") {
+            Some((id, kind)) => (id.trim(), kind.trim_end_matches(">>").trim()),
+            None => (rest, ""),
+        };
+        let index = self.endpoint(number, id, false)?;
+        let node = &mut self.graph.nodes[index];
+        match kind {
+            "choice" => {
+                node.shape = Shape::Diamond;
+                node.label = String::new();
+            }
+            "fork" | "join" => {
+                node.shape = Shape::Point;
+                node.label = String::new();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// A state, or `[*]` as the start (as a source) or end (as a target).
+    fn endpoint(&mut self, number: usize, id: &str, source: bool) -> Result<usize, DiagramError> {
+        let id = id.trim();
+        if id == "[*]" {
+            let (id, shape) = if source {
+                ("[*] start", Shape::Point)
+            } else {
+                ("[*] end", Shape::DoubleCircle)
+            };
+            let index = self.graph.node(id, shape);
+            self.graph.nodes[index].label = String::new();
+            return Ok(index);
+        }
+        if id.is_empty() || id.contains(char::is_whitespace) {
+            return Err(DiagramError::new(
+                number,
+                format!("invalid state name `{}`", id),
+            ));
+        }
+        Ok(self.graph.node(id, Shape::Rounded))
+    }
+}
@@ -0,0 +1,384 @@
+//! Graphviz DOT and Mermaid diagrams, laid out and drawn as SVG.
+//!
+//! Both languages are parsed into one [`Graph`], which is laid out in ranks
+//! (a Sugiyama style layout, as `dot` does) and written out as a standalone
+//! SVG. Nothing is bundled or fetched, and the SVG the page shows is the one
+//! it saves.
+
+mod dot;
+mod layout;
+mod mermaid;
+mod svg;
+
+use super::{page, script_json};
+use crate::{DiagramKind, WindowTheme};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A syntax error in a diagram source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagramError {
+    /// 1-based line of the error.
+    pub line: usize,
+
+    /// What went wrong.
+    pub message: String,
+}
+
+impl DiagramError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DiagramError {}
+
+/// Direction in which ranks follow each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Direction {
+    #[default]
+    TopBottom,
+    BottomTop,
+    LeftRight,
+    RightLeft,
+}
+
+impl Direction {
+    /// Parse a DOT `rankdir` or Mermaid direction such as `LR`.
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "TB" | "TD" => Some(Self::TopBottom),
+            "BT" => Some(Self::BottomTop),
+            "LR" => Some(Self::LeftRight),
+            "RL" => Some(Self::RightLeft),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Shape {
+    #[default]
+    Box,
+    Rounded,
+    Ellipse,
+    Circle,
+    DoubleCircle,
+    Diamond,
+    Hexagon,
+    Stadium,
+    Point,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Line {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+    Bold,
+    Invisible,
+}
+
+/// Colours and line style set by the source. Unset colours follow the theme.
+#[derive(Debug, Clone, Default)]
+struct Style {
+    stroke: Option<String>,
+    fill: Option<String>,
+    text: Option<String>,
+    line: Line,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: String,
+    label: String,
+    shape: Shape,
+    style: Style,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    /// Arrowhead at `to`.
+    head: bool,
+    /// Arrowhead at `from`.
+    tail: bool,
+    style: Style,
+}
+
+#[derive(Debug, Default)]
+struct Graph {
+    direction: Direction,
+    label: Option<String>,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    index: HashMap<String, usize>,
+}
+
+impl Graph {
+    /// Index of the node `id`, adding it with `shape` if it is new.
+    fn node(&mut self, id: &str, shape: Shape) -> usize {
+        if let Some(&index) = self.index.get(id) {
+            return index;
+        }
+        self.nodes.push(Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            shape,
+            style: Style::default(),
+        });
+        self.index.insert(id.to_string(), self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+}
+
+/// Parse a colour for use in a `style` attribute: `#rgb`, `#rrggbb(aa)`, a
+/// colour name, or Graphviz `h,s,v` with components in `0..=1`. Anything
+/// else is ignored so it cannot inject CSS.
+fn colour(value: &str) -> Option<String> {
+    // Graphviz colour lists ("red:blue") and weights ("red;0.3") use the first
+    let value = value.split([':', ';']).next()?.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return (matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| value.to_ascii_lowercase());
+    }
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Some(value.to_ascii_lowercase());
+    }
+
+    let hsv: Vec<f64> = value
+        .split([',', ' '])
+        .filter(|part| !part.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let [h, s, v] = hsv[..] else {
+        return None;
+    };
+    let channel = |n: f64| {
+        let k = (n + h.clamp(0.0, 1.0) * 6.0) % 6.0;
+        let value = v - v * s * k.min(4.0 - k).clamp(0.0, 1.0);
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(5.0),
+        channel(3.0),
+        channel(1.0)
+    ))
+}
+
+/// The text of an HTML-like label: `<br>` becomes a line break, other tags
+/// are dropped and the basic entities are decoded.
+fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim().to_ascii_lowercase();
+        if tag == "br" || tag.starts_with("br ") || tag.starts_with("br/") {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Lay out a diagram and return it as a standalone SVG document.
+pub fn diagram_svg(source: &str, kind: DiagramKind) -> Result<String, DiagramError> {
+    let graph = parse(source, kind)?;
+    let layout = layout::layout(&graph);
+    Ok(svg::write(&graph, &layout))
+}
+
+fn parse(source: &str, kind: DiagramKind) -> Result<Graph, DiagramError> {
+    match kind {
+        DiagramKind::Dot => dot::parse(source),
+        DiagramKind::Mermaid => mermaid::parse(source),
+    }
+}
+
+const DIAGRAM_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+body { display: flex; flex-direction: column; }
+#bar {
+    flex: none; display: flex; align-items: center; gap: 6px; padding: 6px 12px;
+    font-size: 12px; border-bottom: 1px solid var(--border);
+}
+#bar button {
+    font: inherit; color: var(--fg); background: var(--surface); min-width: 28px;
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 8px; cursor: pointer;
+}
+#zoom { color: var(--muted); min-width: 44px; text-align: right; font-variant-numeric: tabular-nums; }
+#save { margin-left: auto; }
+#viewport { flex: 1; position: relative; overflow: hidden; cursor: grab; }
+#viewport.dragging { cursor: grabbing; }
+#stage { position: absolute; left: 0; top: 0; transform-origin: 0 0; }
+#stage svg { display: block; }
+body .diagram { --d-fg: var(--fg); --d-bg: var(--bg); --d-node: var(--surface); --d-edge: var(--muted); }
+"#;
+
+const DIAGRAM_SCRIPT: &str = r#"
+(function () {
+    var data = JSON.parse(document.getElementById('data').textContent);
+    var viewport = document.getElementById('viewport');
+    var stage = document.getElementById('stage');
+    var svg = stage.querySelector('svg');
+    var width = data.width, height = data.height;
+    var scale = 1, x = 0, y = 0;
+    var fitted = true;
+
+    function apply() {
+        stage.style.transform = 'translate(' + x + 'px, ' + y + 'px) scale(' + scale + ')';
+        document.getElementById('zoom').textContent = Math.round(scale * 100) + '%';
+    }
+
+    // Centre the diagram, shrinking it to fit but never enlarging it
+    function fit() {
+        var margin = 24;
+        scale = Math.min(1, (viewport.clientWidth - margin) / width, (viewport.clientHeight - margin) / height);
+        scale = Math.max(scale, 0.02);
+        x = (viewport.clientWidth - width * scale) / 2;
+        y = (viewport.clientHeight - height * scale) / 2;
+        fitted = true;
+        apply();
+    }
+
+    function zoomAt(factor, px, py) {
+        var next = Math.min(8, Math.max(0.02, scale * factor));
+        x = px - (px - x) * next / scale;
+        y = py - (py - y) * next / scale;
+        scale = next;
+        fitted = false;
+        apply();
+    }
+
+    function zoomCentre(factor) {
+        zoomAt(factor, viewport.clientWidth / 2, viewport.clientHeight / 2);
+    }
+
+    viewport.addEventListener('wheel', function (event) {
+        event.preventDefault();
+        var rect = viewport.getBoundingClientRect();
+        if (event.ctrlKey || !event.shiftKey) {
+            zoomAt(Math.exp(-event.deltaY * 0.0015), event.clientX - rect.left, event.clientY - rect.top);
+        } else {
+            x -= event.deltaY;
+            fitted = false;
+            apply();
+        }
+    }, { passive: false });
+
+    var drag = null;
+    viewport.addEventListener('mousedown', function (event) {
+        drag = { x: event.clientX - x, y: event.clientY - y };
+        viewport.classList.add('dragging');
+    });
+    window.addEventListener('mousemove', function (event) {
+        if (!drag) return;
+        x = event.clientX - drag.x;
+        y = event.clientY - drag.y;
+        fitted = false;
+        apply();
+    });
+    window.addEventListener('mouseup', function () {
+        drag = null;
+        viewport.classList.remove('dragging');
+    });
+
+    function actualSize() {
+        zoomCentre(1 / scale);
+    }
+
+    function save() {
+        var internals = window.__TAURI_INTERNALS__;
+        if (!internals) return;
+        internals.invoke('export_file', { fileName: 'diagram.svg', contents: data.svg })
+            .catch(function (error) { console.error('Failed to save diagram:', error); });
+    }
+
+    document.getElementById('fit').addEventListener('click', fit);
+    document.getElementById('actual').addEventListener('click', actualSize);
+    document.getElementById('zoom-in').addEventListener('click', function () { zoomCentre(1.25); });
+    document.getElementById('zoom-out').addEventListener('click', function () { zoomCentre(0.8); });
+    document.getElementById('save').addEventListener('click', save);
+    document.addEventListener('keydown', function (event) {
+        if ((event.ctrlKey || event.metaKey) && event.key === 's') {
+            event.preventDefault();
+            save();
+        } else if (event.key === '+' || event.key === '=') {
+            zoomCentre(1.25);
+        } else if (event.key === '-') {
+            zoomCentre(0.8);
+        } else if (event.key === '0') {
+            fit();
+        } else if (event.key === '1') {
+            actualSize();
+        }
+    });
+    window.addEventListener('resize', function () {
+        if (fitted) fit();
+    });
+
+    svg.style.width = width + 'px';
+    svg.style.height = height + 'px';
+    fit();
+})();
+"#;
+
+/// Render a diagram into a page with pan, zoom and "Save SVG".
+///
+/// The SVG is saved with the viewer's `export_file` command. Its colours
+/// follow the page theme; saved on its own it follows the system theme.
+pub fn render_diagram(
+    source: &str,
+    kind: DiagramKind,
+    theme: &WindowTheme,
+) -> Result<String, DiagramError> {
+    let graph = parse(source, kind)?;
+    let layout = layout::layout(&graph);
+    let svg = svg::write(&graph, &layout);
+    let (width, height) = svg::size(&graph, &layout);
+
+    let data = script_json(&serde_json::json!({
+        "svg": svg,
+        "width": width,
+        "height": height,
+    }));
+    let body = format!(
+        r#"<div id="bar">
+<button id="fit" title="Fit to window (0)">Fit</button>
+<button id="actual" title="Actual size (1)">100%</button>
+<button id="zoom-out" title="Zoom out (-)">−</button>
+<button id="zoom-in" title="Zoom in (+)">+</button>
+<span id="zoom"></span>
+<button id="save" title="Save as SVG (Ctrl+S)">Save SVG</button>
+</div>
+<div id="viewport"><div id="stage">{svg}</div></div>
+<script id="data" type="application/json">{data}</script>
+<script>{DIAGRAM_SCRIPT}</script>"#
+    );
+    Ok(page(theme, DIAGRAM_STYLE, &body))
+}
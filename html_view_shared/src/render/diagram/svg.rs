@@ -0,0 +1,322 @@
+//! SVG output of a laid out [`Graph`].
+
+use super::layout::{EdgePath, LINE_HEIGHT, Layout, MARGIN, NodeBox, Point, text_size};
+use super::{Graph, Line, Shape, Style};
+use crate::render::escape_html;
+use std::fmt::Write;
+
+/// Length and half width of arrowheads.
+const ARROW_LENGTH: f64 = 9.0;
+const ARROW_WIDTH: f64 = 4.5;
+
+/// Colours as variables, so a page can match them to its theme. On its own
+/// the SVG follows the system theme.
+const SVG_STYLE: &str = r#"
+.diagram { --d-fg: #1f2328; --d-bg: #ffffff; --d-node: #f6f8fa; --d-edge: #59636e; }
+@media (prefers-color-scheme: dark) {
+  .diagram { --d-fg: #e6edf3; --d-bg: #0d1117; --d-node: #151b23; --d-edge: #9198a1; }
+}
+.diagram text { fill: var(--d-fg); font: 14px system-ui, -apple-system, "Segoe UI", Roboto, sans-serif; }
+.diagram .node .shape { fill: var(--d-node); stroke: var(--d-fg); stroke-width: 1.2; }
+.diagram .node .ring { fill: none; stroke: var(--d-fg); stroke-width: 1.2; }
+.diagram .node .dot { fill: var(--d-fg); stroke: none; }
+.diagram .edge path { fill: none; stroke: var(--d-edge); stroke-width: 1.3; }
+.diagram .edge polygon { fill: var(--d-edge); stroke: none; }
+.diagram .edge rect { fill: var(--d-bg); opacity: 0.9; }
+"#;
+
+/// Size of the drawing, including the graph label.
+pub(super) fn size(graph: &Graph, layout: &Layout) -> (f64, f64) {
+    match &graph.label {
+        Some(label) => {
+            let (width, height) = text_size(label);
+            (
+                layout.width.max(width + 2.0 * MARGIN),
+                layout.height + height + 8.0,
+            )
+        }
+        None => (layout.width, layout.height),
+    }
+}
+
+pub(super) fn write(graph: &Graph, layout: &Layout) -> String {
+    let (width, height) = size(graph, layout);
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" class="diagram" width="{:.0}" height="{:.0}" viewBox="0 0 {:.1} {:.1}">"#,
+        width.ceil(),
+        height.ceil(),
+        width,
+        height
+    );
+    let _ = write!(svg, "\n<style>{}</style>\n", SVG_STYLE);
+    if let Some(label) = &graph.label {
+        let _ = writeln!(svg, "<title>{}</title>", escape_html(label));
+    }
+
+    // Edges first so nodes cover their ends
+    for (edge, path) in graph.edges.iter().zip(&layout.edges) {
+        if edge.style.line == Line::Invisible {
+            continue;
+        }
+        let from = &graph.nodes[edge.from].id;
+        let to = &graph.nodes[edge.to].id;
+        let _ = writeln!(
+            svg,
+            r#"<g class="edge"><title>{} → {}</title>"#,
+            escape_html(from),
+            escape_html(to)
+        );
+        write_edge(&mut svg, path, edge.head, edge.tail, &edge.style);
+        if let (Some(label), Some(centre)) = (&edge.label, path.label) {
+            let (label_width, label_height) = text_size(label);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="3"/>"#,
+                centre.x - label_width / 2.0 - 3.0,
+                centre.y - label_height / 2.0,
+                label_width + 6.0,
+                label_height
+            );
+            write_text(&mut svg, label, centre, None);
+        }
+        svg.push_str("</g>\n");
+    }
+
+    for (node, position) in graph.nodes.iter().zip(&layout.nodes) {
+        if node.style.line == Line::Invisible {
+            continue;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<g class="node"><title>{}</title>"#,
+            escape_html(&node.id)
+        );
+        write_shape(&mut svg, node.shape, position, &node.style);
+        if !node.label.is_empty() && node.shape != Shape::Point {
+            write_text(
+                &mut svg,
+                &node.label,
+                position.centre,
+                node.style.text.as_deref(),
+            );
+        }
+        svg.push_str("</g>\n");
+    }
+
+    if let Some(label) = &graph.label {
+        let (_, label_height) = text_size(label);
+        write_text(
+            &mut svg,
+            label,
+            Point {
+                x: width / 2.0,
+                y: layout.height + label_height / 2.0,
+            },
+            None,
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Inline style for colours and line style set by the source.
+fn style_attribute(style: &Style, stroke_width: f64) -> String {
+    let mut css = String::new();
+    if let Some(stroke) = &style.stroke {
+        let _ = write!(css, "stroke:{};", stroke);
+    }
+    if let Some(fill) = &style.fill {
+        let _ = write!(css, "fill:{};", fill);
+    }
+    match style.line {
+        Line::Dashed => css.push_str("stroke-dasharray:6 4;"),
+        Line::Dotted => css.push_str("stroke-dasharray:1.5 3;"),
+        Line::Bold => {
+            let _ = write!(css, "stroke-width:{};", stroke_width * 2.0);
+        }
+        Line::Solid | Line::Invisible => {}
+    }
+    if css.is_empty() {
+        String::new()
+    } else {
+        format!(r#" style="{}""#, escape_html(&css))
+    }
+}
+
+fn write_shape(svg: &mut String, shape: Shape, node: &NodeBox, style: &Style) {
+    let Point { x, y } = node.centre;
+    let (w, h) = (node.width / 2.0, node.height / 2.0);
+    let css = style_attribute(style, 1.2);
+    let _ = match shape {
+        Shape::Box => writeln!(
+            svg,
+            r#"<rect class="shape" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}"{css}/>"#,
+            x - w,
+            y - h,
+            node.width,
+            node.height
+        ),
+        Shape::Rounded | Shape::Stadium => writeln!(
+            svg,
+            r#"<rect class="shape" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="{:.1}"{css}/>"#,
+            x - w,
+            y - h,
+            node.width,
+            node.height,
+            if shape == Shape::Stadium { h } else { 8.0 }
+        ),
+        Shape::Ellipse => writeln!(
+            svg,
+            r#"<ellipse class="shape" cx="{x:.1}" cy="{y:.1}" rx="{w:.1}" ry="{h:.1}"{css}/>"#
+        ),
+        Shape::Circle => writeln!(
+            svg,
+            r#"<circle class="shape" cx="{x:.1}" cy="{y:.1}" r="{w:.1}"{css}/>"#
+        ),
+        Shape::DoubleCircle => writeln!(
+            svg,
+            r#"<circle class="shape" cx="{x:.1}" cy="{y:.1}" r="{w:.1}"{css}/><circle class="ring" cx="{x:.1}" cy="{y:.1}" r="{:.1}"{css}/>"#,
+            w - 4.0
+        ),
+        Shape::Diamond => writeln!(
+            svg,
+            r#"<polygon class="shape" points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}"{css}/>"#,
+            x,
+            y - h,
+            x + w,
+            y,
+            x,
+            y + h,
+            x - w,
+            y
+        ),
+        Shape::Hexagon => {
+            let inset = 14.0f64.min(w);
+            writeln!(
+                svg,
+                r#"<polygon class="shape" points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}"{css}/>"#,
+                x - w + inset,
+                y - h,
+                x + w - inset,
+                y - h,
+                x + w,
+                y,
+                x + w - inset,
+                y + h,
+                x - w + inset,
+                y + h,
+                x - w,
+                y
+            )
+        }
+        Shape::Point => writeln!(
+            svg,
+            r#"<circle class="dot" cx="{x:.1}" cy="{y:.1}" r="{w:.1}"{css}/>"#
+        ),
+        Shape::Plain => Ok(()),
+    };
+}
+
+fn write_text(svg: &mut String, text: &str, centre: Point, colour: Option<&str>) {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let top = centre.y - (lines.len() - 1) as f64 * LINE_HEIGHT / 2.0;
+    let fill = colour
+        .map(|colour| format!(r#" style="fill:{}""#, escape_html(colour)))
+        .unwrap_or_default();
+    let _ = write!(
+        svg,
+        r#"<text text-anchor="middle" dominant-baseline="central"{fill}>"#
+    );
+    for (index, line) in lines.iter().enumerate() {
+        let _ = write!(
+            svg,
+            r#"<tspan x="{:.1}" y="{:.1}">{}</tspan>"#,
+            centre.x,
+            top + index as f64 * LINE_HEIGHT,
+            escape_html(line)
+        );
+    }
+    svg.push_str("</text>\n");
+}
+
+fn write_edge(svg: &mut String, path: &EdgePath, head: bool, tail: bool, style: &Style) {
+    let mut start = path.start;
+    let mut curves = path.curves.clone();
+    let mut arrows = Vec::new();
+
+    // Shorten the ends under arrowheads so the line does not poke through
+    if head && let Some(last) = curves.last_mut() {
+        let from = if last[1] != last[2] { last[1] } else { last[0] };
+        if let Some(direction) = unit(from, last[2]) {
+            arrows.push(arrowhead(last[2], direction));
+            last[2] = offset(last[2], direction, -ARROW_LENGTH);
+            last[1] = offset(last[1], direction, -ARROW_LENGTH);
+        }
+    }
+    if tail && let Some(first) = curves.first_mut() {
+        let towards = if first[0] != start {
+            first[0]
+        } else {
+            first[1]
+        };
+        if let Some(direction) = unit(towards, start) {
+            arrows.push(arrowhead(start, direction));
+            start = offset(start, direction, -ARROW_LENGTH);
+            first[0] = offset(first[0], direction, -ARROW_LENGTH);
+        }
+    }
+
+    let mut d = format!("M{:.1},{:.1}", start.x, start.y);
+    for [c1, c2, end] in &curves {
+        let _ = write!(
+            d,
+            " C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+            c1.x, c1.y, c2.x, c2.y, end.x, end.y
+        );
+    }
+    let _ = writeln!(svg, r#"<path d="{}"{}/>"#, d, style_attribute(style, 1.3));
+
+    let fill = style
+        .stroke
+        .as_ref()
+        .map(|stroke| format!(r#" style="fill:{}""#, escape_html(stroke)))
+        .unwrap_or_default();
+    for [a, b, c] in arrows {
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}"{fill}/>"#,
+            a.x, a.y, b.x, b.y, c.x, c.y
+        );
+    }
+}
+
+fn unit(from: Point, to: Point) -> Option<Point> {
+    let length = (to.x - from.x).hypot(to.y - from.y);
+    (length > 1e-6).then(|| Point {
+        x: (to.x - from.x) / length,
+        y: (to.y - from.y) / length,
+    })
+}
+
+fn offset(point: Point, direction: Point, distance: f64) -> Point {
+    Point {
+        x: point.x + direction.x * distance,
+        y: point.y + direction.y * distance,
+    }
+}
+
+/// An arrowhead with its tip at `tip`, pointing along `direction`.
+fn arrowhead(tip: Point, direction: Point) -> [Point; 3] {
+    let base = offset(tip, direction, -ARROW_LENGTH);
+    let normal = Point {
+        x: -direction.y,
+        y: direction.x,
+    };
+    [
+        tip,
+        offset(base, normal, ARROW_WIDTH),
+        offset(base, normal, -ARROW_WIDTH),
+    ]
+}
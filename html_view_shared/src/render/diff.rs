@@ -0,0 +1,259 @@
+//! Line diff view, unified or side by side.
+
+use super::code::LINES_STYLE;
+use super::{highlight_by_line, highlight_css, page};
+use crate::{DiffMode, WindowTheme};
+use similar::{DiffTag, TextDiff};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// Unchanged lines kept around each change when folding.
+const CONTEXT: usize = 3;
+
+const DIFF_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+#bar {
+    position: fixed; left: 0; right: 0; top: 0; height: 36px; box-sizing: border-box;
+    display: flex; align-items: center; gap: 8px; padding: 0 12px; font-size: 12px;
+    border-bottom: 1px solid var(--border);
+}
+#bar button {
+    font: inherit; color: var(--fg); background: var(--surface);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 10px; cursor: pointer;
+}
+#bar button.active { border-color: var(--link); color: var(--link); }
+#stats { margin-right: auto; font-variant-numeric: tabular-nums; }
+#stats .added { color: #1a7f37; }
+#stats .removed { color: #cf222e; }
+#diff { position: fixed; left: 0; right: 0; top: 36px; bottom: 0; overflow: auto; }
+#diff[data-mode="unified"] table.split, #diff[data-mode="side_by_side"] table.unified { display: none; }
+table.split { table-layout: fixed; }
+table.split td:not(.ln) { width: 50%; white-space: pre-wrap; overflow-wrap: anywhere; }
+table.lines td.sign { width: 1%; padding: 0 4px; user-select: none; color: var(--muted); }
+table.lines td.sign::before { content: attr(data-sign); }
+table.lines tr.added td, table.lines td.added { background: rgba(46, 160, 67, 0.15); }
+table.lines tr.removed td, table.lines td.removed { background: rgba(248, 81, 73, 0.15); }
+table.lines td.none { background: var(--surface); }
+table.lines tbody.folded { display: none; }
+table.lines tr.fold td {
+    background: var(--surface); color: var(--muted); cursor: pointer; text-align: center;
+    font-family: system-ui, sans-serif; font-size: 12px; padding: 2px 0;
+}
+table.lines tr.fold:hover td { color: var(--link); }
+table.lines tr.current td.ln { box-shadow: inset 3px 0 var(--link); }
+"#;
+
+const DIFF_SCRIPT: &str = r#"
+(function () {
+    var diff = document.getElementById('diff');
+    var modes = document.querySelectorAll('#bar button[data-mode]');
+    var current = -1;
+
+    function setMode(mode) {
+        diff.dataset.mode = mode;
+        Array.prototype.forEach.call(modes, function (button) {
+            button.classList.toggle('active', button.dataset.mode === mode);
+        });
+        current = -1;
+    }
+    Array.prototype.forEach.call(modes, function (button) {
+        button.addEventListener('click', function () { setMode(button.dataset.mode); });
+    });
+
+    // Clicking a fold reveals the unchanged lines hidden behind it
+    diff.addEventListener('click', function (event) {
+        var fold = event.target.closest('tr.fold');
+        if (!fold) return;
+        var group = fold.parentNode;
+        group.nextElementSibling.classList.remove('folded');
+        group.remove();
+    });
+
+    function jump(step) {
+        var table = diff.querySelector(diff.dataset.mode === 'unified' ? 'table.unified' : 'table.split');
+        var hunks = table.querySelectorAll('tr.hunk');
+        if (!hunks.length) return;
+        if (current >= 0 && hunks[current]) hunks[current].classList.remove('current');
+        current = (current + step + hunks.length) % hunks.length;
+        hunks[current].classList.add('current');
+        hunks[current].scrollIntoView({ block: 'center' });
+    }
+    document.getElementById('next').addEventListener('click', function () { jump(1); });
+    document.getElementById('prev').addEventListener('click', function () { jump(-1); });
+    document.addEventListener('keydown', function (event) {
+        if (event.ctrlKey || event.metaKey || event.altKey) return;
+        if (event.key === 'n' || event.key === 'j') jump(1);
+        if (event.key === 'p' || event.key === 'k') jump(-1);
+    });
+
+    setMode(diff.dataset.mode);
+})();
+"#;
+
+/// A run of lines that are equal, removed, added or replaced.
+struct Hunk {
+    tag: DiffTag,
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+/// Render a line diff between `old` and `new`.
+///
+/// Both sides are highlighted for `language`, runs of unchanged lines away
+/// from changes are folded, and the page can switch between unified and
+/// side-by-side layouts, starting with `mode`.
+pub fn render_diff(
+    old: &str,
+    new: &str,
+    language: Option<&str>,
+    mode: DiffMode,
+    theme: &WindowTheme,
+) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let hunks: Vec<Hunk> = diff
+        .ops()
+        .iter()
+        .map(|op| {
+            let (tag, old, new) = op.as_tag_tuple();
+            Hunk { tag, old, new }
+        })
+        .collect();
+    let changed = hunks.iter().any(|hunk| hunk.tag != DiffTag::Equal);
+
+    let old_lines = highlight_by_line(old, language);
+    let new_lines = highlight_by_line(new, language);
+    let line = |lines: &[String], index: usize| lines.get(index).cloned().unwrap_or_default();
+
+    let mut unified = String::new();
+    let mut split = String::new();
+    let mut added = 0;
+    let mut removed = 0;
+
+    for (position, hunk) in hunks.iter().enumerate() {
+        if hunk.tag == DiffTag::Equal {
+            let first = position == 0;
+            let last = position == hunks.len() - 1;
+            let rows = |range: Range<usize>, unified: &mut String, split: &mut String| {
+                for offset in range {
+                    let (o, n) = (hunk.old.start + offset, hunk.new.start + offset);
+                    let html = line(&new_lines, n);
+                    let _ = write!(
+                        unified,
+                        r#"<tr><td class="ln" data-line="{}"></td><td class="ln" data-line="{}"></td><td class="sign"></td><td>{html}</td></tr>"#,
+                        o + 1,
+                        n + 1
+                    );
+                    let _ = write!(
+                        split,
+                        r#"<tr><td class="ln" data-line="{}"></td><td>{}</td><td class="ln" data-line="{}"></td><td>{html}</td></tr>"#,
+                        o + 1,
+                        line(&old_lines, o),
+                        n + 1
+                    );
+                }
+            };
+
+            let len = hunk.old.len();
+            let head = if first { 0 } else { CONTEXT.min(len) };
+            let tail = if last { 0 } else { CONTEXT.min(len - head) };
+            if !changed || len - head - tail <= 1 {
+                rows(0..len, &mut unified, &mut split);
+                continue;
+            }
+
+            rows(0..head, &mut unified, &mut split);
+            let fold = format!(
+                r#"</tbody><tbody><tr class="fold"><td colspan="4">Show {} unchanged lines</td></tr></tbody><tbody class="folded">"#,
+                len - head - tail
+            );
+            unified.push_str(&fold);
+            split.push_str(&fold);
+            rows(head..len - tail, &mut unified, &mut split);
+            unified.push_str("</tbody><tbody>");
+            split.push_str("</tbody><tbody>");
+            rows(len - tail..len, &mut unified, &mut split);
+            continue;
+        }
+
+        removed += hunk.old.len();
+        added += hunk.new.len();
+
+        let mut hunk_class = " hunk";
+        for o in hunk.old.clone() {
+            let _ = write!(
+                unified,
+                r#"<tr class="removed{hunk_class}"><td class="ln" data-line="{}"></td><td class="ln"></td><td class="sign" data-sign="-"></td><td>{}</td></tr>"#,
+                o + 1,
+                line(&old_lines, o)
+            );
+            hunk_class = "";
+        }
+        for n in hunk.new.clone() {
+            let _ = write!(
+                unified,
+                r#"<tr class="added{hunk_class}"><td class="ln"></td><td class="ln" data-line="{}"></td><td class="sign" data-sign="+"></td><td>{}</td></tr>"#,
+                n + 1,
+                line(&new_lines, n)
+            );
+            hunk_class = "";
+        }
+
+        // Side by side pairs removed and added lines up; the shorter side
+        // is padded with empty cells.
+        for row in 0..hunk.old.len().max(hunk.new.len()) {
+            let class = if row == 0 { r#" class="hunk""# } else { "" };
+            let _ = write!(split, "<tr{class}>");
+            for (range, lines, side) in [
+                (&hunk.old, &old_lines, "removed"),
+                (&hunk.new, &new_lines, "added"),
+            ] {
+                if row < range.len() {
+                    let index = range.start + row;
+                    let _ = write!(
+                        split,
+                        r#"<td class="ln {side}" data-line="{}"></td><td class="{side}">{}</td>"#,
+                        index + 1,
+                        line(lines, index)
+                    );
+                } else {
+                    split.push_str(r#"<td class="ln none"></td><td class="none"></td>"#);
+                }
+            }
+            split.push_str("</tr>");
+        }
+    }
+
+    let mode = match mode {
+        DiffMode::Unified => "unified",
+        DiffMode::SideBySide => "side_by_side",
+    };
+    let mut stats = if changed {
+        format!(
+            r#"<span class="added">+{added}</span> <span class="removed">&minus;{removed}</span>"#
+        )
+    } else {
+        "No differences".to_string()
+    };
+    // Otherwise the last lines would differ without a visible reason
+    if !old.is_empty() && !new.is_empty() && old.ends_with('\n') != new.ends_with('\n') {
+        let side = if old.ends_with('\n') { "new" } else { "old" };
+        stats.push_str(&format!(" &middot; no newline at end of {side} text"));
+    }
+    let body = format!(
+        r#"<div id="bar">
+<span id="stats">{stats}</span>
+<button id="prev" title="Previous change (p)">&uarr;</button>
+<button id="next" title="Next change (n)">&darr;</button>
+<button data-mode="unified">Unified</button>
+<button data-mode="side_by_side">Side by side</button>
+</div>
+<div id="diff" data-mode="{mode}">
+<table class="lines unified hl-code"><tbody>{unified}</tbody></table>
+<table class="lines split hl-code"><colgroup><col style="width: 4em"><col><col style="width: 4em"><col></colgroup><tbody>{split}</tbody></table>
+</div>
+<script>{DIFF_SCRIPT}</script>"#
+    );
+
+    let style = format!("{LINES_STYLE}{DIFF_STYLE}{}", highlight_css(theme));
+    page(theme, &style, &body)
+}
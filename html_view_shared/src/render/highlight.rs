@@ -0,0 +1,101 @@
+//! Syntax highlighting for code blocks.
+
+use super::escape_html;
+use crate::WindowTheme;
+use std::sync::LazyLock;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator, css_for_theme_with_class_style};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEMES: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Prefix for highlighting classes, so they cannot clash with page styles.
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+const LIGHT_THEME: &str = "InspiredGitHub";
+const DARK_THEME: &str = "base16-ocean.dark";
+
+/// Highlight `code` as HTML spans for `language`.
+///
+/// `language` may be a name, alias or file extension such as `rust` or `py`.
+/// Unknown or missing languages produce escaped, unhighlighted text.
+pub fn highlight(code: &str, language: Option<&str>) -> String {
+    let Some(syntax) = language.and_then(|language| SYNTAXES.find_syntax_by_token(language)) else {
+        return escape_html(code);
+    };
+
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAXES, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if generator
+            .parse_html_for_line_which_includes_newline(line)
+            .is_err()
+        {
+            return escape_html(code);
+        }
+    }
+    generator.finalize()
+}
+
+/// Highlight `code` like [`highlight`], split into one HTML fragment per
+/// line.
+///
+/// Spans that cross a line break are closed at the end of the line and
+/// reopened on the next, so every fragment is balanced and can be placed in
+/// its own table cell. Line terminators are not included.
+pub fn highlight_by_line(code: &str, language: Option<&str>) -> Vec<String> {
+    let html = highlight(code, language);
+    let mut lines = Vec::with_capacity(code.lines().count());
+    let mut open: Vec<&str> = Vec::new();
+    let mut current = String::new();
+    let mut rest = html.as_str();
+
+    // The generated HTML only holds `<span class="...">`, `</span>` and
+    // escaped text, so `<` always starts a tag.
+    while let Some(index) = rest.find(['<', '\n']) {
+        current.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(after) = rest.strip_prefix('\n') {
+            current.truncate(current.trim_end_matches('\r').len());
+            current.extend(open.iter().map(|_| "</span>"));
+            lines.push(std::mem::replace(&mut current, open.concat()));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("</span>") {
+            open.pop();
+            current.push_str("</span>");
+            rest = after;
+        } else {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            open.push(&rest[..end]);
+            current.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    current.push_str(rest);
+    if !code.is_empty() && !code.ends_with('\n') {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Stylesheet for the classes emitted by [`highlight`], following `theme`.
+pub fn highlight_css(theme: &WindowTheme) -> String {
+    let light = theme_css(LIGHT_THEME);
+    let dark = theme_css(DARK_THEME);
+    match theme {
+        WindowTheme::Light => light,
+        WindowTheme::Dark => dark,
+        WindowTheme::System => {
+            format!("{light}\n@media (prefers-color-scheme: dark) {{\n{dark}\n}}")
+        }
+    }
+}
+
+fn theme_css(name: &str) -> String {
+    THEMES
+        .themes
+        .get(name)
+        .and_then(|theme: &Theme| css_for_theme_with_class_style(theme, CLASS_STYLE).ok())
+        .unwrap_or_default()
+}
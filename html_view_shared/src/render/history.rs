@@ -0,0 +1,195 @@
+//! Timeline of refreshed contents, each shown in a frame.
+
+use super::page;
+use crate::WindowTheme;
+
+const HISTORY_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+body { display: flex; flex-direction: column; }
+#panes { flex: 1; min-height: 0; display: flex; }
+#panes section { flex: 1; min-width: 0; display: flex; flex-direction: column; }
+#panes section + section { border-left: 1px solid var(--border); }
+#panes .label {
+    flex: none; padding: 4px 10px; font-size: 12px; color: var(--muted);
+    background: var(--surface); border-bottom: 1px solid var(--border);
+}
+#panes .frames { flex: 1; min-height: 0; position: relative; }
+#panes iframe { position: absolute; inset: 0; width: 100%; height: 100%; border: none; }
+#panes iframe.behind { visibility: hidden; }
+#bar {
+    flex: none; display: flex; align-items: center; gap: 6px; padding: 6px 10px;
+    background: var(--surface); border-top: 1px solid var(--border);
+    font-size: 13px; user-select: none;
+}
+#bar button {
+    min-width: 30px; padding: 3px 10px; font: inherit; color: var(--fg);
+    background: var(--bg); border: 1px solid var(--border); border-radius: 6px; cursor: pointer;
+}
+#bar button:hover:not(:disabled) { border-color: var(--muted); }
+#bar button:disabled { color: var(--muted); cursor: default; }
+#bar button[aria-pressed="true"] { background: var(--border); }
+#timeline { flex: 1; min-width: 80px; accent-color: var(--link); }
+#counter { min-width: 150px; text-align: center; color: var(--muted); font-variant-numeric: tabular-nums; }
+"#;
+
+const HISTORY_SCRIPT: &str = r#"
+(function () {
+    var frames = document.getElementById('frames');
+    var pinnedPane = document.getElementById('pinned');
+    var pinnedLabel = document.getElementById('pinned-label');
+    var pinnedFrames = document.getElementById('pinned-frames');
+    var currentLabel = document.getElementById('current-label');
+    var timeline = document.getElementById('timeline');
+    var back = document.getElementById('back');
+    var forward = document.getElementById('forward');
+    var counter = document.getElementById('counter');
+    var latest = document.getElementById('latest');
+    var pin = document.getElementById('pin');
+    var internals = window.__TAURI_INTERNALS__;
+    var entries = [];
+    var viewed = null;
+    var pinned = null;
+    var following = true;
+
+    function indexOf(key) {
+        for (var i = 0; i < entries.length; i++) {
+            if (entries[i].key === key) return i;
+        }
+        return -1;
+    }
+
+    function describe(entry) {
+        var text = '#' + (entry.key + 1);
+        if (entry.received) text += ' · ' + new Date(entry.received).toLocaleTimeString();
+        return text;
+    }
+
+    function frameOf(entry) {
+        if (!entry.frame) {
+            entry.frame = document.createElement('iframe');
+            entry.frame.title = 'Version ' + (entry.key + 1);
+            entry.frame.className = 'behind';
+            entry.frame.src = entry.src;
+            frames.appendChild(entry.frame);
+        }
+        return entry.frame;
+    }
+
+    function render() {
+        var index = indexOf(viewed);
+        var entry = entries[index];
+        if (entry) frameOf(entry);
+        entries.forEach(function (other) {
+            if (other.frame) other.frame.classList.toggle('behind', other !== entry);
+        });
+        timeline.max = Math.max(0, entries.length - 1);
+        timeline.value = Math.max(0, index);
+        timeline.disabled = entries.length < 2;
+        back.disabled = index <= 0;
+        forward.disabled = index < 0 || index >= entries.length - 1;
+        counter.textContent = entry
+            ? describe(entry) + ' (' + (index + 1) + ' / ' + entries.length + ')'
+            : 'No versions';
+        latest.setAttribute('aria-pressed', following ? 'true' : 'false');
+
+        var comparing = pinned !== null;
+        pinnedPane.hidden = !comparing;
+        currentLabel.hidden = !comparing;
+        pin.setAttribute('aria-pressed', comparing ? 'true' : 'false');
+        pin.textContent = comparing ? 'Unpin' : 'Pin';
+        if (comparing) {
+            var pinnedEntry = entries[indexOf(pinned)];
+            pinnedLabel.textContent = 'Pinned ' + describe(pinnedEntry);
+            if (entry) currentLabel.textContent = 'Showing ' + describe(entry);
+        }
+    }
+
+    function view(index) {
+        if (!entries.length) return;
+        index = Math.max(0, Math.min(entries.length - 1, index));
+        viewed = entries[index].key;
+        // Moving to the newest version follows the versions still to come
+        following = index === entries.length - 1;
+        render();
+    }
+
+    function setPinned(key) {
+        pinned = key;
+        while (pinnedFrames.firstChild) pinnedFrames.removeChild(pinnedFrames.firstChild);
+        if (key !== null) {
+            var frame = document.createElement('iframe');
+            frame.title = 'Pinned version ' + (key + 1);
+            frame.src = entries[indexOf(key)].src;
+            pinnedFrames.appendChild(frame);
+        }
+        render();
+    }
+
+    // Bring the versions in line with `list`, the versions the viewer keeps,
+    // oldest first. Frames of versions still kept are reused.
+    function sync(list) {
+        var old = entries;
+        entries = list.map(function (item) {
+            for (var i = 0; i < old.length; i++) {
+                if (old[i].key === item.key) return old[i];
+            }
+            return { key: item.key, src: item.src, received: item.received, frame: null };
+        });
+        old.forEach(function (entry) {
+            if (entry.frame && indexOf(entry.key) < 0) entry.frame.remove();
+        });
+        if (pinned !== null && indexOf(pinned) < 0) setPinned(null);
+        if (following || indexOf(viewed) < 0) {
+            view(following ? entries.length - 1 : 0);
+        } else {
+            render();
+        }
+    }
+
+    timeline.addEventListener('input', function () { view(+timeline.value); });
+    back.addEventListener('click', function () { view(indexOf(viewed) - 1); });
+    forward.addEventListener('click', function () { view(indexOf(viewed) + 1); });
+    latest.addEventListener('click', function () { view(entries.length - 1); });
+    pin.addEventListener('click', function () {
+        setPinned(pinned === null && viewed !== null ? viewed : null);
+    });
+
+    window.__htmlViewHistory = { sync: sync };
+    render();
+
+    // The versions are fetched rather than embedded, so versions added
+    // while the page was loading are not lost
+    if (internals) {
+        internals.invoke('history_list').then(sync).catch(function (e) {
+            counter.textContent = 'Failed to load the history: ' + e;
+        });
+    }
+})();
+"#;
+
+/// Render the page showing a timeline of refreshed contents, for
+/// [`BehaviourOptions::refresh_history`](crate::BehaviourOptions::refresh_history).
+///
+/// The page fetches the versions kept with the viewer's `history_list`
+/// command, showing each in a frame, and is brought up to date through
+/// `window.__htmlViewHistory.sync(versions)`. It follows the newest version
+/// unless an older one is being viewed, and shows a pinned version beside
+/// the viewed one to compare them.
+pub fn render_history(theme: &WindowTheme) -> String {
+    let body = format!(
+        r#"<div id="panes">
+<section id="pinned" hidden><div class="label" id="pinned-label"></div><div class="frames" id="pinned-frames"></div></section>
+<section><div class="label" id="current-label" hidden></div><div class="frames" id="frames"></div></section>
+</div>
+<div id="bar">
+<button id="back" title="Previous version">‹</button>
+<input id="timeline" type="range" min="0" max="0" value="0" aria-label="Version">
+<button id="forward" title="Next version">›</button>
+<span id="counter"></span>
+<button id="latest" title="Show and follow the newest version" aria-pressed="true">Latest</button>
+<button id="pin" title="Pin this version to compare it with others" aria-pressed="false">Pin</button>
+</div>
+<script>{HISTORY_SCRIPT}</script>"#
+    );
+    page(theme, HISTORY_STYLE, &body)
+}
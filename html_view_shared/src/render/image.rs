@@ -0,0 +1,233 @@
+//! Image viewer page with zoom, pan and a pixel readout.
+
+use super::{escape_html, page};
+use crate::WindowTheme;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use std::path::Path;
+
+const IMAGE_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+#viewport {
+    position: fixed; inset: 0; top: 36px; overflow: hidden; cursor: grab;
+    background: var(--surface);
+}
+#viewport.dragging { cursor: grabbing; }
+#image {
+    position: absolute; left: 0; top: 0; transform-origin: 0 0; user-select: none;
+    background-color: #ffffff;
+    background-image: conic-gradient(#cccccc 25%, #ffffff 0 50%, #cccccc 0 75%, #ffffff 0);
+    background-size: 16px 16px;
+    -webkit-user-drag: none;
+}
+#image.pixelated { image-rendering: pixelated; }
+#controls {
+    position: fixed; left: 0; right: 0; top: 0; height: 36px; box-sizing: border-box;
+    display: flex; align-items: center; gap: 8px; padding: 0 10px;
+    border-bottom: 1px solid var(--border); font-size: 12px;
+}
+#controls button {
+    background: var(--surface); color: var(--fg); border: 1px solid var(--border);
+    border-radius: 4px; padding: 2px 10px; cursor: pointer;
+}
+#controls span { color: var(--muted); font-variant-numeric: tabular-nums; }
+#swatch { width: 14px; height: 14px; border: 1px solid var(--border); border-radius: 2px; }
+#readout { margin-left: auto; }
+"#;
+
+const IMAGE_SCRIPT: &str = r#"
+(function () {
+    var viewport = document.getElementById('viewport');
+    var image = document.getElementById('image');
+    var zoomLabel = document.getElementById('zoom');
+    var readout = document.getElementById('readout');
+    var swatch = document.getElementById('swatch');
+    var state = { scale: 1, x: 0, y: 0, fit: true };
+    var pixels = null;
+
+    function apply() {
+        image.style.transform =
+            'translate(' + state.x + 'px,' + state.y + 'px) scale(' + state.scale + ')';
+        image.classList.toggle('pixelated', state.scale >= 4);
+        zoomLabel.textContent = Math.round(state.scale * 100) + '%';
+    }
+    function place(scale) {
+        state.scale = scale;
+        state.x = (viewport.clientWidth - image.naturalWidth * scale) / 2;
+        state.y = (viewport.clientHeight - image.naturalHeight * scale) / 2;
+        apply();
+    }
+    function fit() {
+        state.fit = true;
+        var scale = Math.min(
+            viewport.clientWidth / image.naturalWidth,
+            viewport.clientHeight / image.naturalHeight,
+            1
+        );
+        place(scale > 0 && isFinite(scale) ? scale : 1);
+    }
+    function actual() {
+        state.fit = false;
+        place(1);
+    }
+    function zoomAt(factor, cx, cy) {
+        var scale = Math.min(Math.max(state.scale * factor, 0.02), 64);
+        state.x = cx - (cx - state.x) * (scale / state.scale);
+        state.y = cy - (cy - state.y) * (scale / state.scale);
+        state.scale = scale;
+        state.fit = false;
+        apply();
+    }
+
+    image.addEventListener('load', function () {
+        document.getElementById('dimensions').textContent =
+            image.naturalWidth + ' × ' + image.naturalHeight;
+        try {
+            var canvas = document.createElement('canvas');
+            canvas.width = image.naturalWidth;
+            canvas.height = image.naturalHeight;
+            var context = canvas.getContext('2d');
+            context.drawImage(image, 0, 0);
+            pixels = context.getImageData(0, 0, canvas.width, canvas.height);
+        } catch (e) {
+            pixels = null;
+        }
+        fit();
+    });
+    if (image.complete && image.naturalWidth) image.dispatchEvent(new Event('load'));
+
+    document.getElementById('fit').addEventListener('click', fit);
+    document.getElementById('actual').addEventListener('click', actual);
+    window.addEventListener('resize', function () { if (state.fit) fit(); });
+
+    viewport.addEventListener('wheel', function (e) {
+        e.preventDefault();
+        var rect = viewport.getBoundingClientRect();
+        zoomAt(Math.exp(-e.deltaY * 0.002), e.clientX - rect.left, e.clientY - rect.top);
+    }, { passive: false });
+
+    var drag = null;
+    viewport.addEventListener('mousedown', function (e) {
+        drag = { x: e.clientX - state.x, y: e.clientY - state.y };
+        viewport.classList.add('dragging');
+    });
+    window.addEventListener('mouseup', function () {
+        drag = null;
+        viewport.classList.remove('dragging');
+    });
+    viewport.addEventListener('mousemove', function (e) {
+        if (drag) {
+            state.x = e.clientX - drag.x;
+            state.y = e.clientY - drag.y;
+            state.fit = false;
+            apply();
+        }
+        var rect = viewport.getBoundingClientRect();
+        var px = Math.floor((e.clientX - rect.left - state.x) / state.scale);
+        var py = Math.floor((e.clientY - rect.top - state.y) / state.scale);
+        if (px < 0 || py < 0 || px >= image.naturalWidth || py >= image.naturalHeight) {
+            readout.textContent = '';
+            swatch.style.background = 'transparent';
+            return;
+        }
+        var text = px + ', ' + py;
+        if (pixels) {
+            var i = (py * pixels.width + px) * 4;
+            var d = pixels.data;
+            var hex = '#' + [d[i], d[i + 1], d[i + 2]].map(function (v) {
+                return v.toString(16).padStart(2, '0');
+            }).join('');
+            text += '   rgba(' + d[i] + ', ' + d[i + 1] + ', ' + d[i + 2] + ', ' +
+                (d[i + 3] / 255).toFixed(2) + ')   ' + hex;
+            swatch.style.background =
+                'rgba(' + d[i] + ',' + d[i + 1] + ',' + d[i + 2] + ',' + d[i + 3] / 255 + ')';
+        }
+        readout.textContent = text;
+    });
+    viewport.addEventListener('mouseleave', function () {
+        readout.textContent = '';
+        swatch.style.background = 'transparent';
+    });
+
+    window.addEventListener('keydown', function (e) {
+        if (e.ctrlKey || e.metaKey) return;
+        var cx = viewport.clientWidth / 2, cy = viewport.clientHeight / 2;
+        if (e.key === '0') fit();
+        else if (e.key === '1') actual();
+        else if (e.key === '+' || e.key === '=') zoomAt(1.25, cx, cy);
+        else if (e.key === '-') zoomAt(0.8, cx, cy);
+    });
+})();
+"#;
+
+/// Detect the MIME type of encoded image data.
+///
+/// The data's magic bytes are checked first, then the extension of `path`.
+/// Falls back to `application/octet-stream`.
+pub fn image_mime(data: &[u8], path: Option<&Path>) -> &'static str {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+    ];
+
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| data.starts_with(signature))
+    {
+        return mime;
+    }
+    if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if data.len() >= 12 && &data[4..8] == b"ftyp" && &data[8..12] == b"avif" {
+        return "image/avif";
+    }
+
+    let extension = path
+        .and_then(Path::extension)
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("bmp") => "image/bmp",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("ico") => "image/x-icon",
+        Some("svg") => "image/svg+xml",
+        _ if looks_like_svg(data) => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+fn looks_like_svg(data: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&data[..data.len().min(512)]);
+    head.contains("<svg")
+}
+
+/// Render an image viewer page for encoded image `data` of type `mime`.
+///
+/// The image is embedded as a data URL, so the page needs no file access and
+/// pixel colours can be read back from a canvas.
+pub fn render_image(data: &[u8], mime: &str, theme: &WindowTheme) -> String {
+    let body = format!(
+        r#"<div id="controls">
+<button id="fit">Fit</button>
+<button id="actual">1:1</button>
+<span id="zoom">100%</span>
+<span id="dimensions"></span>
+<div id="swatch"></div>
+<span id="readout"></span>
+</div>
+<div id="viewport"><img id="image" alt="" draggable="false" src="data:{mime};base64,{data}"></div>
+<script>{IMAGE_SCRIPT}</script>"#,
+        mime = escape_html(mime),
+        data = STANDARD.encode(data),
+    );
+    page(theme, IMAGE_STYLE, &body)
+}
@@ -0,0 +1,355 @@
+//! Collapsible tree explorer for JSON values.
+
+use super::{page, script_json};
+use crate::WindowTheme;
+
+const JSON_STYLE: &str = r#"
+#controls {
+    position: sticky; top: 0; z-index: 1; display: flex; align-items: center; gap: 8px;
+    padding: 8px 12px; background: var(--bg); border-bottom: 1px solid var(--border);
+    font-size: 12px;
+}
+#controls input {
+    flex: 1; max-width: 320px; padding: 4px 8px; font: inherit; color: var(--fg);
+    background: var(--surface); border: 1px solid var(--border); border-radius: 4px;
+}
+#controls button, #controls select {
+    font: inherit; color: var(--fg); background: var(--surface);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 8px; cursor: pointer;
+}
+#controls span { color: var(--muted); font-variant-numeric: tabular-nums; }
+#tree { padding: 8px 12px; font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px; }
+.children { padding-left: 18px; border-left: 1px dotted var(--border); margin-left: 5px; }
+.row { display: flex; align-items: baseline; gap: 6px; white-space: nowrap; border-radius: 3px; }
+.row:hover { background: var(--surface); }
+.row.current { outline: 2px solid var(--link); }
+.toggle { width: 12px; color: var(--muted); cursor: pointer; user-select: none; flex: none; }
+.key { color: #8250df; }
+.index, .range { color: var(--muted); }
+.range { cursor: pointer; }
+.string { color: #0a3069; white-space: pre-wrap; word-break: break-all; }
+.number { color: #0550ae; }
+.boolean, .null { color: #cf222e; }
+.preview { color: var(--muted); overflow: hidden; text-overflow: ellipsis; }
+.copy {
+    visibility: hidden; border: none; background: none; color: var(--muted);
+    cursor: pointer; padding: 0 4px; font: inherit;
+}
+.row:hover .copy { visibility: visible; }
+.hit { background: rgba(255, 200, 0, 0.35); border-radius: 2px; }
+:root[data-theme="dark"] .key { color: #d2a8ff; }
+:root[data-theme="dark"] .string { color: #a5d6ff; }
+:root[data-theme="dark"] .number { color: #79c0ff; }
+:root[data-theme="dark"] .boolean, :root[data-theme="dark"] .null { color: #ff7b72; }
+@media (prefers-color-scheme: dark) {
+    :root[data-theme="system"] .key { color: #d2a8ff; }
+    :root[data-theme="system"] .string { color: #a5d6ff; }
+    :root[data-theme="system"] .number { color: #79c0ff; }
+    :root[data-theme="system"] .boolean, :root[data-theme="system"] .null { color: #ff7b72; }
+}
+"#;
+
+const JSON_SCRIPT: &str = r#"
+(function () {
+    // Containers with more entries than this are split into index ranges,
+    // and children are only put in the DOM once their parent is expanded,
+    // so large values stay responsive.
+    var CHUNK = 100;
+    var EXPAND_BUDGET = 5000;
+    var MAX_MATCHES = 10000;
+
+    var data = JSON.parse(document.getElementById('data').textContent);
+    var tree = document.getElementById('tree');
+    var search = document.getElementById('search');
+    var counter = document.getElementById('count');
+
+    function isContainer(value) {
+        return value !== null && typeof value === 'object';
+    }
+    function childPath(path, key, isIndex) {
+        if (isIndex) return path + '[' + key + ']';
+        if (/^[A-Za-z_$][A-Za-z0-9_$]*$/.test(key)) return path + '.' + key;
+        return path + '[' + JSON.stringify(key) + ']';
+    }
+    function entry(container, keys, index, path) {
+        var key = keys ? keys[index] : index;
+        return {
+            kind: 'value', key: key, index: index, isIndex: !keys,
+            value: container[key], path: childPath(path, key, !keys)
+        };
+    }
+    function group(container, keys, start, end, path) {
+        var items = [];
+        var count = end - start;
+        if (count <= CHUNK) {
+            for (var i = start; i < end; i++) items.push(entry(container, keys, i, path));
+            return items;
+        }
+        var span = CHUNK;
+        while (count / span > CHUNK) span *= CHUNK;
+        for (var s = start; s < end; s += span) {
+            items.push({
+                kind: 'range', container: container, keys: keys,
+                start: s, end: Math.min(s + span, end), path: path
+            });
+        }
+        return items;
+    }
+    function children(item) {
+        if (item.kind === 'range') {
+            return group(item.container, item.keys, item.start, item.end, item.path);
+        }
+        var keys = Array.isArray(item.value) ? null : Object.keys(item.value);
+        var count = keys ? keys.length : item.value.length;
+        return group(item.value, keys, 0, count, item.path);
+    }
+
+    function text(tag, cls, content) {
+        var el = document.createElement(tag);
+        if (cls) el.className = cls;
+        el.textContent = content;
+        return el;
+    }
+    function preview(value) {
+        if (Array.isArray(value)) return 'Array(' + value.length + ')';
+        var keys = Object.keys(value);
+        var shown = keys.slice(0, 5).join(', ');
+        return '{' + shown + (keys.length > 5 ? ', …' : '') + '}  ' + keys.length + ' keys';
+    }
+    function primitive(value) {
+        if (value === null) return text('span', 'null', 'null');
+        if (typeof value === 'string') return text('span', 'string', JSON.stringify(value));
+        return text('span', typeof value, String(value));
+    }
+
+    function copy(path, button) {
+        function done() {
+            button.textContent = '✓';
+            setTimeout(function () { button.textContent = '⧉'; }, 800);
+        }
+        function fallback() {
+            var area = document.createElement('textarea');
+            area.value = path;
+            document.body.appendChild(area);
+            area.select();
+            document.execCommand('copy');
+            area.remove();
+            done();
+        }
+        if (navigator.clipboard && navigator.clipboard.writeText) {
+            navigator.clipboard.writeText(path).then(done, fallback);
+        } else {
+            fallback();
+        }
+    }
+
+    function node(item) {
+        var el = document.createElement('div');
+        el.item = item;
+        var row = document.createElement('div');
+        row.className = 'row';
+        el.appendChild(row);
+
+        var expandable = item.kind === 'range' || isContainer(item.value);
+        var toggle = text('span', 'toggle', expandable ? '▸' : '');
+        row.appendChild(toggle);
+
+        if (item.kind === 'range') {
+            row.appendChild(text('span', 'range', '[' + item.start + ' … ' + (item.end - 1) + ']'));
+        } else {
+            if (item.key !== null) {
+                row.appendChild(text('span', item.isIndex ? 'index' : 'key', String(item.key)));
+                row.appendChild(text('span', 'index', ':'));
+            }
+            if (expandable) row.appendChild(text('span', 'preview', preview(item.value)));
+            else row.appendChild(primitive(item.value));
+
+            var button = text('button', 'copy', '⧉');
+            button.title = 'Copy path ' + item.path;
+            button.addEventListener('click', function (e) {
+                e.stopPropagation();
+                copy(item.path, button);
+            });
+            row.appendChild(button);
+        }
+
+        if (expandable) {
+            row.style.cursor = 'pointer';
+            row.addEventListener('click', function () {
+                setExpanded(el, !el.expanded);
+            });
+        }
+        return el;
+    }
+
+    function setExpanded(el, expanded) {
+        var item = el.item;
+        if (!(item.kind === 'range' || isContainer(item.value))) return 0;
+        el.expanded = expanded;
+        el.firstChild.firstChild.textContent = expanded ? '▾' : '▸';
+        var created = 0;
+        if (expanded && !el.childrenEl) {
+            el.childrenEl = document.createElement('div');
+            el.childrenEl.className = 'children';
+            var fragment = document.createDocumentFragment();
+            children(item).forEach(function (child) {
+                fragment.appendChild(node(child));
+                created++;
+            });
+            el.childrenEl.appendChild(fragment);
+            el.appendChild(el.childrenEl);
+        }
+        if (el.childrenEl) el.childrenEl.style.display = expanded ? '' : 'none';
+        return created;
+    }
+
+    function expandToDepth(el, depth, budget) {
+        var isRange = el.item.kind === 'range';
+        if (depth <= 0 && !isRange) {
+            setExpanded(el, false);
+            return budget;
+        }
+        if (budget <= 0) return budget;
+        budget -= setExpanded(el, true);
+        if (!el.childrenEl) return budget;
+        var next = isRange ? depth : depth - 1;
+        Array.prototype.forEach.call(el.childrenEl.children, function (child) {
+            budget = expandToDepth(child, next, budget);
+        });
+        return budget;
+    }
+
+    var rootEl = node({ kind: 'value', key: null, value: data, path: '$' });
+    tree.appendChild(rootEl);
+    expandToDepth(rootEl, 1, EXPAND_BUDGET);
+
+    document.getElementById('depth').addEventListener('change', function (e) {
+        var depth = e.target.value === 'all' ? Infinity : Number(e.target.value);
+        expandToDepth(rootEl, depth, EXPAND_BUDGET);
+    });
+
+    // Search walks the data rather than the DOM, then reveals each match
+    // by expanding the nodes on its path.
+    var matches = [];
+    var current = -1;
+
+    function findMatches(query) {
+        var found = [];
+        var stack = [];
+        function pushChildren(value, steps) {
+            var keys = Array.isArray(value) ? null : Object.keys(value);
+            var count = keys ? keys.length : value.length;
+            // Pushed in reverse so matches come out in document order
+            for (var i = count - 1; i >= 0; i--) {
+                var key = keys ? keys[i] : i;
+                stack.push({ key: keys ? key : null, value: value[key], steps: steps.concat([i]) });
+            }
+        }
+        if (isContainer(data)) pushChildren(data, []);
+        while (stack.length && found.length < MAX_MATCHES) {
+            var top = stack.pop();
+            var keyHit = top.key !== null && top.key.toLowerCase().indexOf(query) !== -1;
+            var valueHit = !isContainer(top.value) &&
+                String(top.value).toLowerCase().indexOf(query) !== -1;
+            if (keyHit || valueHit) found.push(top.steps);
+            if (isContainer(top.value)) pushChildren(top.value, top.steps);
+        }
+        return found;
+    }
+
+    function reveal(steps) {
+        var el = rootEl;
+        for (var s = 0; s < steps.length; s++) {
+            setExpanded(el, true);
+            var index = steps[s];
+            var next = null;
+            while (!next) {
+                var found = Array.prototype.find.call(el.childrenEl.children, function (child) {
+                    var item = child.item;
+                    return item.kind === 'range'
+                        ? index >= item.start && index < item.end
+                        : item.index === index;
+                });
+                if (!found) return null;
+                if (found.item.kind === 'range') {
+                    setExpanded(found, true);
+                    el = found;
+                } else {
+                    next = found;
+                }
+            }
+            el = next;
+        }
+        return el;
+    }
+
+    function show(position) {
+        var previous = tree.querySelector('.row.current');
+        if (previous) previous.classList.remove('current');
+        if (!matches.length) {
+            counter.textContent = search.value ? '0 matches' : '';
+            return;
+        }
+        current = (position + matches.length) % matches.length;
+        counter.textContent = (current + 1) + ' / ' + matches.length +
+            (matches.length >= MAX_MATCHES ? '+' : '');
+        var el = reveal(matches[current]);
+        if (el) {
+            el.firstChild.classList.add('current');
+            el.firstChild.scrollIntoView({ block: 'center' });
+        }
+    }
+
+    var pending = null;
+    search.addEventListener('input', function () {
+        clearTimeout(pending);
+        pending = setTimeout(function () {
+            var query = search.value.trim().toLowerCase();
+            matches = query ? findMatches(query) : [];
+            show(0);
+        }, 150);
+    });
+    search.addEventListener('keydown', function (e) {
+        if (e.key === 'Enter') show(current + (e.shiftKey ? -1 : 1));
+    });
+    document.getElementById('next').addEventListener('click', function () { show(current + 1); });
+    document.getElementById('prev').addEventListener('click', function () { show(current - 1); });
+    window.addEventListener('keydown', function (e) {
+        if ((e.ctrlKey || e.metaKey) && e.key === 'f') {
+            e.preventDefault();
+            search.focus();
+            search.select();
+        }
+    });
+})();
+"#;
+
+/// Render an interactive tree explorer for `value`.
+///
+/// Nodes are collapsible and their children are created lazily, large
+/// arrays and objects are split into index ranges, and the page offers
+/// search, copy-path and expand-to-depth.
+pub fn render_json(value: &serde_json::Value, theme: &WindowTheme) -> String {
+    let json = script_json(value);
+
+    let body = format!(
+        r#"<div id="controls">
+<input id="search" type="search" placeholder="Search keys and values" spellcheck="false">
+<button id="prev" title="Previous match (Shift+Enter)">↑</button>
+<button id="next" title="Next match (Enter)">↓</button>
+<span id="count"></span>
+<label>Expand <select id="depth">
+<option value="0">collapsed</option>
+<option value="1" selected>1 level</option>
+<option value="2">2 levels</option>
+<option value="3">3 levels</option>
+<option value="5">5 levels</option>
+<option value="all">all</option>
+</select></label>
+</div>
+<div id="tree"></div>
+<script id="data" type="application/json">{json}</script>
+<script>{JSON_SCRIPT}</script>"#
+    );
+    page(theme, JSON_STYLE, &body)
+}
@@ -0,0 +1,62 @@
+//! Grid of frames showing other content.
+
+use super::{escape_html, page};
+use crate::{Layout, WindowTheme};
+
+const LAYOUT_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+#grid { display: grid; gap: 1px; height: 100%; }
+#grid iframe {
+    width: 100%; height: 100%; min-width: 0; min-height: 0; border: none;
+    outline: 1px solid var(--border);
+}
+"#;
+
+const LAYOUT_SCRIPT: &str = r#"
+(function () {
+    var frames = document.querySelectorAll('#grid iframe');
+    window.__htmlViewLayout = {
+        // Setting the source reloads the frame even when it is unchanged,
+        // which picks up content replaced under the same URL
+        update: function (index, src) {
+            if (frames[index]) frames[index].src = src;
+        }
+    };
+})();
+"#;
+
+/// Render the page holding the grid of a
+/// [`ViewerContent::Layout`](crate::ViewerContent::Layout).
+///
+/// `srcs` are the URLs the cells' frames open, in the order of
+/// `layout.cells`. The viewer replaces a cell's frame through
+/// `window.__htmlViewLayout.update(index, src)`.
+pub fn render_layout(layout: &Layout, srcs: &[String], theme: &WindowTheme) -> String {
+    let frames: String = layout
+        .cells
+        .iter()
+        .zip(srcs)
+        .map(|(cell, src)| {
+            format!(
+                r#"<iframe title="Cell {row}, {column}" src="{src}" style="grid-area: {top} / {left} / span {rows} / span {columns}"></iframe>"#,
+                row = cell.row,
+                column = cell.column,
+                src = escape_html(src),
+                top = cell.row + 1,
+                left = cell.column + 1,
+                rows = cell.row_span,
+                columns = cell.column_span,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = format!(
+        r#"<div id="grid" style="grid-template-rows: repeat({rows}, minmax(0, 1fr)); grid-template-columns: repeat({columns}, minmax(0, 1fr))">
+{frames}
+</div>
+<script>{LAYOUT_SCRIPT}</script>"#,
+        rows = layout.rows,
+        columns = layout.columns,
+    );
+    page(theme, LAYOUT_STYLE, &body)
+}
@@ -0,0 +1,214 @@
+//! Append-only log view.
+
+use super::{page, script_json};
+use crate::WindowTheme;
+
+/// Lines a log view keeps when the content does not set a limit.
+pub const DEFAULT_LOG_LINES: usize = 10_000;
+
+const LOG_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+#bar {
+    position: fixed; left: 0; right: 0; top: 0; height: 36px; box-sizing: border-box;
+    display: flex; align-items: center; gap: 10px; padding: 0 12px; font-size: 12px;
+    border-bottom: 1px solid var(--border);
+}
+#filter {
+    width: 220px; font: inherit; color: var(--fg); background: var(--bg);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 6px;
+}
+#bar label { display: flex; align-items: center; gap: 3px; user-select: none; }
+#bar button {
+    font: inherit; color: var(--fg); background: var(--surface);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 10px; cursor: pointer;
+}
+#count { color: var(--muted); margin-left: auto; font-variant-numeric: tabular-nums; }
+#output {
+    position: fixed; left: 0; right: 0; top: 36px; bottom: 0; overflow: auto;
+    padding: 6px 12px; box-sizing: border-box; line-height: 18px;
+    font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px;
+}
+.line { white-space: pre-wrap; overflow-wrap: anywhere; min-height: 18px; }
+.line.hidden { display: none; }
+.line.error { color: #cf222e; }
+.line.warn { color: #9a6700; }
+.line.debug, .line.trace { color: var(--muted); }
+:root[data-theme="dark"] .line.error { color: #ff7b72; }
+:root[data-theme="dark"] .line.warn { color: #d29922; }
+@media (prefers-color-scheme: dark) {
+    :root[data-theme="system"] .line.error { color: #ff7b72; }
+    :root[data-theme="system"] .line.warn { color: #d29922; }
+}
+#follow {
+    position: fixed; right: 24px; bottom: 16px; display: none; font-size: 12px;
+    color: var(--bg); background: var(--link); border: none; border-radius: 12px;
+    padding: 4px 12px; cursor: pointer; box-shadow: 0 1px 4px rgba(0, 0, 0, 0.3);
+}
+#follow.visible { display: block; }
+"#;
+
+const LOG_SCRIPT: &str = r#"
+(function () {
+    var MAX_LINES = JSON.parse(document.getElementById('data').textContent).maxLines;
+    var LEVEL = /\b(FATAL|ERROR|WARN(?:ING)?|INFO|DEBUG|TRACE)\b/;
+    var PREFIX = /^\s*[\[<]?(fatal|error|err|warn(?:ing)?|info|debug|trace)\b/i;
+
+    var output = document.getElementById('output');
+    var filter = document.getElementById('filter');
+    var counter = document.getElementById('count');
+    var follow = document.getElementById('follow');
+    var internals = window.__TAURI_INTERNALS__;
+
+    var next = 0;
+    var fetching = false;
+    var following = true;
+    var unseen = 0;
+    var needle = '';
+    var hiddenLevels = {};
+
+    function levelOf(text) {
+        var match = LEVEL.exec(text) || PREFIX.exec(text);
+        if (!match) return 'other';
+        var level = match[1].toLowerCase();
+        if (level === 'fatal' || level === 'err') return 'error';
+        if (level === 'warning') return 'warn';
+        return level;
+    }
+
+    function visible(line) {
+        return !hiddenLevels[line.dataset.level] &&
+            (!needle || line.textContent.toLowerCase().indexOf(needle) !== -1);
+    }
+
+    function updateCount() {
+        var total = output.children.length;
+        var shown = total - output.querySelectorAll('div.line.hidden').length;
+        counter.textContent = shown === total ? total + ' lines' : shown + ' of ' + total + ' lines';
+    }
+
+    function add(entries) {
+        var fragment = document.createDocumentFragment();
+        var added = 0;
+        entries.forEach(function (entry) {
+            var parts = entry.text !== undefined ? entry.text.split('\n') : [null];
+            parts.forEach(function (text) {
+                var div = document.createElement('div');
+                div.className = 'line';
+                if (text === null) div.innerHTML = entry.html;
+                else div.textContent = text;
+                var level = levelOf(div.textContent);
+                div.dataset.level = level;
+                div.classList.add(level);
+                if (!visible(div)) div.classList.add('hidden');
+                fragment.appendChild(div);
+                added++;
+            });
+        });
+        output.appendChild(fragment);
+        while (output.children.length > MAX_LINES) output.removeChild(output.firstChild);
+        updateCount();
+
+        if (following) {
+            output.scrollTop = output.scrollHeight;
+        } else {
+            unseen += added;
+            follow.textContent = '↓ ' + unseen + ' new';
+            follow.classList.add('visible');
+        }
+    }
+
+    // Entries are numbered by the viewer. Duplicates are skipped, and a gap
+    // (entries sent before this page was ready) is filled from the viewer.
+    function receive(first, entries, fetched) {
+        if (first > next) {
+            if (!fetched) return fetch();
+            next = first;
+        }
+        entries = entries.slice(next - first);
+        if (!entries.length) return;
+        next += entries.length;
+        add(entries);
+    }
+
+    function fetch() {
+        if (fetching || !internals) return;
+        fetching = true;
+        internals.invoke('log_entries', { from: next }).then(function (batch) {
+            fetching = false;
+            receive(batch.first, batch.entries, true);
+        }, function () { fetching = false; });
+    }
+
+    window.__htmlViewLog = { push: function (first, entries) { receive(first, entries, false); } };
+
+    output.addEventListener('scroll', function () {
+        following = output.scrollHeight - output.scrollTop - output.clientHeight < 4;
+        if (following) {
+            unseen = 0;
+            follow.classList.remove('visible');
+        }
+    });
+    follow.addEventListener('click', function () {
+        output.scrollTop = output.scrollHeight;
+    });
+
+    function refilter() {
+        Array.prototype.forEach.call(output.children, function (div) {
+            div.classList.toggle('hidden', !visible(div));
+        });
+        updateCount();
+    }
+    var pending = null;
+    filter.addEventListener('input', function () {
+        clearTimeout(pending);
+        pending = setTimeout(function () {
+            needle = filter.value.toLowerCase();
+            refilter();
+        }, 150);
+    });
+    Array.prototype.forEach.call(document.querySelectorAll('#bar input[data-level]'), function (box) {
+        box.addEventListener('change', function () {
+            hiddenLevels[box.dataset.level] = !box.checked;
+            refilter();
+        });
+    });
+    document.getElementById('clear').addEventListener('click', function () {
+        output.textContent = '';
+        unseen = 0;
+        follow.classList.remove('visible');
+        updateCount();
+    });
+
+    updateCount();
+    fetch();
+})();
+"#;
+
+/// Render an empty log view that keeps at most `max_lines` lines.
+///
+/// Entries arrive through `window.__htmlViewLog.push(first, entries)`, where
+/// `first` is the running index of the first entry. Missed entries are
+/// fetched with the viewer's `log_entries` command. Each line's level is
+/// detected from words such as `ERROR` or a leading `warn:`.
+pub fn render_log(max_lines: usize, theme: &WindowTheme) -> String {
+    let data = script_json(&serde_json::json!({ "maxLines": max_lines.max(1) }));
+    let levels: String = ["error", "warn", "info", "debug", "trace", "other"]
+        .iter()
+        .map(|level| {
+            format!(r#"<label><input type="checkbox" data-level="{level}" checked>{level}</label>"#)
+        })
+        .collect();
+    let body = format!(
+        r#"<div id="bar">
+<input id="filter" type="search" placeholder="Filter">
+{levels}
+<button id="clear">Clear</button>
+<span id="count"></span>
+</div>
+<div id="output"></div>
+<button id="follow"></button>
+<script id="data" type="application/json">{data}</script>
+<script>{LOG_SCRIPT}</script>"#
+    );
+    page(theme, LOG_STYLE, &body)
+}
@@ -0,0 +1,71 @@
+//! CommonMark / GitHub Flavoured Markdown rendering.
+
+use super::{highlight, highlight_css, page};
+use crate::WindowTheme;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd, html};
+
+const MARKDOWN_STYLE: &str = r#"
+.markdown-body { max-width: 880px; margin: 0 auto; padding: 32px; }
+.markdown-body h1, .markdown-body h2 { border-bottom: 1px solid var(--border); padding-bottom: 0.3em; }
+.markdown-body pre { background: var(--surface); border-radius: 6px; padding: 16px; overflow: auto; }
+.markdown-body :not(pre) > code { background: var(--surface); border-radius: 4px; padding: 0.2em 0.4em; }
+.markdown-body blockquote { margin: 0; padding: 0 1em; color: var(--muted); border-left: 4px solid var(--border); }
+.markdown-body table { border-collapse: collapse; margin: 16px 0; }
+.markdown-body th, .markdown-body td { border: 1px solid var(--border); padding: 6px 13px; }
+.markdown-body tr:nth-child(2n) { background: var(--surface); }
+.markdown-body img { max-width: 100%; }
+.markdown-body li:has(> input[type="checkbox"]) { list-style: none; }
+.markdown-body input[type="checkbox"] { margin: 0 0.4em 0 -1.4em; }
+.markdown-body hr { border: 0; border-top: 1px solid var(--border); }
+"#;
+
+/// Render Markdown `source` to a complete, themed HTML page.
+///
+/// Supports CommonMark plus the GitHub extensions: tables, task lists,
+/// strikethrough, footnotes and alerts. Fenced code blocks are syntax
+/// highlighted based on their info string.
+pub fn render_markdown(source: &str, theme: &WindowTheme) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_GFM;
+
+    let mut events = Vec::new();
+    let mut code_block: Option<(Option<String>, String)> = None;
+
+    for event in Parser::new_ext(source, options) {
+        match (event, &mut code_block) {
+            (Event::Start(Tag::CodeBlock(kind)), _) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|language| language.to_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                code_block = Some((language, String::new()));
+            }
+            (Event::Text(text), Some((_, code))) => code.push_str(&text),
+            (Event::End(TagEnd::CodeBlock), Some((language, code))) => {
+                let html = format!(
+                    "<pre class=\"hl-code\"><code>{}</code></pre>\n",
+                    highlight(code, language.as_deref())
+                );
+                events.push(Event::Html(html.into()));
+                code_block = None;
+            }
+            (event, _) => events.push(event),
+        }
+    }
+
+    let mut body = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut body, events.into_iter());
+
+    let style = format!("{MARKDOWN_STYLE}{}", highlight_css(theme));
+    page(
+        theme,
+        &style,
+        &format!("<article class=\"markdown-body\">\n{body}</article>"),
+    )
+}
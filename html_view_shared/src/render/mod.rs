@@ -0,0 +1,147 @@
+//! Renderers and loaders that turn structured
+//! [`ViewerContent`](crate::ViewerContent) variants into something the
+//! webview can display: self-contained HTML pages, or files to serve.
+//!
+//! Only compiled with the `render` feature, which the viewer app enables.
+//! Every page shares [`BASE_STYLE`], so all rendered content follows the
+//! requested [`WindowTheme`].
+
+mod ansi;
+mod archive;
+mod audio;
+mod code;
+mod diagram;
+mod diff;
+mod files;
+mod highlight;
+mod history;
+mod image;
+mod json;
+mod layout;
+mod log;
+mod markdown;
+mod slides;
+mod table;
+mod tabs;
+mod template;
+
+pub use ansi::{ansi_to_html, render_ansi};
+pub use archive::{ArchiveLimits, archive_entry, read_archive, read_archive_with_limits};
+pub use audio::{DecodedAudio, Spectrogram, audio_files, decode_audio, encode_wav, spectrogram};
+pub use code::render_code;
+pub use diagram::{DiagramError, diagram_svg, render_diagram};
+pub use diff::render_diff;
+pub use files::{bundle_path, mime_for_path};
+pub use highlight::{highlight, highlight_by_line, highlight_css};
+pub use history::render_history;
+pub use image::{image_mime, render_image};
+pub use json::render_json;
+pub use layout::render_layout;
+pub use log::{DEFAULT_LOG_LINES, render_log};
+pub use markdown::render_markdown;
+pub use slides::render_slides;
+pub use table::{read_delimited, render_table};
+pub use tabs::render_tabs;
+pub use template::render_template;
+
+use crate::WindowTheme;
+
+/// Stylesheet shared by all rendered pages.
+///
+/// Colours are exposed as CSS variables so content-specific styles can follow
+/// the theme without repeating the light and dark palettes.
+pub const BASE_STYLE: &str = r#"
+:root {
+    color-scheme: light;
+    --bg: #ffffff;
+    --fg: #1f2328;
+    --muted: #59636e;
+    --border: #d1d9e0;
+    --surface: #f6f8fa;
+    --link: #0969da;
+}
+:root[data-theme="dark"] {
+    color-scheme: dark;
+    --bg: #0d1117;
+    --fg: #e6edf3;
+    --muted: #9198a1;
+    --border: #3d444d;
+    --surface: #151b23;
+    --link: #4493f8;
+}
+@media (prefers-color-scheme: dark) {
+    :root[data-theme="system"] {
+        color-scheme: dark;
+        --bg: #0d1117;
+        --fg: #e6edf3;
+        --muted: #9198a1;
+        --border: #3d444d;
+        --surface: #151b23;
+        --link: #4493f8;
+    }
+}
+html, body {
+    margin: 0;
+    background: var(--bg);
+    color: var(--fg);
+    font-family: system-ui, -apple-system, "Segoe UI", Roboto, sans-serif;
+    font-size: 15px;
+    line-height: 1.5;
+}
+a { color: var(--link); }
+code, pre { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px; }
+"#;
+
+/// Wrap `body` in a complete HTML document using the base stylesheet.
+///
+/// `style` is appended after [`BASE_STYLE`] and may use its CSS variables.
+pub fn page(theme: &WindowTheme, style: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html data-theme="{theme}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<style>{BASE_STYLE}{style}</style>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        theme = theme_name(theme),
+    )
+}
+
+/// Serialize `value` as JSON that is safe to embed in a `<script>` element.
+pub fn script_json<T: serde::Serialize + ?Sized>(value: &T) -> String {
+    // `<` only occurs inside JSON strings, where `\u003c` is equivalent, and
+    // escaping it keeps `</script>` in the data from ending the script.
+    serde_json::to_string(value)
+        .unwrap_or_else(|_| "null".to_string())
+        .replace('<', "\\u003c")
+}
+
+/// Escape text for use in HTML element content and quoted attributes.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn theme_name(theme: &WindowTheme) -> &'static str {
+    match theme {
+        WindowTheme::Light => "light",
+        WindowTheme::Dark => "dark",
+        WindowTheme::System => "system",
+    }
+}
@@ -0,0 +1,205 @@
+//! Slide show of frames showing other content.
+
+use super::{page, script_json};
+use crate::WindowTheme;
+
+const SLIDES_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+body { display: flex; flex-direction: column; }
+#frames { flex: 1; min-height: 0; position: relative; }
+#frames iframe { position: absolute; inset: 0; width: 100%; height: 100%; border: none; }
+#frames iframe.behind { visibility: hidden; }
+#bar {
+    flex: none; display: flex; align-items: center; gap: 6px; padding: 6px 10px;
+    background: var(--surface); border-top: 1px solid var(--border);
+    font-size: 13px; user-select: none;
+}
+#bar button {
+    min-width: 30px; padding: 3px 10px; font: inherit; color: var(--fg);
+    background: var(--bg); border: 1px solid var(--border); border-radius: 6px; cursor: pointer;
+}
+#bar button:hover:not(:disabled) { border-color: var(--muted); }
+#bar button:disabled { color: var(--muted); cursor: default; }
+#bar button[aria-pressed="true"] { background: var(--border); }
+#counter { min-width: 64px; text-align: center; color: var(--muted); font-variant-numeric: tabular-nums; }
+#spacer { flex: 1; }
+"#;
+
+const SLIDES_SCRIPT: &str = r#"
+(function () {
+    var srcs = JSON.parse(document.getElementById('slides').textContent);
+    var advance = JSON.parse(document.getElementById('advance').textContent);
+    var frames = document.getElementById('frames');
+    var counter = document.getElementById('counter');
+    var prev = document.getElementById('prev');
+    var next = document.getElementById('next');
+    var play = document.getElementById('play');
+    var full = document.getElementById('fullscreen');
+    var internals = window.__TAURI_INTERNALS__;
+    var loaded = [];
+    var current = -1;
+    var timer = null;
+    var playing = false;
+    var fullscreen = false;
+
+    function invoke(command, args) {
+        if (!internals) return Promise.resolve(null);
+        return internals.invoke(command, args).catch(function (e) {
+            console.error('Slide command ' + command + ' failed:', e);
+            return null;
+        });
+    }
+
+    // Frames stay once created, so slides keep their state. Hidden frames
+    // keep their size, so the next slide can load and lay out in advance.
+    function frameAt(index) {
+        if (!loaded[index]) {
+            var frame = document.createElement('iframe');
+            frame.title = 'Slide ' + (index + 1);
+            frame.className = 'behind';
+            frame.addEventListener('load', function () {
+                // Frames from another origin cannot be listened to
+                try {
+                    frame.contentWindow.addEventListener('keydown', shortcut);
+                } catch (e) {}
+            });
+            frame.src = srcs[index];
+            frames.appendChild(frame);
+            loaded[index] = frame;
+        }
+        return loaded[index];
+    }
+
+    function show(index) {
+        if (!srcs.length) return;
+        index = Math.max(0, Math.min(srcs.length - 1, index));
+        if (index !== current) {
+            frameAt(index);
+            loaded.forEach(function (frame, i) {
+                if (frame) frame.classList.toggle('behind', i !== index);
+            });
+            if (index + 1 < srcs.length) frameAt(index + 1);
+            current = index;
+            counter.textContent = (index + 1) + ' / ' + srcs.length;
+            prev.disabled = index === 0;
+            next.disabled = index === srcs.length - 1;
+            invoke('slide_shown', { index: index });
+        }
+        schedule();
+    }
+
+    function schedule() {
+        clearTimeout(timer);
+        timer = null;
+        if (!playing) return;
+        if (current < srcs.length - 1) {
+            timer = setTimeout(function () { show(current + 1); }, advance * 1000);
+        } else {
+            setPlaying(false);
+        }
+    }
+
+    function setPlaying(value) {
+        playing = value;
+        play.textContent = value ? '❚❚ Pause' : '▶ Play';
+        play.setAttribute('aria-pressed', value ? 'true' : 'false');
+        schedule();
+    }
+
+    function togglePlaying() {
+        if (advance === null) return;
+        // Playing from the last slide starts over
+        if (!playing && current === srcs.length - 1) show(0);
+        setPlaying(!playing);
+    }
+
+    function toggleFullscreen() {
+        invoke('toggle_fullscreen').then(function (value) {
+            if (typeof value !== 'boolean') return;
+            fullscreen = value;
+            full.setAttribute('aria-pressed', value ? 'true' : 'false');
+        });
+    }
+
+    function shortcut(event) {
+        if (event.ctrlKey || event.metaKey || event.altKey) return;
+        var target = event.target;
+        if (target && (target.isContentEditable || /^(INPUT|TEXTAREA|SELECT)$/.test(target.tagName))) {
+            return;
+        }
+        switch (event.key) {
+            case 'ArrowRight':
+            case 'PageDown':
+                show(current + 1);
+                break;
+            case 'ArrowLeft':
+            case 'PageUp':
+                show(current - 1);
+                break;
+            case 'Home':
+                show(0);
+                break;
+            case 'End':
+                show(srcs.length - 1);
+                break;
+            case 'f':
+            case 'F':
+                toggleFullscreen();
+                break;
+            case 'Escape':
+                if (!fullscreen) return;
+                toggleFullscreen();
+                break;
+            case 'p':
+            case 'P':
+                if (advance === null) return;
+                togglePlaying();
+                break;
+            default:
+                return;
+        }
+        event.preventDefault();
+    }
+
+    prev.addEventListener('click', function () { show(current - 1); });
+    next.addEventListener('click', function () { show(current + 1); });
+    play.addEventListener('click', togglePlaying);
+    full.addEventListener('click', toggleFullscreen);
+    document.addEventListener('keydown', shortcut);
+
+    play.hidden = advance === null;
+    if (!srcs.length) counter.textContent = 'No slides';
+    show(0);
+    if (advance !== null) setPlaying(true);
+})();
+"#;
+
+/// Render the page showing slides one at a time, for
+/// [`ViewerContent::Slides`](crate::ViewerContent::Slides).
+///
+/// `srcs` are the URLs the slides' frames open, in order. Slides are
+/// stepped through with the arrow keys, PageUp/PageDown, Home and End, or
+/// the buttons below them; F toggles fullscreen with the viewer's
+/// `toggle_fullscreen` command. With `advance_seconds` set, the page moves
+/// on by itself until the last slide, and P pauses or resumes it. Each
+/// slide shown is reported with the viewer's `slide_shown` command.
+pub fn render_slides(srcs: &[String], advance_seconds: Option<f64>, theme: &WindowTheme) -> String {
+    let advance = advance_seconds.filter(|seconds| seconds.is_finite() && *seconds > 0.0);
+    let body = format!(
+        r#"<div id="frames"></div>
+<div id="bar">
+<button id="prev" title="Previous slide (Left)">‹ Previous</button>
+<span id="counter"></span>
+<button id="next" title="Next slide (Right)">Next ›</button>
+<button id="play" title="Play or pause (P)" aria-pressed="false"></button>
+<span id="spacer"></span>
+<button id="fullscreen" title="Fullscreen (F)" aria-pressed="false">⛶</button>
+</div>
+<script id="slides" type="application/json">{srcs}</script>
+<script id="advance" type="application/json">{advance}</script>
+<script>{SLIDES_SCRIPT}</script>"#,
+        srcs = script_json(srcs),
+        advance = script_json(&advance),
+    );
+    page(theme, SLIDES_STYLE, &body)
+}
@@ -0,0 +1,377 @@
+//! Sortable, filterable grid for tabular data.
+
+use super::{page, script_json};
+use crate::WindowTheme;
+use serde_json::Value;
+use std::io;
+use std::path::Path;
+
+const TABLE_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+#controls {
+    position: fixed; left: 0; right: 0; top: 0; height: 36px; box-sizing: border-box;
+    display: flex; align-items: center; gap: 8px; padding: 0 12px; font-size: 12px;
+    border-bottom: 1px solid var(--border);
+}
+#controls button {
+    font: inherit; color: var(--fg); background: var(--surface);
+    border: 1px solid var(--border); border-radius: 4px; padding: 3px 10px; cursor: pointer;
+}
+#count { color: var(--muted); margin-right: auto; font-variant-numeric: tabular-nums; }
+#scroller { position: fixed; left: 0; right: 0; top: 36px; bottom: 0; overflow: auto; }
+#grid { table-layout: fixed; border-collapse: separate; border-spacing: 0; font-size: 13px; }
+#grid th, #grid td {
+    height: 24px; box-sizing: border-box; padding: 0 8px; overflow: hidden;
+    white-space: nowrap; text-overflow: ellipsis; border-right: 1px solid var(--border);
+    border-bottom: 1px solid var(--border);
+}
+#grid th { position: sticky; z-index: 1; background: var(--surface); text-align: left; }
+#headers th { top: 0; cursor: pointer; user-select: none; font-weight: 600; }
+#headers th .arrow { color: var(--muted); margin-left: 4px; }
+#filters th { top: 24px; padding: 2px 4px; }
+#filters input {
+    width: 100%; box-sizing: border-box; font: inherit; font-size: 12px; color: var(--fg);
+    background: var(--bg); border: 1px solid var(--border); border-radius: 3px; padding: 1px 4px;
+}
+#grid td.number { text-align: right; font-variant-numeric: tabular-nums; }
+#grid td.empty { color: var(--muted); }
+#grid tbody tr.odd td { background: var(--surface); }
+#grid tbody tr.spacer td { border: none; padding: 0; background: none; }
+"#;
+
+const TABLE_SCRIPT: &str = r#"
+(function () {
+    var ROW = 24;
+    var OVERSCAN = 20;
+
+    var table = JSON.parse(document.getElementById('data').textContent);
+    var columns = table.columns;
+    var rows = table.rows;
+    var scroller = document.getElementById('scroller');
+    var body = document.getElementById('body');
+    var counter = document.getElementById('count');
+
+    // A column is numeric when every non-empty value is a number or a
+    // numeric string; numeric strings are converted so they sort correctly.
+    var numeric = columns.map(function (_, c) {
+        var seen = false;
+        for (var r = 0; r < rows.length; r++) {
+            var v = rows[r][c];
+            if (v === null || v === undefined || v === '') continue;
+            if (typeof v === 'number') { seen = true; continue; }
+            if (typeof v === 'string' && v.trim() !== '' && isFinite(Number(v))) {
+                seen = true;
+                continue;
+            }
+            return false;
+        }
+        return seen;
+    });
+    rows.forEach(function (row) {
+        numeric.forEach(function (isNumeric, c) {
+            if (isNumeric && typeof row[c] === 'string') {
+                row[c] = row[c].trim() === '' ? null : Number(row[c]);
+            }
+        });
+    });
+
+    // Numbers in a column share the same number of decimals, up to six, so
+    // they line up; large values are grouped.
+    var formats = columns.map(function (_, c) {
+        if (!numeric[c]) return null;
+        var decimals = 0, max = 0;
+        for (var r = 0; r < rows.length; r++) {
+            var v = rows[r][c];
+            if (typeof v !== 'number' || !isFinite(v)) continue;
+            max = Math.max(max, Math.abs(v));
+            var fraction = String(v).split('.')[1];
+            if (fraction && String(v).indexOf('e') === -1) {
+                decimals = Math.max(decimals, Math.min(fraction.length, 6));
+            }
+        }
+        return new Intl.NumberFormat(undefined, {
+            minimumFractionDigits: decimals,
+            maximumFractionDigits: decimals,
+            useGrouping: max >= 100000
+        });
+    });
+
+    function display(value, c) {
+        if (value === null || value === undefined) return '';
+        if (typeof value === 'number') return formats[c] ? formats[c].format(value) : String(value);
+        if (typeof value === 'object') return JSON.stringify(value);
+        return String(value);
+    }
+
+    var collator = new Intl.Collator(undefined, { numeric: true, sensitivity: 'base' });
+    var sort = { column: -1, direction: 0 };
+    var filters = columns.map(function () { return null; });
+    var view = [];
+
+    function parseFilter(text, c) {
+        text = text.trim();
+        if (!text) return null;
+        var match = numeric[c] && /^(>=|<=|!=|>|<|=)\s*(-?[\d.]+(?:e[+-]?\d+)?)$/i.exec(text);
+        if (match) {
+            var operand = Number(match[2]);
+            var op = match[1];
+            return function (v) {
+                if (typeof v !== 'number') return false;
+                switch (op) {
+                    case '>': return v > operand;
+                    case '<': return v < operand;
+                    case '>=': return v >= operand;
+                    case '<=': return v <= operand;
+                    case '=': return v === operand;
+                    default: return v !== operand;
+                }
+            };
+        }
+        var needle = text.toLowerCase();
+        return function (v) {
+            return display(v, c).toLowerCase().indexOf(needle) !== -1;
+        };
+    }
+
+    function compare(a, b, c) {
+        var x = rows[a][c], y = rows[b][c];
+        var xEmpty = x === null || x === undefined || x === '';
+        var yEmpty = y === null || y === undefined || y === '';
+        // Empty cells sort last in both directions
+        if (xEmpty || yEmpty) return xEmpty === yEmpty ? a - b : (xEmpty ? 1 : -1) * sort.direction;
+        var result = numeric[c] ? x - y : collator.compare(display(x, c), display(y, c));
+        return result || a - b;
+    }
+
+    function update() {
+        var active = [];
+        filters.forEach(function (filter, c) { if (filter) active.push([c, filter]); });
+        view = [];
+        for (var r = 0; r < rows.length; r++) {
+            var keep = true;
+            for (var f = 0; f < active.length && keep; f++) {
+                keep = active[f][1](rows[r][active[f][0]]);
+            }
+            if (keep) view.push(r);
+        }
+        if (sort.direction) {
+            var c = sort.column, d = sort.direction;
+            view.sort(function (a, b) { return d * compare(a, b, c); });
+        }
+        counter.textContent = view.length.toLocaleString() + ' of ' +
+            rows.length.toLocaleString() + ' rows';
+        scroller.scrollTop = 0;
+        render();
+    }
+
+    function spacer(height) {
+        var tr = document.createElement('tr');
+        tr.className = 'spacer';
+        var td = document.createElement('td');
+        td.colSpan = columns.length;
+        td.style.height = height + 'px';
+        tr.appendChild(td);
+        return tr;
+    }
+
+    function render() {
+        var first = Math.max(0, Math.floor(scroller.scrollTop / ROW) - OVERSCAN);
+        var last = Math.min(view.length,
+            Math.ceil((scroller.scrollTop + scroller.clientHeight) / ROW) + OVERSCAN);
+        var fragment = document.createDocumentFragment();
+        if (first > 0) fragment.appendChild(spacer(first * ROW));
+        for (var i = first; i < last; i++) {
+            var row = rows[view[i]];
+            var tr = document.createElement('tr');
+            if (i % 2) tr.className = 'odd';
+            for (var c = 0; c < columns.length; c++) {
+                var td = document.createElement('td');
+                var text = display(row[c], c);
+                td.textContent = text;
+                td.title = text;
+                td.className = (numeric[c] ? 'number' : '') + (text === '' ? ' empty' : '');
+                tr.appendChild(td);
+            }
+            fragment.appendChild(tr);
+        }
+        if (last < view.length) fragment.appendChild(spacer((view.length - last) * ROW));
+        body.textContent = '';
+        body.appendChild(fragment);
+    }
+
+    var scheduled = false;
+    scroller.addEventListener('scroll', function () {
+        if (scheduled) return;
+        scheduled = true;
+        (window.requestAnimationFrame || setTimeout)(function () {
+            scheduled = false;
+            render();
+        });
+    });
+
+    // Headers, filters and column widths estimated from a sample of rows
+    var headers = document.getElementById('headers');
+    var filterRow = document.getElementById('filters');
+    var colgroup = document.getElementById('cols');
+    var totalWidth = 0;
+    columns.forEach(function (name, c) {
+        var chars = String(name).length + 2;
+        for (var r = 0; r < Math.min(rows.length, 1000); r++) {
+            chars = Math.max(chars, Math.min(display(rows[r][c], c).length, 40));
+        }
+        var width = Math.max(60, Math.min(400, Math.round(chars * 7.5 + 24)));
+        totalWidth += width;
+        var col = document.createElement('col');
+        col.style.width = width + 'px';
+        colgroup.appendChild(col);
+
+        var th = document.createElement('th');
+        th.textContent = name;
+        th.title = name;
+        var arrow = document.createElement('span');
+        arrow.className = 'arrow';
+        th.appendChild(arrow);
+        th.addEventListener('click', function () {
+            if (sort.column !== c) sort = { column: c, direction: 1 };
+            else sort.direction = sort.direction === 1 ? -1 : sort.direction === -1 ? 0 : 1;
+            Array.prototype.forEach.call(headers.querySelectorAll('span.arrow'), function (a) {
+                a.textContent = '';
+            });
+            arrow.textContent = sort.direction === 1 ? '▲' : sort.direction === -1 ? '▼' : '';
+            update();
+        });
+        headers.appendChild(th);
+
+        var filterCell = document.createElement('th');
+        var input = document.createElement('input');
+        input.type = 'search';
+        input.placeholder = numeric[c] ? '> 0, = 5, …' : 'filter';
+        var pending = null;
+        input.addEventListener('input', function () {
+            clearTimeout(pending);
+            pending = setTimeout(function () {
+                filters[c] = parseFilter(input.value, c);
+                update();
+            }, 200);
+        });
+        filterCell.appendChild(input);
+        filterRow.appendChild(filterCell);
+    });
+    document.getElementById('grid').style.width = totalWidth + 'px';
+
+    document.getElementById('clear').addEventListener('click', function () {
+        Array.prototype.forEach.call(filterRow.querySelectorAll('input'), function (input) {
+            input.value = '';
+        });
+        filters = columns.map(function () { return null; });
+        update();
+    });
+
+    function csvField(value) {
+        var text = value === null || value === undefined ? '' :
+            typeof value === 'object' ? JSON.stringify(value) : String(value);
+        return /[",\r\n]/.test(text) ? '"' + text.replace(/"/g, '""') + '"' : text;
+    }
+    document.getElementById('export').addEventListener('click', function () {
+        var lines = [columns.map(csvField).join(',')];
+        view.forEach(function (r) { lines.push(rows[r].map(csvField).join(',')); });
+        var csv = lines.join('\r\n') + '\r\n';
+        var internals = window.__TAURI_INTERNALS__;
+        if (internals) {
+            internals.invoke('export_file', { fileName: 'table.csv', contents: csv })
+                .catch(function (e) { console.error('Export failed: ' + e); });
+        } else {
+            var link = document.createElement('a');
+            link.href = URL.createObjectURL(new Blob([csv], { type: 'text/csv' }));
+            link.download = 'table.csv';
+            link.click();
+        }
+    });
+
+    update();
+})();
+"#;
+
+/// Read a CSV or TSV file into column names and rows.
+///
+/// The first record is the header. The delimiter defaults to a tab for
+/// `.tsv` and `.tab` files and a comma otherwise. Cells are strings; the
+/// table view detects numeric columns itself.
+pub fn read_delimited(
+    path: &Path,
+    delimiter: Option<char>,
+) -> io::Result<(Vec<String>, Vec<Vec<Value>>)> {
+    let delimiter = match delimiter {
+        Some(delimiter) => u8::try_from(delimiter)
+            .ok()
+            .filter(u8::is_ascii)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("delimiter {delimiter:?} is not an ASCII character"),
+                )
+            })?,
+        None => {
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(str::to_ascii_lowercase);
+            match extension.as_deref() {
+                Some("tsv" | "tab") => b'\t',
+                _ => b',',
+            }
+        }
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)
+        .map_err(io::Error::other)?;
+
+    let columns: Vec<String> = reader
+        .headers()
+        .map_err(io::Error::other)?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(io::Error::other)?;
+        let mut row: Vec<Value> = record
+            .iter()
+            .map(|cell| Value::String(cell.to_string()))
+            .collect();
+        row.resize(columns.len(), Value::Null);
+        rows.push(row);
+    }
+
+    Ok((columns, rows))
+}
+
+/// Render an interactive grid for `rows` under `columns`.
+///
+/// The grid has column sorting, per-column filters (substring, or
+/// comparisons such as `> 5` on numeric columns), aligned number formatting,
+/// sticky headers, virtual scrolling and CSV export of the filtered rows.
+pub fn render_table(columns: &[String], rows: &[Vec<Value>], theme: &WindowTheme) -> String {
+    let data = script_json(&serde_json::json!({ "columns": columns, "rows": rows }));
+    let body = format!(
+        r#"<div id="controls">
+<span id="count"></span>
+<button id="clear">Clear filters</button>
+<button id="export">Export CSV</button>
+</div>
+<div id="scroller">
+<table id="grid">
+<colgroup id="cols"></colgroup>
+<thead><tr id="headers"></tr><tr id="filters"></tr></thead>
+<tbody id="body"></tbody>
+</table>
+</div>
+<script id="data" type="application/json">{data}</script>
+<script>{TABLE_SCRIPT}</script>"#
+    );
+    page(theme, TABLE_STYLE, &body)
+}
@@ -0,0 +1,237 @@
+//! Tab strip around frames showing other content.
+
+use super::page;
+use crate::WindowTheme;
+
+const TABS_STYLE: &str = r#"
+html, body { height: 100%; overflow: hidden; }
+body { display: flex; flex-direction: column; }
+#strip {
+    flex: none; display: flex; align-items: flex-end; gap: 2px; padding: 6px 8px 0;
+    overflow-x: auto; scrollbar-width: none; background: var(--surface);
+    border-bottom: 1px solid var(--border); font-size: 13px; user-select: none;
+}
+#strip::-webkit-scrollbar { display: none; }
+.tab {
+    flex: none; display: flex; align-items: center; gap: 6px; max-width: 240px;
+    padding: 5px 6px 5px 12px; margin-bottom: -1px; cursor: default; color: var(--muted);
+    border: 1px solid transparent; border-bottom: none; border-radius: 6px 6px 0 0;
+}
+.tab:hover { color: var(--fg); }
+.tab.active { color: var(--fg); background: var(--bg); border-color: var(--border); }
+.tab:focus-visible { outline: 2px solid var(--link); outline-offset: -2px; }
+.tab .label { overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+.tab .close {
+    width: 18px; height: 18px; line-height: 16px; padding: 0; font: inherit; font-size: 14px;
+    color: inherit; background: none; border: none; border-radius: 4px; cursor: pointer;
+    visibility: hidden;
+}
+.tab:hover .close, .tab.active .close { visibility: visible; }
+.tab .close:hover { background: var(--border); }
+#frames { flex: 1; min-height: 0; position: relative; }
+#frames iframe { position: absolute; inset: 0; width: 100%; height: 100%; border: none; }
+#empty { margin: 24px; color: var(--muted); }
+"#;
+
+const TABS_SCRIPT: &str = r#"
+(function () {
+    var strip = document.getElementById('strip');
+    var frames = document.getElementById('frames');
+    var empty = document.getElementById('empty');
+    var internals = window.__TAURI_INTERNALS__;
+    var tabs = [];
+    var selected = null;
+
+    function invoke(command, args) {
+        if (!internals) return;
+        internals.invoke(command, args).catch(function (e) {
+            console.error('Tab command ' + command + ' failed:', e);
+        });
+    }
+
+    function indexOf(id) {
+        for (var i = 0; i < tabs.length; i++) {
+            if (tabs[i].id === id) return i;
+        }
+        return -1;
+    }
+
+    function create(id, src) {
+        var tab = { id: id, src: src, frame: null };
+        tab.button = document.createElement('div');
+        tab.button.className = 'tab';
+        tab.button.setAttribute('role', 'tab');
+        tab.button.tabIndex = -1;
+        tab.button.title = id;
+        var label = document.createElement('span');
+        label.className = 'label';
+        label.textContent = id;
+        var close = document.createElement('button');
+        close.className = 'close';
+        close.title = 'Close tab';
+        close.textContent = '×';
+        tab.button.appendChild(label);
+        tab.button.appendChild(close);
+
+        tab.button.addEventListener('mousedown', function (event) {
+            if (event.button === 1) {
+                event.preventDefault();
+                closeTab(tab.id, true);
+            } else if (event.button === 0 && event.target !== close) {
+                selectTab(tab.id, true);
+            }
+        });
+        close.addEventListener('click', function (event) {
+            event.stopPropagation();
+            closeTab(tab.id, true);
+        });
+        strip.appendChild(tab.button);
+        tabs.push(tab);
+        empty.hidden = true;
+        return tab;
+    }
+
+    // Frames are only created once their tab is first shown, so content
+    // such as a diagram lays out at the size it is seen at
+    function frameOf(tab) {
+        if (!tab.frame) {
+            tab.frame = document.createElement('iframe');
+            tab.frame.title = tab.id;
+            tab.frame.addEventListener('load', function () {
+                // Keep tab shortcuts working while a frame has focus. Frames
+                // from another origin cannot be listened to.
+                try {
+                    tab.frame.contentWindow.addEventListener('keydown', shortcut);
+                } catch (e) {}
+            });
+            tab.frame.src = tab.src;
+            frames.appendChild(tab.frame);
+        }
+        return tab.frame;
+    }
+
+    function selectTab(id, byUser) {
+        var index = indexOf(id);
+        if (index < 0) return;
+        var tab = tabs[index];
+        selected = tab;
+        tabs.forEach(function (other) {
+            var active = other === tab;
+            other.button.classList.toggle('active', active);
+            other.button.setAttribute('aria-selected', active ? 'true' : 'false');
+            other.button.tabIndex = active ? 0 : -1;
+            if (other.frame) other.frame.hidden = !active;
+        });
+        frameOf(tab).hidden = false;
+        tab.button.scrollIntoView({ block: 'nearest', inline: 'nearest' });
+        if (byUser) invoke('tab_selected', { id: id });
+    }
+
+    function closeTab(id, byUser) {
+        var index = indexOf(id);
+        if (index < 0) return;
+        var tab = tabs.splice(index, 1)[0];
+        tab.button.remove();
+        if (tab.frame) tab.frame.remove();
+        empty.hidden = tabs.length > 0;
+        // The viewer makes the same choice, so it is not told separately
+        if (selected === tab) {
+            selected = null;
+            var next = tabs[Math.min(index, tabs.length - 1)];
+            if (next) selectTab(next.id, false);
+        }
+        if (byUser) invoke('tab_closed', { id: id });
+    }
+
+    function addTab(id, src) {
+        var index = indexOf(id);
+        if (index < 0) {
+            create(id, src);
+        } else {
+            updateTab(id, src);
+        }
+        selectTab(id, false);
+    }
+
+    function updateTab(id, src) {
+        var index = indexOf(id);
+        if (index < 0) return;
+        var tab = tabs[index];
+        tab.src = src;
+        if (tab.frame) tab.frame.src = src;
+    }
+
+    function reset(list) {
+        tabs.slice().forEach(function (tab) { closeTab(tab.id, false); });
+        list.tabs.forEach(function (tab) { create(tab.id, tab.src); });
+        if (list.selected !== null) selectTab(list.selected, false);
+    }
+
+    function step(offset) {
+        if (!tabs.length) return;
+        var index = selected ? indexOf(selected.id) : 0;
+        selectTab(tabs[(index + offset + tabs.length) % tabs.length].id, true);
+    }
+
+    function shortcut(event) {
+        if (!(event.ctrlKey || event.metaKey) || event.altKey) return;
+        if (event.key === 'Tab') {
+            step(event.shiftKey ? -1 : 1);
+        } else if (event.key === 'PageDown') {
+            step(1);
+        } else if (event.key === 'PageUp') {
+            step(-1);
+        } else if (/^[1-9]$/.test(event.key) && tabs.length) {
+            var index = event.key === '9' ? tabs.length - 1 : Math.min(+event.key, tabs.length) - 1;
+            selectTab(tabs[index].id, true);
+        } else {
+            return;
+        }
+        event.preventDefault();
+    }
+
+    document.addEventListener('keydown', shortcut);
+    strip.addEventListener('keydown', function (event) {
+        if (event.key === 'ArrowLeft' || event.key === 'ArrowRight') {
+            step(event.key === 'ArrowLeft' ? -1 : 1);
+            if (selected) selected.button.focus();
+            event.preventDefault();
+        } else if (event.key === 'Delete' && selected) {
+            closeTab(selected.id, true);
+        }
+    });
+
+    window.__htmlViewTabs = {
+        add: addTab,
+        close: function (id) { closeTab(id, false); },
+        select: function (id) { selectTab(id, false); },
+        update: updateTab
+    };
+
+    // The tabs are fetched rather than embedded, so changes made while the
+    // page was loading are not lost
+    if (internals) {
+        internals.invoke('tab_list').then(reset).catch(function (e) {
+            empty.textContent = 'Failed to load the tabs: ' + e;
+            empty.hidden = false;
+        });
+    }
+})();
+"#;
+
+/// Render the page holding a tab strip, for
+/// [`ViewerContent::Tabs`](crate::ViewerContent::Tabs).
+///
+/// The page fetches its tabs with the viewer's `tab_list` command, showing
+/// each in a frame, and reports changes made by the user with `tab_selected`
+/// and `tab_closed`. The viewer changes tabs through `window.__htmlViewTabs`.
+/// Tabs are selected with Ctrl+Tab, Ctrl+PageUp/PageDown and Ctrl+1 to 9,
+/// and closed with their close button or a middle click.
+pub fn render_tabs(theme: &WindowTheme) -> String {
+    let body = format!(
+        r#"<div id="strip" role="tablist"></div>
+<div id="frames"><div id="empty" hidden>No tabs are open.</div></div>
+<script>{TABS_SCRIPT}</script>"#
+    );
+    page(theme, TABS_STYLE, &body)
+}
@@ -0,0 +1,23 @@
+//! Rendering of HTML templates with JSON data.
+
+use crate::TemplateEngine;
+use minijinja::Environment;
+
+/// Render `template` with `data` using `engine`.
+///
+/// HTML auto-escaping is enabled, so values are escaped unless marked
+/// `| safe`. Data can be embedded in scripts with `{{ data | tojson }}`.
+pub fn render_template(
+    template: &str,
+    data: &serde_json::Value,
+    engine: TemplateEngine,
+) -> Result<String, minijinja::Error> {
+    match engine {
+        TemplateEngine::Minijinja => {
+            let mut env = Environment::new();
+            // The `.html` name turns on HTML auto-escaping
+            env.add_template("template.html", template)?;
+            env.get_template("template.html")?.render(data)
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use html_view_shared::BehaviourOptions;
+use url::Url;
+
+fn url(s: &str) -> Url {
+    Url::parse(s).unwrap()
+}
+
+#[test]
+fn test_local_urls_allowed_with_external_navigation() {
+    let behaviour = BehaviourOptions {
+        allow_external_navigation: true,
+        ..Default::default()
+    };
+
+    assert!(
+        behaviour
+            .check_navigation(&url("file:///tmp/index.html"), None)
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("data:text/html,hi"), None)
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("about:blank"), None)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_remote_urls_require_remote_content() {
+    let behaviour = BehaviourOptions {
+        allow_external_navigation: true,
+        ..Default::default()
+    };
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com"), None)
+            .is_err()
+    );
+
+    let behaviour = BehaviourOptions {
+        allow_external_navigation: true,
+        allow_remote_content: true,
+        ..Default::default()
+    };
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com"), None)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_allowed_domains_match_host_and_subdomains() {
+    let behaviour = BehaviourOptions {
+        allow_external_navigation: true,
+        allow_remote_content: true,
+        allowed_domains: Some(vec!["example.com".to_string()]),
+        ..Default::default()
+    };
+
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com/a"), None)
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("https://docs.example.com"), None)
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("https://notexample.com"), None)
+            .is_err()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("https://other.org"), None)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_other_schemes_rejected() {
+    let behaviour = BehaviourOptions {
+        allow_external_navigation: true,
+        allow_remote_content: true,
+        ..Default::default()
+    };
+
+    assert!(
+        behaviour
+            .check_navigation(&url("javascript:alert(1)"), None)
+            .is_err()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("ftp://example.com"), None)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_navigation_stays_on_home_origin_without_external_navigation() {
+    let behaviour = BehaviourOptions {
+        allow_remote_content: true,
+        ..Default::default()
+    };
+    let home = url("https://example.com/app/index.html");
+
+    // Same origin, and the built-in viewers on either form of the bundle scheme
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com/about"), Some(&home))
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("bundle://localhost/index.html"), None)
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("http://bundle.localhost/index.html"), None)
+            .is_ok()
+    );
+
+    // Other origins, including local URLs, are rejected
+    for target in [
+        "https://other.org",
+        "http://example.com/about",
+        "https://docs.example.com",
+        "file:///etc/passwd",
+        "data:text/html,hi",
+        "about:blank",
+    ] {
+        assert!(
+            behaviour
+                .check_navigation(&url(target), Some(&home))
+                .is_err(),
+            "{} should be rejected",
+            target
+        );
+    }
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com"), None)
+            .is_err()
+    );
+
+    // Files count as the same origin as the files in their directory
+    let home = url("file:///srv/app/index.html");
+    assert!(
+        behaviour
+            .check_navigation(&url("file:///srv/app/docs/page.html"), Some(&home))
+            .is_ok()
+    );
+    assert!(
+        behaviour
+            .check_navigation(&url("file:///srv/other/page.html"), Some(&home))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_allowed_domains_apply_without_external_navigation() {
+    let home = url("https://example.com/report");
+    let behaviour = BehaviourOptions {
+        allow_remote_content: true,
+        allowed_domains: Some(vec!["docs.example.com".to_string()]),
+        ..Default::default()
+    };
+
+    // The content's own origin is still checked against the list
+    assert!(
+        behaviour
+            .check_navigation(&url("https://example.com/other"), Some(&home))
+            .is_err()
+    );
+    // A listed domain of another origin is not a way around the origin check
+    assert!(
+        behaviour
+            .check_navigation(&url("https://docs.example.com"), Some(&home))
+            .is_err()
+    );
+
+    let home = url("https://docs.example.com/index.html");
+    assert!(
+        behaviour
+            .check_navigation(&url("https://docs.example.com/page"), Some(&home))
+            .is_ok()
+    );
+}
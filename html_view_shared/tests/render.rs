@@ -0,0 +1,678 @@
+//! Tests for the content renderers. Run with `--features render`.
+#![cfg(feature = "render")]
+
+use html_view_shared::DiagramKind;
+use html_view_shared::DiffMode;
+use html_view_shared::Layout;
+use html_view_shared::TemplateEngine;
+use html_view_shared::WindowTheme;
+use html_view_shared::render::{
+    ArchiveLimits, DecodedAudio, ansi_to_html, archive_entry, audio_files, bundle_path,
+    decode_audio, diagram_svg, encode_wav, escape_html, highlight, highlight_by_line, image_mime,
+    mime_for_path, read_archive, read_archive_with_limits, read_delimited, render_code,
+    render_diagram, render_diff, render_history, render_image, render_json, render_layout,
+    render_log, render_markdown, render_slides, render_table, render_tabs, render_template,
+    spectrogram,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+#[test]
+fn test_markdown_gfm_extensions() {
+    let source = "\
+| a | b |
+|---|---|
+| 1 | 2 |
+
+- [x] done
+- [ ] todo
+
+~~gone~~
+";
+    let html = render_markdown(source, &WindowTheme::System);
+
+    assert!(html.contains("<table>"));
+    assert!(html.contains("<td>2</td>"));
+    assert!(html.contains(r#"type="checkbox""#));
+    assert!(html.contains("checked"));
+    assert!(html.contains("<del>gone</del>"));
+}
+
+#[test]
+fn test_markdown_highlights_fenced_code() {
+    let html = render_markdown("```rust\nfn main() {}\n```\n", &WindowTheme::Light);
+
+    assert!(html.contains(r#"<pre class="hl-code">"#));
+    assert!(html.contains("hl-"));
+    assert!(!html.contains("```"));
+}
+
+#[test]
+fn test_markdown_theme_attribute() {
+    assert!(render_markdown("# Hi", &WindowTheme::Dark).contains(r#"data-theme="dark""#));
+    assert!(render_markdown("# Hi", &WindowTheme::System).contains("prefers-color-scheme: dark"));
+}
+
+#[test]
+fn test_highlight_unknown_language_is_escaped() {
+    assert_eq!(
+        highlight("<b>&</b>", Some("no-such-language")),
+        "&lt;b&gt;&amp;&lt;/b&gt;"
+    );
+    assert_eq!(escape_html(r#""'"#), "&quot;&#39;");
+}
+
+#[test]
+fn test_image_mime_detection() {
+    assert_eq!(image_mime(b"\x89PNG\r\n\x1a\n....", None), "image/png");
+    assert_eq!(image_mime(b"\xff\xd8\xff\xe0", None), "image/jpeg");
+    assert_eq!(image_mime(b"RIFF\0\0\0\0WEBPVP8 ", None), "image/webp");
+    assert_eq!(
+        image_mime(b"<svg xmlns='http://www.w3.org/2000/svg'/>", None),
+        "image/svg+xml"
+    );
+    assert_eq!(image_mime(b"", Some(Path::new("photo.JPG"))), "image/jpeg");
+    assert_eq!(image_mime(b"??", None), "application/octet-stream");
+}
+
+#[test]
+fn test_image_page_embeds_data_url() {
+    let html = render_image(&[1, 2, 3], "image/png", &WindowTheme::Light);
+
+    assert!(html.contains(r#"src="data:image/png;base64,AQID""#));
+    assert!(html.contains(r#"id="readout""#));
+}
+
+#[test]
+fn test_bundle_path_rules() {
+    assert_eq!(
+        bundle_path("/css/./app.css").as_deref(),
+        Some("css/app.css")
+    );
+    assert_eq!(bundle_path("js\\main.js").as_deref(), Some("js/main.js"));
+    assert_eq!(bundle_path("../secret"), None);
+    assert_eq!(bundle_path("assets/../../secret"), None);
+    assert_eq!(bundle_path("C:/Windows"), None);
+
+    assert_eq!(mime_for_path("fonts/Inter.WOFF2"), "font/woff2");
+    assert_eq!(mime_for_path("README"), "application/octet-stream");
+}
+
+#[test]
+fn test_read_zip_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.zip");
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.add_directory("report/", options).unwrap();
+    zip.start_file("report/index.html", options).unwrap();
+    zip.write_all(b"<h1>Report</h1>").unwrap();
+    zip.start_file("report/../escape.txt", options).unwrap();
+    zip.write_all(b"nope").unwrap();
+    zip.finish().unwrap();
+
+    let files = read_archive(&path).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files["report/index.html"], b"<h1>Report</h1>");
+    assert_eq!(
+        archive_entry(&files, None).as_deref(),
+        Some("report/index.html")
+    );
+}
+
+#[test]
+fn test_read_tar_gz_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.tar.gz");
+
+    let encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&path).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut tar = tar::Builder::new(encoder);
+    for (name, data) in [("index.html", "<h1>Hi</h1>"), ("app.css", "h1 {}")] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, name, data.as_bytes()).unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+
+    let files = read_archive(&path).unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(archive_entry(&files, None).as_deref(), Some("index.html"));
+    assert_eq!(archive_entry(&files, Some("missing.html")), None);
+}
+
+#[test]
+fn test_read_archive_rejects_oversized_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("huge.tar");
+
+    // The header claims far more data than follows it
+    let mut tar = tar::Builder::new(std::fs::File::create(&path).unwrap());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(ArchiveLimits::default().max_entry_size + 1);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, "index.html", &b"<h1>Hi</h1>"[..])
+        .unwrap();
+    tar.into_inner().unwrap();
+
+    let err = read_archive(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_read_archive_limits_total_size_and_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("many.zip");
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for index in 0..10 {
+        zip.start_file(format!("page{}.html", index), options)
+            .unwrap();
+        zip.write_all(&[b'x'; 100]).unwrap();
+    }
+    zip.finish().unwrap();
+
+    let limits = ArchiveLimits {
+        max_entry_size: 100,
+        max_total_size: 1000,
+        max_entries: 10,
+    };
+    assert_eq!(read_archive_with_limits(&path, &limits).unwrap().len(), 10);
+
+    // Every file fits on its own, but not all of them together
+    let small_total = ArchiveLimits {
+        max_total_size: 999,
+        ..limits
+    };
+    let err = read_archive_with_limits(&path, &small_total).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("in total"), "{}", err);
+
+    let few_entries = ArchiveLimits {
+        max_entries: 9,
+        ..limits
+    };
+    let err = read_archive_with_limits(&path, &few_entries).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("entries"), "{}", err);
+}
+
+#[test]
+fn test_archive_entry_requires_single_root() {
+    let files: HashMap<String, Vec<u8>> = [("a/index.html", ""), ("b/index.html", "")]
+        .into_iter()
+        .map(|(path, data)| (path.to_string(), data.as_bytes().to_vec()))
+        .collect();
+
+    assert_eq!(archive_entry(&files, None), None);
+    assert_eq!(
+        archive_entry(&files, Some("b/index.html")).as_deref(),
+        Some("b/index.html")
+    );
+}
+
+#[test]
+fn test_template_renders_with_escaping() {
+    let html = render_template(
+        "<p>{{ name }}</p>{% for x in xs %}<i>{{ x }}</i>{% endfor %}",
+        &json!({ "name": "<b>", "xs": [1, 2] }),
+        TemplateEngine::Minijinja,
+    )
+    .unwrap();
+
+    assert_eq!(html, "<p>&lt;b&gt;</p><i>1</i><i>2</i>");
+}
+
+#[test]
+fn test_template_syntax_error() {
+    assert!(render_template("{% for %}", &json!({}), TemplateEngine::Minijinja).is_err());
+}
+
+#[test]
+fn test_json_page_escapes_script_end() {
+    let html = render_json(&json!({ "html": "</script><b>" }), &WindowTheme::System);
+
+    assert!(!html.contains("</script><b>"));
+    assert!(html.contains(r#"{"html":"\u003c/script>\u003cb>"}"#));
+    assert!(html.contains(r#"id="search""#));
+}
+
+#[test]
+fn test_read_delimited_csv_and_tsv() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("runs.csv");
+    std::fs::write(&csv, "name,score\n\"a, b\",1.5\nc\n").unwrap();
+
+    let (columns, rows) = read_delimited(&csv, None).unwrap();
+    assert_eq!(columns, vec!["name", "score"]);
+    assert_eq!(
+        rows,
+        vec![
+            vec![json!("a, b"), json!("1.5")],
+            vec![json!("c"), json!(null)]
+        ]
+    );
+
+    let tsv = dir.path().join("runs.tsv");
+    std::fs::write(&tsv, "name\tscore\na,b\t2\n").unwrap();
+    let (columns, rows) = read_delimited(&tsv, None).unwrap();
+    assert_eq!(columns, vec!["name", "score"]);
+    assert_eq!(rows[0], vec![json!("a,b"), json!("2")]);
+
+    assert!(read_delimited(&tsv, Some('é')).is_err());
+}
+
+#[test]
+fn test_table_page_embeds_rows() {
+    let html = render_table(
+        &["name".to_string()],
+        &[vec![json!("</script>")]],
+        &WindowTheme::Dark,
+    );
+
+    assert!(html.contains(r#"data-theme="dark""#));
+    assert!(html.contains(r#"{"columns":["name"],"rows":[["\u003c/script>"]]}"#));
+    assert!(html.contains(r#"id="export""#));
+}
+
+#[test]
+fn test_highlight_by_line_balances_spans() {
+    let lines = highlight_by_line("/* a\r\n b */ x\nlet s = \"<\";\n", Some("rust"));
+
+    assert_eq!(lines.len(), 3);
+    for line in &lines {
+        assert_eq!(
+            line.matches("<span").count(),
+            line.matches("</span>").count()
+        );
+        assert!(!line.contains('\n') && !line.contains('\r'));
+    }
+    // The comment spans both lines, so it is reopened on the second
+    assert!(lines[1].starts_with("<span") && lines[1].contains("hl-comment"));
+    assert!(lines[2].contains("&lt;"));
+
+    assert_eq!(highlight_by_line("a\nb", None), vec!["a", "b"]);
+    assert!(highlight_by_line("", Some("rust")).is_empty());
+}
+
+#[test]
+fn test_code_page_marks_lines() {
+    let html = render_code("a\nb\nc\n", None, true, &[2], &WindowTheme::Light);
+
+    assert!(html.contains(
+        r#"<tr id="L2" class="marked"><td class="ln" data-line="2"></td><td>b</td></tr>"#
+    ));
+    assert!(html.contains(r#"<tr id="L3"><td class="ln" data-line="3"></td>"#));
+
+    let html = render_code("a\n", None, false, &[], &WindowTheme::Light);
+    assert!(!html.contains(r#"class="ln""#));
+}
+
+#[test]
+fn test_diff_page_folds_unchanged_lines() {
+    let old: String = (1..=20).map(|i| format!("{i}\n")).collect();
+    let new = old.replace("10\n", "ten\n");
+    let html = render_diff(&old, &new, None, DiffMode::SideBySide, &WindowTheme::Light);
+
+    assert!(html.contains(r#"data-mode="side_by_side""#));
+    assert!(
+        html.contains(r#"<span class="added">+1</span> <span class="removed">&minus;1</span>"#)
+    );
+    assert_eq!(html.matches("Show 6 unchanged lines").count(), 2);
+    assert_eq!(html.matches("Show 7 unchanged lines").count(), 2);
+    assert!(html.contains(r#"data-sign="-"></td><td>10</td>"#));
+    assert!(html.contains(r#"<td class="added">ten</td>"#));
+
+    let html = render_diff("a\n", "a\n", None, DiffMode::Unified, &WindowTheme::Light);
+    assert!(html.contains("No differences"));
+    assert!(!html.contains(r#"class="fold""#));
+}
+
+#[test]
+fn test_ansi_colours_and_styles() {
+    assert_eq!(
+        ansi_to_html("\x1b[1;31merror\x1b[0m: <bad>\n"),
+        "<div class=\"line\"><span style=\"color:var(--ansi-1);font-weight:bold;\">error</span>: &lt;bad&gt;</div>\n"
+    );
+    assert!(ansi_to_html("\x1b[38;5;196mx").contains("color:#ff0000;"));
+    assert!(ansi_to_html("\x1b[38;5;244mx").contains("color:#808080;"));
+    assert!(
+        ansi_to_html("\x1b[48;2;1;2;3;4mx")
+            .contains("background:#010203;text-decoration:underline;")
+    );
+    assert!(ansi_to_html("\x1b[7mx").contains("color:var(--bg);background:var(--fg);"));
+}
+
+#[test]
+fn test_ansi_colon_separated_colours() {
+    // With an empty or given colour space, or without one
+    for sequence in ["38:2::255:0:0", "38:2:0:255:0:0", "38:2:255:0:0"] {
+        let html = ansi_to_html(&format!("\x1b[{}mx", sequence));
+        assert!(html.contains("color:#ff0000;"), "{}: {}", sequence, html);
+    }
+    assert!(ansi_to_html("\x1b[48:5:196mx").contains("background:#ff0000;"));
+
+    // Arguments of one parameter are not read as parameters of their own
+    assert_eq!(
+        ansi_to_html("\x1b[4:3;38:2::0:0:255mx"),
+        ansi_to_html("\x1b[4;38;2;0;0;255mx")
+    );
+}
+
+#[test]
+fn test_ansi_styles_span_lines_and_drop_other_escapes() {
+    let html = ansi_to_html("\x1b[32mgreen\r\nstill\x1b[39m plain\x1b]0;title\x07\x1b[2K!\n");
+    assert_eq!(
+        html,
+        "<div class=\"line\"><span style=\"color:var(--ansi-2);\">green</span></div>\n\
+         <div class=\"line\"><span style=\"color:var(--ansi-2);\">still</span> plain!</div>\n"
+    );
+
+    // Progress output keeps the text after the last carriage return
+    let html = ansi_to_html("10%\r\x1b[1m50%\r100%\n");
+    assert_eq!(
+        html,
+        "<div class=\"line\"><span style=\"font-weight:bold;\">100%</span></div>\n"
+    );
+}
+
+#[test]
+fn test_log_page_settings() {
+    let html = render_log(500, &WindowTheme::Dark);
+
+    assert!(html.contains(r#"{"maxLines":500}"#));
+    assert!(html.contains(r#"data-level="error""#));
+    assert!(html.contains("window.__htmlViewLog"));
+}
+
+fn tone(frames: usize, channels: u16) -> DecodedAudio {
+    let samples = (0..frames)
+        .flat_map(|i| {
+            let value = (i as f32 * 1000.0 * std::f32::consts::TAU / 8000.0).sin() * 0.5;
+            std::iter::repeat_n(value, usize::from(channels))
+        })
+        .collect();
+    DecodedAudio {
+        samples,
+        sample_rate: 8000,
+        channels,
+    }
+}
+
+#[test]
+fn test_wav_rejects_header_overflow() {
+    let mut audio = tone(10, 2);
+    audio.sample_rate = u32::MAX;
+    assert!(encode_wav(&audio).is_err());
+
+    audio.sample_rate = 0;
+    assert!(encode_wav(&audio).is_err());
+
+    let mut audio = tone(1, u16::MAX);
+    audio.sample_rate = 8000;
+    assert!(encode_wav(&audio).is_err());
+}
+
+#[test]
+fn test_wav_round_trip() {
+    let audio = tone(4000, 2);
+    let wav = encode_wav(&audio).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(wav.len(), 44 + 4000 * 2 * 2);
+
+    let mut file = tempfile::Builder::new().suffix(".wav").tempfile().unwrap();
+    file.write_all(&wav).unwrap();
+    let decoded = decode_audio(file.path()).unwrap();
+
+    assert_eq!(decoded.sample_rate, 8000);
+    assert_eq!(decoded.channels, 2);
+    assert_eq!(decoded.frames(), 4000);
+    for (a, b) in decoded.samples.iter().zip(&audio.samples) {
+        assert!((a - b).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn test_decode_audio_rejects_non_audio() {
+    let mut file = tempfile::Builder::new().suffix(".wav").tempfile().unwrap();
+    file.write_all(b"not audio at all").unwrap();
+
+    let error = decode_audio(file.path()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_spectrogram_peaks_at_tone() {
+    let spec = spectrogram(&tone(8000, 1));
+
+    assert_eq!(spec.bins, 512);
+    assert_eq!(spec.hop, 256);
+    assert_eq!(spec.columns, 8000_usize.div_ceil(256));
+    assert_eq!(spec.data.len(), spec.columns * spec.bins);
+
+    // 1 kHz at 8 kHz is a quarter of the way up, counted from the bottom row
+    let column = &spec.data[10 * 512..11 * 512];
+    let loudest = (0..512).max_by_key(|&row| column[row]).unwrap();
+    assert_eq!(511 - loudest, 128);
+    assert!(column[loudest] > 250);
+
+    // Long audio is limited in columns rather than in detail per column
+    let long = spectrogram(&DecodedAudio {
+        samples: vec![0.0; 10_000_000],
+        sample_rate: 48_000,
+        channels: 1,
+    });
+    assert!(long.columns <= 8192);
+}
+
+#[test]
+fn test_audio_bundle_files() {
+    let files = audio_files(&tone(800, 1), true, &WindowTheme::Light).unwrap();
+
+    let html = String::from_utf8(files["index.html"].clone()).unwrap();
+    assert!(html.contains(r#""showSpectrogram":true"#));
+    assert!(html.contains(r#""sampleRate":8000"#));
+    assert!(html.contains("audio_selection"));
+    assert_eq!(files["audio.wav"].len(), 44 + 800 * 2);
+    assert_eq!(files["spectrogram.bin"].len(), 4 * 512);
+}
+
+/// Width and height attributes of an SVG.
+fn svg_size(svg: &str) -> (f64, f64) {
+    let attribute = |name: &str| -> f64 {
+        let start = svg.find(&format!(r#" {}=""#, name)).unwrap() + name.len() + 3;
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].parse().unwrap()
+    };
+    (attribute("width"), attribute("height"))
+}
+
+#[test]
+fn test_dot_diagram_nodes_edges_and_labels() {
+    let svg = diagram_svg(
+        r#"digraph G {
+            // Defaults apply to later nodes
+            node [shape=box];
+            idle -> running [label="start"];
+            running -> idle [label="stop", style=dashed];
+            running -> "failed <x>";
+            "failed <x>" [shape=doublecircle, color=red];
+        }"#,
+        DiagramKind::Dot,
+    )
+    .unwrap();
+
+    assert_eq!(svg.matches(r#"<g class="node">"#).count(), 3);
+    assert_eq!(svg.matches(r#"<g class="edge">"#).count(), 3);
+    assert!(svg.contains("<title>idle → running</title>"));
+    assert!(svg.contains("failed &lt;x&gt;"));
+    assert!(svg.contains(">start</tspan>"));
+    assert!(svg.contains("stroke-dasharray"));
+    assert!(svg.contains("stroke:red;"));
+}
+
+#[test]
+fn test_dot_rankdir_and_records() {
+    let source = "digraph { a -> b -> c -> d; }";
+    let (width, height) = svg_size(&diagram_svg(source, DiagramKind::Dot).unwrap());
+    assert!(height > width);
+
+    let source = "digraph { rankdir=LR; a -> b -> c -> d; }";
+    let (width, height) = svg_size(&diagram_svg(source, DiagramKind::Dot).unwrap());
+    assert!(width > height);
+
+    let svg = diagram_svg(
+        r#"digraph { s [shape=record, label="{name|size}"]; s -> s; }"#,
+        DiagramKind::Dot,
+    )
+    .unwrap();
+    assert!(svg.contains(">name</tspan>"));
+    assert!(svg.contains(">size</tspan>"));
+}
+
+#[test]
+fn test_dot_cycles_and_subgraphs() {
+    let svg = diagram_svg(
+        "strict graph { a -- b -- c -- a; subgraph cluster_x { d; e } a -- {d e}; }",
+        DiagramKind::Dot,
+    )
+    .unwrap();
+    assert_eq!(svg.matches(r#"<g class="node">"#).count(), 5);
+    assert_eq!(svg.matches(r#"<g class="edge">"#).count(), 5);
+    // Undirected edges have no arrowheads
+    assert!(!svg.contains("<polygon points="));
+}
+
+#[test]
+fn test_dot_syntax_error_reports_line() {
+    let error = diagram_svg("digraph {\n  a -> b;\n  c -> ;\n}", DiagramKind::Dot).unwrap_err();
+    assert_eq!(error.line, 3);
+    assert!(error.to_string().starts_with("line 3: "));
+}
+
+#[test]
+fn test_mermaid_flowchart() {
+    let svg = diagram_svg(
+        "flowchart LR\n  A[Start] --> B{Ready?}\n  B -- yes --> C([Go])\n  B -->|no| A\n  C -.-> D((Done))\n  style D fill:#f9f\n",
+        DiagramKind::Mermaid,
+    )
+    .unwrap();
+
+    assert_eq!(svg.matches(r#"<g class="node">"#).count(), 4);
+    assert_eq!(svg.matches(r#"<g class="edge">"#).count(), 4);
+    assert!(svg.contains(">Ready?</tspan>"));
+    assert!(svg.contains(">yes</tspan>"));
+    assert!(svg.contains(">no</tspan>"));
+    assert!(svg.contains("<circle"));
+    assert!(svg.contains("fill:#f9f;"));
+    let (width, height) = svg_size(&svg);
+    assert!(width > height);
+}
+
+#[test]
+fn test_mermaid_state_diagram() {
+    let svg = diagram_svg(
+        "stateDiagram-v2\n  [*] --> Still\n  Still --> Moving : push\n  Moving --> Still\n  Moving --> [*]\n",
+        DiagramKind::Mermaid,
+    )
+    .unwrap();
+    // Start and end states are distinct nodes
+    assert_eq!(svg.matches(r#"<g class="node">"#).count(), 4);
+    assert!(svg.contains(">push</tspan>"));
+    assert!(svg.contains(r#"class="dot""#));
+    assert!(svg.contains(r#"class="ring""#));
+}
+
+#[test]
+fn test_mermaid_unsupported_diagram() {
+    let error = diagram_svg("sequenceDiagram\n  A->>B: hi", DiagramKind::Mermaid).unwrap_err();
+    assert_eq!(error.line, 1);
+    assert!(error.message.contains("sequenceDiagram"));
+}
+
+#[test]
+fn test_diagram_page_saves_svg() {
+    let html = render_diagram(
+        "graph TD\n  A --> B",
+        DiagramKind::Mermaid,
+        &WindowTheme::Dark,
+    )
+    .unwrap();
+    assert!(html.contains(r#"<svg xmlns="http://www.w3.org/2000/svg" class="diagram""#));
+    assert!(html.contains("export_file"));
+    assert!(html.contains("diagram.svg"));
+}
+
+#[test]
+fn test_tab_page_fetches_tabs() {
+    let html = render_tabs(&WindowTheme::Dark);
+    assert!(html.contains(r#"<html data-theme="dark">"#));
+    assert!(html.contains(r#"<div id="strip" role="tablist">"#));
+    assert!(html.contains("invoke('tab_list')"));
+    assert!(html.contains("window.__htmlViewTabs"));
+    assert!(html.contains("tab_selected"));
+    assert!(html.contains("tab_closed"));
+}
+
+#[test]
+fn test_layout_page_places_cells() {
+    let layout = Layout::grid(2, 3)
+        .cell(0, 0, "a")
+        .span(2, 1)
+        .cell(1, 2, "b");
+    let srcs = [
+        "bundle://localhost/cell/0/index.html".to_string(),
+        "https://example.com/?a=1&b=2".to_string(),
+    ];
+    let html = render_layout(&layout, &srcs, &WindowTheme::Light);
+    assert!(html.contains(r#"<html data-theme="light">"#));
+    assert!(html.contains("grid-template-rows: repeat(2, minmax(0, 1fr))"));
+    assert!(html.contains("grid-template-columns: repeat(3, minmax(0, 1fr))"));
+    assert!(html.contains(
+        r#"src="bundle://localhost/cell/0/index.html" style="grid-area: 1 / 1 / span 2 / span 1""#
+    ));
+    assert!(html.contains(
+        r#"src="https://example.com/?a=1&amp;b=2" style="grid-area: 2 / 3 / span 1 / span 1""#
+    ));
+    assert!(html.contains("window.__htmlViewLayout"));
+}
+
+#[test]
+fn test_history_page_fetches_versions() {
+    let html = render_history(&WindowTheme::Light);
+    assert!(html.contains(r#"<html data-theme="light">"#));
+    assert!(html.contains(r#"<input id="timeline" type="range""#));
+    assert!(html.contains("invoke('history_list')"));
+    assert!(html.contains("window.__htmlViewHistory"));
+    assert!(html.contains(r#"<button id="pin""#));
+}
+
+#[test]
+fn test_slide_page_embeds_sources() {
+    let srcs = ["bundle://localhost/slide/0/index.html".to_string()];
+    let html = render_slides(&srcs, Some(2.0), &WindowTheme::Dark);
+    assert!(html.contains(r#"<html data-theme="dark">"#));
+    assert!(html.contains(
+        r#"<script id="slides" type="application/json">["bundle://localhost/slide/0/index.html"]</script>"#
+    ));
+    assert!(html.contains(r#"<script id="advance" type="application/json">2.0</script>"#));
+    assert!(html.contains("slide_shown"));
+    assert!(html.contains("toggle_fullscreen"));
+
+    // Advancing needs a positive interval
+    for advance in [None, Some(0.0), Some(f64::NAN)] {
+        let html = render_slides(&srcs, advance, &WindowTheme::Dark);
+        assert!(html.contains(r#"<script id="advance" type="application/json">null</script>"#));
+    }
+}
@@ -16,6 +16,8 @@ fn test_viewer_request_roundtrip() {
         environment: EnvironmentOptions::default(),
         dialog: DialogOptions::default(),
         command_path: None,
+        event_path: None,
+        stream_path: None,
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -102,6 +104,9 @@ fn test_viewer_exit_status_roundtrip() {
         id: Uuid::new_v4(),
         reason: ViewerExitReason::ClosedByUser,
         viewer_version: "0.1.0".to_string(),
+        geometry: None,
+        audio_selection: None,
+        slide: None,
     };
 
     let json = serde_json::to_string(&status).unwrap();
@@ -140,13 +145,30 @@ fn test_viewer_exit_reason_error() {
 fn test_window_options_defaults() {
     let opts = WindowOptions::default();
     assert_eq!(opts.title, Some("HTML Viewer".to_string()));
-    assert_eq!(opts.width, Some(1024));
-    assert_eq!(opts.height, Some(768));
+    // Unset sizes fall back to a restored size, a percentage or the default
+    assert_eq!(opts.width, None);
+    assert_eq!(opts.height, None);
+    assert_eq!(DEFAULT_WINDOW_SIZE, (1024, 768));
     assert!(opts.resizable);
     assert!(!opts.maximised);
     assert!(!opts.fullscreen);
 }
 
+#[test]
+fn test_window_options_check_percentages() {
+    let mut opts = WindowOptions {
+        width_percent: Some(100.0),
+        height_percent: Some(0.5),
+        ..Default::default()
+    };
+    assert!(opts.check().is_ok());
+
+    for invalid in [0.0, -10.0, 100.5, f64::NAN, f64::INFINITY] {
+        opts.height_percent = Some(invalid);
+        assert!(opts.check().is_err(), "{} was accepted", invalid);
+    }
+}
+
 #[test]
 fn test_behaviour_options_defaults() {
     let opts = BehaviourOptions::default();
@@ -154,6 +176,24 @@ fn test_behaviour_options_defaults() {
     assert!(!opts.enable_devtools);
     assert!(!opts.allow_remote_content);
     assert_eq!(opts.allowed_domains, None);
+    assert_eq!(opts.refresh_history, None);
+}
+
+#[test]
+fn test_refresh_history_serialization() {
+    let opts = BehaviourOptions {
+        refresh_history: Some(20),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&opts).unwrap();
+    assert!(json.ends_with(r#""refresh_history":20}"#));
+
+    // Requests written by older libraries keep no history
+    let opts: BehaviourOptions = serde_json::from_str(
+        r#"{"allow_external_navigation":false,"allowed_domains":null,"enable_devtools":false,"allow_remote_content":false,"allow_notifications":false}"#,
+    )
+    .unwrap();
+    assert_eq!(opts.refresh_history, None);
 }
 
 #[test]
@@ -174,3 +214,685 @@ fn test_protocol_version_constant() {
     assert!(!PROTOCOL_VERSION.is_empty());
     assert!(PROTOCOL_VERSION.contains('.'));
 }
+
+#[test]
+fn test_viewer_command_navigate_roundtrip() {
+    let command = ViewerCommand::Navigate {
+        seq: 3,
+        url: Url::parse("https://example.com/page").unwrap(),
+    };
+
+    let json = serde_json::to_string(&command).unwrap();
+    let deserialized: ViewerCommand = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized.seq(), 3);
+    match deserialized {
+        ViewerCommand::Navigate { url, .. } => {
+            assert_eq!(url.as_str(), "https://example.com/page");
+        }
+        _ => panic!("Expected Navigate"),
+    }
+}
+
+#[test]
+fn test_viewer_command_reload_defaults_to_cached() {
+    let json = r#"{"type":"reload","seq":1}"#;
+    let deserialized: ViewerCommand = serde_json::from_str(json).unwrap();
+
+    match deserialized {
+        ViewerCommand::Reload { ignore_cache, .. } => assert!(!ignore_cache),
+        _ => panic!("Expected Reload"),
+    }
+}
+
+#[test]
+fn test_viewer_command_response_backward_compatibility() {
+    // Responses from older viewers carry no data field
+    let json = r#"{"seq":7,"success":true,"error":null}"#;
+    let deserialized: ViewerCommandResponse = serde_json::from_str(json).unwrap();
+
+    assert_eq!(deserialized.seq, 7);
+    assert!(deserialized.data.is_none());
+}
+
+#[test]
+fn test_viewer_state_roundtrip() {
+    let state = ViewerState {
+        geometry: WindowGeometry {
+            x: 10,
+            y: 20,
+            width: 800,
+            height: 600,
+            physical_x: 20,
+            physical_y: 40,
+            physical_width: 1600,
+            physical_height: 1200,
+            scale_factor: 2.0,
+            monitor: Some("DP-1".to_string()),
+            maximised: false,
+            fullscreen: false,
+        },
+        focused: true,
+        visible: true,
+        title: "Report".to_string(),
+        url: Url::parse("file:///tmp/index.html").unwrap(),
+        load_state: LoadState::Loaded,
+    };
+
+    let response = ViewerCommandResponse {
+        seq: 1,
+        success: true,
+        error: None,
+        data: Some(ViewerCommandData::State {
+            state: state.clone(),
+        }),
+    };
+
+    let json = serde_json::to_string(&response).unwrap();
+    let deserialized: ViewerCommandResponse = serde_json::from_str(&json).unwrap();
+
+    match deserialized.data {
+        Some(ViewerCommandData::State { state: s }) => assert_eq!(s, state),
+        _ => panic!("Expected State"),
+    }
+}
+
+#[test]
+fn test_viewer_exit_status_without_geometry() {
+    let json = r#"{"id":"550e8400-e29b-41d4-a716-446655440000","reason":{"reason":"closed_by_user"},"viewer_version":"0.2.2"}"#;
+    let deserialized: ViewerExitStatus = serde_json::from_str(json).unwrap();
+
+    assert!(deserialized.geometry.is_none());
+}
+
+#[test]
+fn test_window_options_state_key_backward_compatibility() {
+    let mut value = serde_json::to_value(WindowOptions::default()).unwrap();
+    value.as_object_mut().unwrap().remove("state_key");
+
+    let deserialized: WindowOptions = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized.state_key, None);
+}
+
+#[test]
+fn test_window_placement_roundtrip() {
+    let opts = WindowOptions {
+        center: Some(WindowCenter::Parent {
+            x: 100,
+            y: 50,
+            width: 1200,
+            height: 900,
+        }),
+        monitor: Some(MonitorSelector::Name("DP-1".to_string())),
+        width_percent: Some(60.0),
+        min_width: Some(400),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&opts).unwrap();
+    let deserialized: WindowOptions = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized.center, opts.center);
+    assert_eq!(deserialized.monitor, opts.monitor);
+    assert_eq!(deserialized.width_percent, Some(60.0));
+    assert_eq!(deserialized.min_width, Some(400));
+    assert_eq!(deserialized.max_width, None);
+}
+
+#[test]
+fn test_viewer_event_serialization() {
+    let event = ViewerEvent::LoadFailed {
+        url: Some(Url::parse("file:///missing.html").unwrap()),
+        error: "not found".to_string(),
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+    assert!(json.contains(r#""type":"load_failed""#));
+
+    let deserialized: ViewerEvent = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, event);
+
+    let shown: ViewerEvent = serde_json::from_str(r#"{"type":"window_shown"}"#).unwrap();
+    assert_eq!(shown, ViewerEvent::WindowShown);
+}
+
+#[test]
+fn test_page_event_serialization() {
+    let console: ViewerEvent =
+        serde_json::from_str(r#"{"type":"console","level":"warn","message":"careful"}"#).unwrap();
+    assert_eq!(
+        console,
+        ViewerEvent::Console {
+            level: ConsoleLevel::Warn,
+            message: "careful".to_string(),
+        }
+    );
+
+    let error = ViewerEvent::PageError {
+        kind: PageErrorKind::UnhandledRejection,
+        message: "boom".to_string(),
+        source: None,
+        line: None,
+        column: None,
+        stack: Some("Error: boom\n    at main.js:1:1".to_string()),
+    };
+    let json = serde_json::to_string(&error).unwrap();
+    assert!(json.contains(r#""kind":"unhandled_rejection""#));
+
+    let deserialized: ViewerEvent = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, error);
+}
+
+#[test]
+fn test_image_bytes_serialize_as_base64() {
+    let content = ViewerContent::Image {
+        source: ImageSource::Bytes(vec![0x89, b'P', b'N', b'G']),
+        mime: Some("image/png".to_string()),
+    };
+
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.contains(r#""source":{"bytes":"iVBORw=="}"#));
+
+    let deserialized: ViewerContent = serde_json::from_str(&json).unwrap();
+    match deserialized {
+        ViewerContent::Image { source, mime } => {
+            assert_eq!(source, ImageSource::Bytes(vec![0x89, b'P', b'N', b'G']));
+            assert_eq!(mime.as_deref(), Some("image/png"));
+        }
+        _ => panic!("Wrong variant"),
+    }
+}
+
+#[test]
+fn test_bundle_serialization() {
+    let mut files = std::collections::HashMap::new();
+    files.insert("index.html".to_string(), b"<h1>Hi</h1>".to_vec());
+    files.insert("fonts/icon.woff2".to_string(), vec![0, 159, 146, 150]);
+    let content = ViewerContent::Bundle {
+        entry: "index.html".to_string(),
+        files: files.clone(),
+    };
+
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.contains(r#""type":"bundle""#));
+    assert!(json.contains(r#""fonts/icon.woff2":"AJ+Slg==""#));
+
+    match serde_json::from_str(&json).unwrap() {
+        ViewerContent::Bundle {
+            entry,
+            files: deserialized,
+        } => {
+            assert_eq!(entry, "index.html");
+            assert_eq!(deserialized, files);
+        }
+        _ => panic!("Wrong variant"),
+    }
+}
+
+#[test]
+fn test_template_defaults_and_update_data() {
+    let content: ViewerContent =
+        serde_json::from_str(r#"{"type":"template","template":"<p>{{ x }}</p>"}"#).unwrap();
+    match content {
+        ViewerContent::Template { data, engine, .. } => {
+            assert!(data.is_null());
+            assert_eq!(engine, TemplateEngine::Minijinja);
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let command = ViewerCommand::UpdateData {
+        seq: 7,
+        data: serde_json::json!({ "x": 1 }),
+    };
+    let json = serde_json::to_string(&command).unwrap();
+    assert!(json.contains(r#""type":"update_data""#));
+    assert_eq!(
+        serde_json::from_str::<ViewerCommand>(&json).unwrap().seq(),
+        7
+    );
+}
+
+#[test]
+fn test_table_source_serialization() {
+    let source = TableSource::Rows {
+        columns: vec!["a".to_string()],
+        rows: vec![vec![serde_json::json!(1)]],
+    };
+    let json = serde_json::to_string(&ViewerContent::Table {
+        source: source.clone(),
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"table","source":{"rows":{"columns":["a"],"rows":[[1]]}}}"#
+    );
+    match serde_json::from_str::<ViewerContent>(&json).unwrap() {
+        ViewerContent::Table { source: parsed } => assert_eq!(parsed, source),
+        _ => panic!("Wrong variant"),
+    }
+
+    let content: ViewerContent =
+        serde_json::from_str(r#"{"type":"table","source":{"file":{"path":"runs.tsv"}}}"#).unwrap();
+    match content {
+        ViewerContent::Table {
+            source: TableSource::File { path, delimiter },
+        } => {
+            assert_eq!(path, PathBuf::from("runs.tsv"));
+            assert_eq!(delimiter, None);
+        }
+        _ => panic!("Wrong variant"),
+    }
+}
+
+#[test]
+fn test_code_and_diff_defaults() {
+    let content: ViewerContent =
+        serde_json::from_str(r#"{"type":"code","source":"x = 1"}"#).unwrap();
+    match content {
+        ViewerContent::Code {
+            language,
+            line_numbers,
+            highlight_lines,
+            ..
+        } => {
+            assert_eq!(language, None);
+            assert!(line_numbers);
+            assert!(highlight_lines.is_empty());
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let content = ViewerContent::Diff {
+        old: "a".to_string(),
+        new: "b".to_string(),
+        mode: DiffMode::SideBySide,
+        language: None,
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.contains(r#""mode":"side_by_side""#));
+    match serde_json::from_str(r#"{"type":"diff","old":"a","new":"b"}"#).unwrap() {
+        ViewerContent::Diff { mode, .. } => assert_eq!(mode, DiffMode::Unified),
+        _ => panic!("Wrong variant"),
+    }
+}
+
+#[test]
+fn test_append_log_serialization() {
+    let command = ViewerCommand::AppendLog {
+        seq: 3,
+        entries: vec![
+            LogEntry::Text("INFO started".to_string()),
+            LogEntry::Html("<b>done</b>".to_string()),
+        ],
+    };
+    let json = serde_json::to_string(&command).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"append_log","seq":3,"entries":[{"text":"INFO started"},{"html":"<b>done</b>"}]}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<ViewerCommand>(&json).unwrap().seq(),
+        3
+    );
+
+    let content: ViewerContent = serde_json::from_str(r#"{"type":"log"}"#).unwrap();
+    assert!(matches!(content, ViewerContent::Log { max_lines: None }));
+}
+
+#[test]
+fn test_audio_serialization() {
+    let content = ViewerContent::Audio {
+        source: AudioSource::Samples {
+            samples: vec![0.0, 0.5, -1.0],
+            sample_rate: 8000,
+            channels: 1,
+        },
+        spectrogram: true,
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    // Samples travel as base64 of little-endian f32s
+    assert!(json.contains(r#""samples":"AAAAAAAAAD8AAIC/""#));
+    match serde_json::from_str(&json).unwrap() {
+        ViewerContent::Audio {
+            source,
+            spectrogram,
+        } => {
+            assert_eq!(source, AudioSource::mono(vec![0.0, 0.5, -1.0], 8000));
+            assert!(spectrogram);
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let content: ViewerContent =
+        serde_json::from_str(r#"{"type":"audio","source":{"path":"a.wav"}}"#).unwrap();
+    match content {
+        ViewerContent::Audio {
+            source,
+            spectrogram,
+        } => {
+            assert_eq!(source, AudioSource::Path(PathBuf::from("a.wav")));
+            assert!(!spectrogram);
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let event = ViewerEvent::SelectionChanged {
+        selection: Some(AudioSelection {
+            start: 1.5,
+            end: 2.0,
+        }),
+    };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"selection_changed","selection":{"start":1.5,"end":2.0}}"#
+    );
+    assert_eq!(serde_json::from_str::<ViewerEvent>(&json).unwrap(), event);
+
+    // Exit statuses written by older viewers have no selection
+    let status: ViewerExitStatus = serde_json::from_str(&format!(
+        r#"{{"id":"{}","reason":{{"reason":"closed_by_user"}},"viewer_version":"0.2.0"}}"#,
+        Uuid::nil()
+    ))
+    .unwrap();
+    assert_eq!(status.audio_selection, None);
+}
+
+#[test]
+fn test_diagram_serialization() {
+    let content = ViewerContent::Diagram {
+        source: "graph TD\n  A --> B".to_string(),
+        kind: DiagramKind::Mermaid,
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.contains(r#""type":"diagram""#));
+    assert!(json.contains(r#""kind":"mermaid""#));
+    match serde_json::from_str(&json).unwrap() {
+        ViewerContent::Diagram { source, kind } => {
+            assert_eq!(source, "graph TD\n  A --> B");
+            assert_eq!(kind, DiagramKind::Mermaid);
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    // DOT is the default
+    let content: ViewerContent =
+        serde_json::from_str(r#"{"type":"diagram","source":"digraph { a -> b }"}"#).unwrap();
+    assert!(matches!(
+        content,
+        ViewerContent::Diagram {
+            kind: DiagramKind::Dot,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_tabs_serialization() {
+    let content = ViewerContent::Tabs {
+        tabs: vec![
+            (
+                "Summary".to_string(),
+                ViewerContent::Markdown {
+                    source: "# Run".to_string(),
+                    base_dir: None,
+                },
+            ),
+            (
+                "Log".to_string(),
+                ViewerContent::AnsiText {
+                    text: "ok".to_string(),
+                },
+            ),
+        ],
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.starts_with(r#"{"type":"tabs","tabs":[["Summary",{"type":"markdown""#));
+    match serde_json::from_str(&json).unwrap() {
+        ViewerContent::Tabs { tabs } => {
+            assert_eq!(tabs.len(), 2);
+            assert_eq!(tabs[1].0, "Log");
+            assert!(matches!(tabs[1].1, ViewerContent::AnsiText { .. }));
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let commands = [
+        ViewerCommand::AddTab {
+            seq: 1,
+            id: "Extra".to_string(),
+            content: ViewerContent::Json {
+                value: serde_json::json!([1, 2]),
+            },
+        },
+        ViewerCommand::SelectTab {
+            seq: 2,
+            id: "Extra".to_string(),
+        },
+        ViewerCommand::RefreshTab {
+            seq: 3,
+            id: "Extra".to_string(),
+            content: ViewerContent::Json {
+                value: serde_json::json!([3]),
+            },
+        },
+        ViewerCommand::CloseTab {
+            seq: 4,
+            id: "Extra".to_string(),
+        },
+    ];
+    for (command, kind) in
+        commands
+            .iter()
+            .zip(["add_tab", "select_tab", "refresh_tab", "close_tab"])
+    {
+        let json = serde_json::to_string(command).unwrap();
+        assert!(json.contains(&format!(r#""type":"{}""#, kind)));
+        assert!(json.contains(r#""id":"Extra""#));
+        assert_eq!(
+            serde_json::from_str::<ViewerCommand>(&json).unwrap().seq(),
+            command.seq()
+        );
+    }
+}
+
+#[test]
+fn test_layout_serialization() {
+    let content = ViewerContent::Layout {
+        layout: Layout::grid(2, 2)
+            .cell(0, 0, "<p>top</p>")
+            .span(1, 2)
+            .cell(1, 1, "<p>right</p>"),
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.starts_with(r#"{"type":"layout","layout":{"rows":2,"columns":2,"cells":["#));
+    assert!(json.contains(r#""column_span":2"#));
+    match serde_json::from_str(&json).unwrap() {
+        ViewerContent::Layout { layout } => {
+            assert_eq!(layout.cells.len(), 2);
+            assert_eq!(layout.position(1, 1), Some(1));
+            assert!(matches!(
+                layout.cells[0].content,
+                ViewerContent::InlineHtml { .. }
+            ));
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    // Spans default to one cell
+    let cell: LayoutCell =
+        serde_json::from_str(r#"{"row":0,"column":1,"content":{"type":"ansi_text","text":"ok"}}"#)
+            .unwrap();
+    assert_eq!((cell.row_span, cell.column_span), (1, 1));
+
+    let command = ViewerCommand::RefreshCell {
+        seq: 5,
+        row: 1,
+        column: 1,
+        content: ViewerContent::AnsiText {
+            text: "done".to_string(),
+        },
+    };
+    let json = serde_json::to_string(&command).unwrap();
+    assert!(json.starts_with(r#"{"type":"refresh_cell","seq":5,"row":1,"column":1"#));
+    assert_eq!(
+        serde_json::from_str::<ViewerCommand>(&json).unwrap().seq(),
+        5
+    );
+}
+
+#[test]
+fn test_layout_stacks_and_check() {
+    let row = Layout::hstack(["a", "b", "c"]);
+    assert_eq!((row.rows, row.columns), (1, 3));
+    assert_eq!(row.position(0, 2), Some(2));
+    let column = Layout::vstack(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!((column.rows, column.columns), (2, 1));
+    assert_eq!(column.position(1, 0), Some(1));
+    assert!(row.check().is_ok());
+
+    // A cell placed again replaces the first
+    let replaced = Layout::grid(1, 1).cell(0, 0, "old").cell(0, 0, "new");
+    assert_eq!(replaced.cells.len(), 1);
+    assert!(matches!(
+        &replaced.cells[0].content,
+        ViewerContent::InlineHtml { html, .. } if html == "new"
+    ));
+
+    let outside = Layout::grid(2, 2).cell(0, 1, "x").span(1, 2).check();
+    assert!(
+        outside
+            .unwrap_err()
+            .contains("does not fit in the 2x2 grid")
+    );
+    let overlap = Layout::grid(2, 2)
+        .cell(0, 0, "x")
+        .span(2, 1)
+        .cell(1, 0, "y")
+        .check();
+    assert!(overlap.unwrap_err().contains("overlaps"));
+    assert!(Layout::grid(0, 3).check().is_err());
+    assert!(
+        Layout::grid(1, 1)
+            .cell(0, 0, "x")
+            .span(0, 1)
+            .check()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_slides_serialization() {
+    let content = ViewerContent::Slides {
+        slides: vec![
+            ViewerContent::AnsiText {
+                text: "epoch 1".to_string(),
+            },
+            ViewerContent::AnsiText {
+                text: "epoch 2".to_string(),
+            },
+        ],
+        advance_seconds: Some(1.5),
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.starts_with(r#"{"type":"slides","slides":[{"type":"ansi_text""#));
+    assert!(json.ends_with(r#""advance_seconds":1.5}"#));
+    match serde_json::from_str(r#"{"type":"slides","slides":[]}"#).unwrap() {
+        ViewerContent::Slides {
+            slides,
+            advance_seconds,
+        } => {
+            assert!(slides.is_empty());
+            assert_eq!(advance_seconds, None);
+        }
+        _ => panic!("Wrong variant"),
+    }
+
+    let event = ViewerEvent::SlideChanged { index: 3 };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(json, r#"{"type":"slide_changed","index":3}"#);
+    assert_eq!(serde_json::from_str::<ViewerEvent>(&json).unwrap(), event);
+
+    // Exit statuses written by older viewers have no slide
+    let status: ViewerExitStatus = serde_json::from_str(&format!(
+        r#"{{"id":"{}","reason":{{"reason":"closed_by_user"}},"viewer_version":"0.2.0"}}"#,
+        Uuid::nil()
+    ))
+    .unwrap();
+    assert_eq!(status.slide, None);
+}
+
+#[test]
+fn test_stream_serialization() {
+    let message = StreamMessage {
+        seq: 7,
+        frame: StreamFrame::Html {
+            html: "<b>7</b>".to_string(),
+        },
+    };
+    let json = serde_json::to_string(&message).unwrap();
+    assert_eq!(
+        json,
+        r#"{"seq":7,"frame":{"type":"html","html":"<b>7</b>"}}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<StreamMessage>(&json).unwrap(),
+        message
+    );
+
+    let data: StreamMessage =
+        serde_json::from_str(r#"{"seq":0,"frame":{"type":"data","data":[1,2]}}"#).unwrap();
+    assert_eq!(
+        data.frame,
+        StreamFrame::Data {
+            data: serde_json::json!([1, 2])
+        }
+    );
+
+    let event: ViewerEvent =
+        serde_json::from_str(r#"{"type":"stream_stats","applied":58,"dropped":3,"fps":59.5}"#)
+            .unwrap();
+    assert_eq!(
+        event,
+        ViewerEvent::StreamStats {
+            applied: 58,
+            dropped: 3,
+            fps: 59.5
+        }
+    );
+
+    // Requests written by older libraries have no stream file
+    let request: ViewerRequest = serde_json::from_str(&format!(
+        r#"{{"id":"{}","content":{{"type":"inline_html","html":"","base_dir":null}},"window":{},"behaviour":{},"environment":{},"dialog":{}}}"#,
+        Uuid::nil(),
+        serde_json::to_string(&WindowOptions::default()).unwrap(),
+        serde_json::to_string(&BehaviourOptions::default()).unwrap(),
+        serde_json::to_string(&EnvironmentOptions::default()).unwrap(),
+        serde_json::to_string(&DialogOptions::default()).unwrap(),
+    ))
+    .unwrap();
+    assert_eq!(request.stream_path, None);
+}
+
+#[test]
+fn test_send_binary_serialization() {
+    let command = ViewerCommand::SendBinary {
+        seq: 9,
+        channel: "samples".to_string(),
+        path: PathBuf::from("/tmp/binary_1.bin"),
+    };
+    let json = serde_json::to_string(&command).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"send_binary","seq":9,"channel":"samples","path":"/tmp/binary_1.bin"}"#
+    );
+    match serde_json::from_str(&json).unwrap() {
+        ViewerCommand::SendBinary { seq, channel, path } => {
+            assert_eq!(seq, 9);
+            assert_eq!(channel, "samples");
+            assert_eq!(path, PathBuf::from("/tmp/binary_1.bin"));
+        }
+        _ => panic!("Wrong variant"),
+    }
+}